@@ -1,6 +1,6 @@
 """
 rsubmitter 构建脚本
-使用方法: python build.py <输出目录>
+使用方法: python build.py <输出目录> [--oj luogu,vjudge]
 """
 
 """
@@ -11,14 +11,19 @@ License, v. 2.0. If a copy of the MPL was not distributed with this
 file, You can obtain one at https://mozilla.org/MPL/2.0/.
 """
 
+import os
 import sys
+import json
 import shutil
 import subprocess
 import argparse
 from pathlib import Path
 
-def build_wasm(output_dir: str):
-    """ 构建 WASM 模块到指定输出目录的 wasm 子目录中 """
+# 提取器清单 (由 extractor/build.rs 生成)
+MANIFEST_CACHE = Path("target/extractors.json").resolve()
+
+def build_wasm(output_dir: str, ojs: list[str] | None = None):
+    """ 构建 WASM 模块到指定输出目录的 wasm 子目录中, 指定 ojs 时仅打包对应提取器 """
     # 将输出目录转换为绝对路径
     output_path = Path(output_dir).resolve()
     wasm_output = output_path / "wasm"
@@ -26,9 +31,16 @@ def build_wasm(output_dir: str):
     
     print(f"构建 WASM 到 {wasm_output}...")
     
+    # 提取器清单由 extractor 的 build.rs 写出, 与构建缓存一同存放在 target 下
+    env = dict(os.environ, RSUBMITTER_MANIFEST_OUT=str(MANIFEST_CACHE))
+    command = ["wasm-pack", "build", "--release", "--target", "web", "--out-dir", str(wasm_output)]
+    if ojs:
+        print(f"  仅包含提取器: {', '.join(ojs)}")
+        command += ["--", "--no-default-features", "--features", ",".join(ojs)]
     result = subprocess.run(
-        ["wasm-pack", "build", "--release", "--target", "web", "--out-dir", str(wasm_output)],
-        cwd="runtime"
+        command,
+        cwd="runtime",
+        env=env
     )
     
     if result.returncode != 0:
@@ -66,23 +78,43 @@ def copy_extension(output_dir: str):
         print(f"  已复制: {file}")
     
     print("扩展文件复制完成")
+    apply_manifest(output_path)
+
+def apply_manifest(output_path: Path):
+    """ 将提取器清单中的匹配模式合并到扩展 manifest 的 host_permissions """
+    manifest = output_path / "manifest.json"
+    if not MANIFEST_CACHE.exists() or not manifest.exists():
+        print("警告: 找不到提取器清单, 跳过匹配规则生成")
+        return
+    
+    shutil.copy2(MANIFEST_CACHE, output_path / "extractors.json")
+    matches = json.loads(MANIFEST_CACHE.read_text(encoding="utf-8"))["matches"]
+    data = json.loads(manifest.read_text(encoding="utf-8"))
+    permissions = data.setdefault("host_permissions", [])
+    for pattern in matches:
+        if pattern not in permissions:
+            permissions.append(pattern)
+    manifest.write_text(json.dumps(data, ensure_ascii=False, indent=4) + "\n", encoding="utf-8")
+    print(f"  已合并 {len(matches)} 条匹配规则")
 
 def main():
     parser = argparse.ArgumentParser(description="构建 Chrome 插件")
     parser.add_argument("output_dir", help="输出目录路径")
     parser.add_argument("--wasm-only", action="store_true", help="仅构建 WASM")
     parser.add_argument("--copy-only", action="store_true", help="仅复制扩展文件")
+    parser.add_argument("--oj", help="仅打包指定 oj 的提取器, 以逗号分隔, 如 luogu,vjudge")
     
     args = parser.parse_args()
+    ojs = [oj.strip() for oj in args.oj.split(",") if oj.strip()] if args.oj else None
     
     try:
         if args.wasm_only:
-            build_wasm(args.output_dir)
+            build_wasm(args.output_dir, ojs)
         elif args.copy_only:
             copy_extension(args.output_dir)
         else:
             # 默认执行完整构建
-            build_wasm(args.output_dir)
+            build_wasm(args.output_dir, ojs)
             copy_extension(args.output_dir)
             print(f"\n构建完成! 输出目录: {args.output_dir}")
             
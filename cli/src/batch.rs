@@ -0,0 +1,176 @@
+//! 批量提取与提交
+//!
+//! 输入为 URL 列表文件 (每行一项, `#` 开头为注释) 或保存的 HTML 文件所在目录,
+//! 以有限的并发处理各项, 结果按输入顺序输出.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use futures_util::stream::{self, StreamExt};
+use submitter::{SubmitOutcome, Submitter};
+
+use crate::Result;
+use crate::commands::App;
+
+/// 单项的处理结果
+#[derive(Debug)]
+pub enum ItemStatus {
+    /// 仅提取
+    Extracted {
+        oj: String,
+        pid: String,
+        rid: String,
+    },
+    Submitted {
+        rid: String,
+        record_id: Option<String>,
+    },
+    Duplicate {
+        rid: String,
+    },
+    Failed(crate::Error),
+}
+
+impl ItemStatus {
+    pub fn is_failed(&self) -> bool {
+        matches!(self, ItemStatus::Failed(_))
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            ItemStatus::Extracted { .. } => "ok",
+            ItemStatus::Submitted { .. } => "submitted",
+            ItemStatus::Duplicate { .. } => "duplicate",
+            ItemStatus::Failed(_) => "failed",
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            ItemStatus::Extracted { oj, pid, rid } => format!("{oj} {pid} #{rid}"),
+            ItemStatus::Submitted { rid, record_id } => {
+                format!("#{rid} -> {}", record_id.as_deref().unwrap_or("-"))
+            }
+            ItemStatus::Duplicate { rid } => format!("#{rid}"),
+            ItemStatus::Failed(e) => e.to_string(),
+        }
+    }
+}
+
+/// 读取待处理的项: 目录中的 `.html` / `.htm` 文件, 或列表文件中的各行
+pub fn items(source: &Path) -> Result<Vec<String>> {
+    if source.is_dir() {
+        let mut files = std::fs::read_dir(source)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        files.retain(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm")
+                })
+        });
+        files.sort();
+        return Ok(files
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect());
+    }
+    Ok(parse_list(&std::fs::read_to_string(source)?))
+}
+
+/// 拆分列表文件, 忽略空行与注释
+fn parse_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 处理单项, 指定 `submitter` 时推送到 7fa4
+async fn process(
+    app: &App,
+    item: &str,
+    submitter: Option<&Submitter>,
+    in_contest: bool,
+) -> Result<ItemStatus> {
+    let report = app.extract(item, None).await?;
+    let sub = &report.submission;
+    let Some(submitter) = submitter else {
+        return Ok(ItemStatus::Extracted {
+            oj: sub.oj.clone(),
+            pid: sub.pid.clone(),
+            rid: sub.rid.clone(),
+        });
+    };
+    let status = match app.submit_report(submitter, &report, in_contest).await? {
+        SubmitOutcome::Duplicate { rid, .. } => ItemStatus::Duplicate { rid },
+        outcome => ItemStatus::Submitted {
+            rid: sub.rid.clone(),
+            record_id: outcome.record_id().map(str::to_string),
+        },
+    };
+    Ok(status)
+}
+
+/// 以至多 `jobs` 个并发处理全部项, 返回与输入顺序一致的结果
+pub async fn run(
+    app: &App,
+    items: &[String],
+    jobs: usize,
+    submitter: Option<&Submitter>,
+    in_contest: bool,
+) -> Vec<ItemStatus> {
+    stream::iter(items)
+        .map(|item| async move {
+            let status = process(app, item, submitter, in_contest).await;
+            status.unwrap_or_else(ItemStatus::Failed)
+        })
+        .buffered(jobs.max(1))
+        .collect()
+        .await
+}
+
+/// 逐项输出结果与汇总
+pub fn print_summary(items: &[String], statuses: &[ItemStatus]) {
+    let width = items
+        .iter()
+        .map(|item| item.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (item, status) in items.iter().zip(statuses) {
+        println!("{:<9}  {item:<width$}  {}", status.label(), status.detail());
+    }
+    let failed = statuses.iter().filter(|s| s.is_failed()).count();
+    println!(
+        "\n{} items, {} succeeded, {failed} failed",
+        statuses.len(),
+        statuses.len() - failed
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        let text =
+            "# luogu\nhttps://www.luogu.com.cn/record/1\n\n  https://vjudge.net/solution/2  \n";
+        assert_eq!(
+            parse_list(text),
+            [
+                "https://www.luogu.com.cn/record/1",
+                "https://vjudge.net/solution/2"
+            ]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! 子命令实现
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use extractor::ValidationPolicy;
+use extractor::models::{ExtractionReport, PreparedSubmission};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use submitter::{SubmitOutcome, Submitter, SubmitterConfig};
+
+use crate::config::Config;
+use crate::credentials::Credentials;
+use crate::{Error, Result};
+
+/// 子命令共用的客户端与凭据
+pub struct App {
+    pub credentials: Credentials,
+    pub client: fetcher::Client,
+    pub policy: ValidationPolicy,
+    submitter_config: SubmitterConfig,
+    /// 配置中的 7fa4 接口主机, 优先于凭据中的主机
+    chost: Option<String>,
+}
+
+impl App {
+    pub fn new(credentials: Credentials, config: &Config) -> Result<Self> {
+        let client = fetcher::Client::from_config(&config.fetcher)?;
+        credentials.apply(&client);
+        Ok(Self {
+            credentials,
+            client,
+            policy: ValidationPolicy::default(),
+            submitter_config: config.submitter.clone(),
+            chost: config.seven_fa4.cookies.chost.clone(),
+        })
+    }
+
+    /// 以保存的 7fa4 登录信息创建提交客户端
+    pub fn submitter(&self) -> Result<Submitter> {
+        let mut cookies = self
+            .credentials
+            .seven_fa4()
+            .ok_or(submitter::Error::MissingCredentials)?;
+        if self.chost.is_some() {
+            cookies.chost.clone_from(&self.chost);
+        }
+        Ok(Submitter::from_config(cookies, &self.submitter_config)?
+            .with_policy(self.policy.clone()))
+    }
+
+    /// 提取 URL 或保存的 HTML 文件
+    ///
+    /// 文件未以 `url` 指明提交记录地址时, 取浏览器保存页面时留下的来源地址.
+    pub async fn extract(&self, input: &str, url: Option<&str>) -> Result<ExtractionReport> {
+        if is_url(input) {
+            return Ok(self.client.extract(input, &self.policy).await?);
+        }
+        self.extract_file(Path::new(input), url)
+    }
+
+    /// 提取保存的 HTML 文件, 自动处理压缩与非 UTF-8 编码
+    pub fn extract_file(&self, path: &Path, url: Option<&str>) -> Result<ExtractionReport> {
+        let bytes = std::fs::read(path)?;
+        let html = extractor::decode::decode_body(&bytes, None, None)?;
+        let url = match url {
+            Some(url) => url.to_string(),
+            None => saved_url(&html).ok_or_else(|| {
+                Error::Usage(format!(
+                    "cannot find the source url of `{}`, pass it with --url",
+                    path.display()
+                ))
+            })?,
+        };
+        Ok(extractor::extract_with_report(&url, &html, &self.policy)?)
+    }
+
+    /// 推送提取结果, 接口拒绝时报错
+    pub async fn submit_report(
+        &self,
+        submitter: &Submitter,
+        report: &ExtractionReport,
+        in_contest: bool,
+    ) -> Result<SubmitOutcome> {
+        match submitter.submit(&report.submission, in_contest).await? {
+            SubmitOutcome::Rejected(response) => Err(Error::Rejected {
+                status: response.status_code,
+                message: response.message.unwrap_or_default(),
+            }),
+            outcome => Ok(outcome),
+        }
+    }
+
+    /// 提取并推送到 7fa4
+    pub async fn submit(
+        &self,
+        submitter: &Submitter,
+        url: &str,
+        in_contest: bool,
+    ) -> Result<SubmitOutcome> {
+        let report = self.extract(url, None).await?;
+        self.submit_report(submitter, &report, in_contest).await
+    }
+
+    /// 提取并生成将要发送的请求, 不实际提交
+    pub async fn prepare(
+        &self,
+        submitter: &Submitter,
+        url: &str,
+        in_contest: bool,
+    ) -> Result<PreparedSubmission> {
+        let report = self.extract(url, None).await?;
+        Ok(submitter.prepare(&report.submission, in_contest)?)
+    }
+}
+
+// 浏览器 "另存为" 留下的来源注释, 以及页面声明的规范地址
+static SAVED_URL_REGEXES: Lazy<[Regex; 3]> = Lazy::new(|| {
+    [
+        Regex::new(r"<!--\s*saved from url=\(\d+\)(\S+?)\s*-->").unwrap(),
+        Regex::new(r#"<link[^>]+rel=["']canonical["'][^>]+href=["']([^"']+)["']"#).unwrap(),
+        Regex::new(r#"<meta[^>]+property=["']og:url["'][^>]+content=["']([^"']+)["']"#).unwrap(),
+    ]
+});
+
+/// 保存的页面中记录的来源地址
+pub fn saved_url(html: &str) -> Option<String> {
+    SAVED_URL_REGEXES.iter().find_map(|regex| {
+        regex
+            .captures(html)
+            .map(|caps| caps[1].to_string())
+            .filter(|url| is_url(url))
+    })
+}
+
+/// 以 http(s) 开头的输入视为 URL, 其余视为文件路径
+pub fn is_url(input: &str) -> bool {
+    let input = input.trim_start().to_ascii_lowercase();
+    input.starts_with("http://") || input.starts_with("https://")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("https://www.luogu.com.cn/record/1"));
+        assert!(is_url("HTTP://vjudge.net/solution/1"));
+        assert!(!is_url("record.html"));
+        assert!(!is_url("./https/record.html"));
+    }
+
+    #[test]
+    fn test_saved_url() {
+        let html =
+            "<!-- saved from url=(0041)https://www.luogu.com.cn/record/241494617 -->\n<html>";
+        assert_eq!(
+            saved_url(html).as_deref(),
+            Some("https://www.luogu.com.cn/record/241494617")
+        );
+        let html = r#"<head><link rel="canonical" href="https://vjudge.net/solution/1"></head>"#;
+        assert_eq!(
+            saved_url(html).as_deref(),
+            Some("https://vjudge.net/solution/1")
+        );
+        assert_eq!(saved_url("<html></html>"), None);
+    }
+
+    #[test]
+    fn test_submitter_chost() {
+        let credentials = Credentials::from_json(
+            r#"[{"oj": "7fa4", "login": "u", "connect.sid": "s", "chost": "oj.7fa4.cn"}]"#,
+        )
+        .unwrap();
+        let config = Config::from_toml("[7fa4]\nchost = \"jx.7fa4.cn:8888\"").unwrap();
+        let app = App::new(credentials.clone(), &config).unwrap();
+        assert_eq!(app.submitter().unwrap().chost(), "jx.7fa4.cn:8888");
+
+        let app = App::new(credentials, &Config::default()).unwrap();
+        assert_eq!(app.submitter().unwrap().chost(), "oj.7fa4.cn");
+    }
+}
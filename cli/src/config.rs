@@ -0,0 +1,244 @@
+//! 统一配置
+//!
+//! 读取 `<配置目录>/rsubmitter/config.toml`, 各节分别交给提取器工厂, 获取器与提交客户端:
+//!
+//! ```toml
+//! [7fa4]
+//! chost = "jx.7fa4.cn:8888"
+//! login = "..."
+//! "connect.sid" = "..."
+//! deployments = [{ host = "test.7fa4.cn", port = 3000 }]
+//!
+//! [extractors]
+//! enabled = ["luogu", "vjudge"]
+//! aliases = { "luogu.example.com" = "luogu" }
+//!
+//! [overrides.luogu.selectors]
+//! code = "pre code"
+//!
+//! [fetcher]
+//! min_interval_ms = 500
+//!
+//! [[credentials]]
+//! oj = "luogu"
+//! __client_id = "..."
+//! _uid = "42"
+//! ```
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use extractor::FactoryConfig;
+use extractor::models::{CookieInfo, Credential, Deployment};
+use extractor::overrides::ExtractorOverrides;
+use fetcher::FetcherConfig;
+use serde::{Deserialize, Serialize};
+use submitter::SubmitterConfig;
+
+use crate::{Error, Result};
+
+/// 默认的配置文件路径
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rsubmitter").join("config.toml"))
+}
+
+/// 全部配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// 7fa4 接口与登录信息
+    #[serde(rename = "7fa4")]
+    pub seven_fa4: SevenFa4Config,
+    pub extractors: ExtractorsConfig,
+    /// 提取器名称 -> 选择器与正则覆盖
+    pub overrides: HashMap<String, ExtractorOverrides>,
+    pub fetcher: FetcherConfig,
+    pub submitter: SubmitterConfig,
+    pub cli: CliConfig,
+    /// oj 站点的登录凭据
+    pub credentials: Vec<Credential>,
+}
+
+/// 7fa4 接口与登录信息
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SevenFa4Config {
+    #[serde(flatten)]
+    pub cookies: CookieInfo,
+    /// 额外的部署, 优先于内置部署
+    pub deployments: Vec<Deployment>,
+}
+
+/// 提取器的启用与匹配
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtractorsConfig {
+    /// 参与 URL 匹配的提取器名称或标识, 缺省时为全部
+    pub enabled: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub factory: FactoryConfig,
+}
+
+impl ExtractorsConfig {
+    /// 工厂配置, 未启用的提取器并入 `disabled`, 启用未知的提取器时报错
+    fn factory_config(&self) -> Result<FactoryConfig> {
+        let mut config = self.factory.clone();
+        let Some(enabled) = &self.enabled else {
+            return Ok(config);
+        };
+        let extractors = extractor::list_extractors();
+        let matches = |entry: &str, info: &extractor::ExtractorInfo| {
+            entry.eq_ignore_ascii_case(info.name) || entry.eq_ignore_ascii_case(info.id)
+        };
+        if let Some(unknown) = enabled
+            .iter()
+            .find(|entry| !extractors.iter().any(|info| matches(entry, info)))
+        {
+            return Err(Error::Config(format!(
+                "extractors.enabled: unknown extractor `{unknown}`"
+            )));
+        }
+        config.disabled.extend(
+            extractors
+                .iter()
+                .filter(|info| !enabled.iter().any(|entry| matches(entry, info)))
+                .map(|info| info.name.to_string()),
+        );
+        Ok(config)
+    }
+}
+
+/// 命令行的默认参数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CliConfig {
+    /// 批量模式的并发数
+    pub jobs: usize,
+    /// 自动同步的轮询间隔 (秒)
+    pub watch_interval_secs: u64,
+}
+
+impl Default for CliConfig {
+    fn default() -> Self {
+        Self {
+            jobs: 4,
+            watch_interval_secs: 300,
+        }
+    }
+}
+
+impl Config {
+    /// 读取配置文件, 文件不存在时使用默认配置
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::from_toml(&text)
+                .map_err(|e| Error::Config(format!("{}: {e}", path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn from_toml(text: &str) -> std::result::Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// 将提取器相关的配置安装到全局的工厂, 部署表与覆盖表
+    pub fn apply(&self) -> Result<()> {
+        extractor::configure(self.extractors.factory_config()?);
+        // 部署表与覆盖表沿用扩展的 JSON 入口, 共用其校验
+        if !self.seven_fa4.deployments.is_empty() {
+            let json = serde_json::to_string(&self.seven_fa4.deployments)?;
+            extractor::models::load_deployments(&json)
+                .map_err(|e| Error::Config(format!("7fa4.deployments: {e}")))?;
+        }
+        if !self.overrides.is_empty() {
+            let json = serde_json::to_string(&self.overrides)?;
+            extractor::load_overrides(&json)
+                .map_err(|e| Error::Config(format!("overrides: {e}")))?;
+        }
+        Ok(())
+    }
+
+    /// 配置中的全部登录凭据, 7fa4 登录信息排在最后
+    pub fn credentials(&self) -> Vec<Credential> {
+        let mut credentials = self.credentials.clone();
+        let cookies = &self.seven_fa4.cookies;
+        if cookies.has_session() {
+            credentials.push(Credential::SevenFa4 {
+                login: cookies.login.clone(),
+                connect_sid: cookies.connect_sid.clone(),
+                chost: cookies.chost.clone(),
+            });
+        }
+        credentials
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let config = Config::from_toml(
+            r#"
+            [7fa4]
+            chost = "jx.7fa4.cn:8888"
+            login = "u"
+            "connect.sid" = "s"
+            deployments = [{ host = "test.7fa4.cn", port = 3000 }]
+
+            [extractors]
+            enabled = ["luogu"]
+            pinned = ["luogu"]
+            aliases = { "luogu.example.com" = "luogu" }
+
+            [overrides.luogu.selectors]
+            code = "pre code"
+
+            [fetcher]
+            min_interval_ms = 500
+
+            [[credentials]]
+            oj = "luogu"
+            __client_id = "abc"
+            _uid = "42"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.seven_fa4.cookies.chost(), "jx.7fa4.cn:8888");
+        assert_eq!(config.seven_fa4.deployments[0].chost(), "test.7fa4.cn:3000");
+        assert_eq!(config.extractors.factory.pinned, ["luogu"]);
+        assert_eq!(config.overrides["luogu"].selectors["code"], "pre code");
+        assert_eq!(config.fetcher.min_interval_ms, 500);
+        assert_eq!(config.fetcher.timeout_secs, fetcher::DEFAULT_TIMEOUT_SECS);
+        assert_eq!(config.cli.jobs, 4);
+
+        let credentials = config.credentials();
+        assert_eq!(credentials[0].oj(), "luogu");
+        assert_eq!(credentials[1].oj(), "7fa4");
+
+        let factory = config.extractors.factory_config().unwrap();
+        assert!(!factory.disabled.iter().any(|name| name == "luogu"));
+        assert!(factory.disabled.iter().any(|name| name == "vj"));
+
+        // 按标识启用, VJudge 的名称为 `vj`
+        let config = Config::from_toml(r#"extractors.enabled = ["luogu", "vjudge"]"#).unwrap();
+        let factory = config.extractors.factory_config().unwrap();
+        assert!(!factory.disabled.iter().any(|name| name == "vj"));
+        let config = Config::from_toml(r#"extractors.enabled = ["vjudg"]"#).unwrap();
+        assert!(config.extractors.factory_config().is_err());
+
+        assert!(Config::from_toml("[unknown]").is_err());
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+    }
+}
@@ -0,0 +1,102 @@
+//! 登录凭据文件
+//!
+//! 文件内容为 [`Credential`] 数组, 与扩展的 `parse_credential` 输出一致, 如
+//! `[{ "oj": "7fa4", "login": "...", "connect.sid": "...", "chost": "oj.7fa4.cn" }]`.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::{Path, PathBuf};
+
+use extractor::models::{CookieInfo, Credential};
+
+use crate::{Error, Result};
+
+/// 默认的凭据文件路径
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("rsubmitter").join("credentials.json"))
+}
+
+/// 已保存的各站点凭据
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Credentials {
+    sites: Vec<Credential>,
+}
+
+impl Credentials {
+    /// 读取凭据文件, 文件不存在时为空
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Self::from_json(&json)
+                .map_err(|e| Error::Config(format!("{}: {e}", path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(Self {
+            sites: serde_json::from_str(json)?,
+        })
+    }
+
+    /// 追加凭据, 同一站点以后追加的为准
+    pub fn extend(&mut self, credentials: impl IntoIterator<Item = Credential>) {
+        self.sites.extend(credentials);
+    }
+
+    /// 7fa4 登录信息, 重复时取最后一条
+    pub fn seven_fa4(&self) -> Option<CookieInfo> {
+        self.sites
+            .iter()
+            .rev()
+            .find_map(|credential| match credential {
+                Credential::SevenFa4 {
+                    login,
+                    connect_sid,
+                    chost,
+                } => Some(CookieInfo {
+                    login: login.clone(),
+                    connect_sid: connect_sid.clone(),
+                    chost: chost.clone(),
+                }),
+                _ => None,
+            })
+    }
+
+    /// 将 oj 站点的凭据加入客户端的 cookie jar
+    pub fn apply(&self, client: &fetcher::Client) {
+        for credential in &self.sites {
+            if !matches!(credential, Credential::SevenFa4 { .. }) {
+                client.add_credential(credential);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials() {
+        let credentials = Credentials::from_json(
+            r#"[
+                {"oj": "luogu", "__client_id": "abc", "_uid": "42"},
+                {"oj": "7fa4", "login": "u", "connect.sid": "s", "chost": "jx.7fa4.cn:8888"}
+            ]"#,
+        )
+        .unwrap();
+        let info = credentials.seven_fa4().unwrap();
+        assert_eq!(info.chost(), "jx.7fa4.cn:8888");
+        assert_eq!(info.cookie_header(), "login=u; connect.sid=s");
+
+        assert!(Credentials::default().seven_fa4().is_none());
+        assert!(Credentials::from_json(r#"[{"oj": "unknown"}]"#).is_err());
+    }
+}
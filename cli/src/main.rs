@@ -0,0 +1,285 @@
+//! rsubmitter 命令行
+//!
+//! 在浏览器之外提取 oj 提交记录并同步到 7fa4, 便于脚本化使用.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+mod batch;
+mod commands;
+mod config;
+mod credentials;
+mod watch;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use submitter::SubmitOutcome;
+use tracing_subscriber::EnvFilter;
+
+use commands::App;
+use config::Config;
+use credentials::Credentials;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// 命令行错误
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0}")]
+    Usage(String),
+    #[error("config error: {0}")]
+    Config(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Fetch(#[from] fetcher::Error),
+    #[error(transparent)]
+    Submit(#[from] submitter::Error),
+    #[error(transparent)]
+    Extract(#[from] extractor::error::Error),
+    #[error("record list error: {0}")]
+    List(String),
+    #[error("7fa4 rejected the submission (HTTP {status}): {message}")]
+    Rejected { status: u16, message: String },
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "rsubmitter", version, about = "提取 oj 提交记录并同步到 7fa4")]
+struct Cli {
+    /// 配置文件, 缺省为 `<配置目录>/rsubmitter/config.toml`
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// 凭据文件, 缺省为 `<配置目录>/rsubmitter/credentials.json`
+    #[arg(long, global = true)]
+    credentials: Option<PathBuf>,
+
+    /// 输出调试日志
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// 提取提交记录并以 JSON 输出
+    Extract {
+        /// 提交记录 URL 或保存的 HTML 文件
+        input: String,
+        /// HTML 文件对应的提交记录 URL
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// 下载并提取提交记录, 推送到 7fa4
+    Submit {
+        /// 提交记录 URL
+        url: String,
+        /// 比赛中的提交
+        #[arg(long)]
+        in_contest: bool,
+        /// 只输出将要发送的请求, 不实际提交
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 批量处理 URL 列表文件或保存的 HTML 文件所在目录, 有失败项时以非零状态退出
+    Batch {
+        /// URL 列表文件 (每行一项) 或 HTML 文件目录
+        source: PathBuf,
+        /// 同时处理的项数, 缺省取配置中的 `cli.jobs`
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        /// 提取后推送到 7fa4
+        #[arg(long)]
+        submit: bool,
+        /// 比赛中的提交
+        #[arg(long)]
+        in_contest: bool,
+    },
+    /// 定期轮询用户的通过记录, 自动提交新记录到 7fa4
+    Watch {
+        /// 记录所在的 oj
+        #[arg(long)]
+        oj: watch::Source,
+        /// 用户编号 (洛谷 uid) 或用户名 (VJudge)
+        #[arg(long)]
+        user: String,
+        /// 轮询间隔 (秒), 缺省取配置中的 `cli.watch_interval_secs`
+        #[arg(long)]
+        interval: Option<u64>,
+        /// 状态文件, 缺省为 `<数据目录>/rsubmitter/watch-<oj>-<user>.json`
+        #[arg(long)]
+        state: Option<PathBuf>,
+        /// 首次运行时只记录已有的记录, 不提交
+        #[arg(long)]
+        skip_existing: bool,
+        /// 只轮询一次, 便于由 cron 等调度
+        #[arg(long)]
+        once: bool,
+    },
+    /// 运行各提取器的自检样例, 有失败项时以非零状态退出
+    Verify,
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+async fn run(cli: Cli) -> Result<ExitCode> {
+    let config = match cli.config.or_else(config::default_path) {
+        Some(path) => Config::load(&path)?,
+        None => Config::default(),
+    };
+    config.apply()?;
+    let mut credentials = match cli.credentials.or_else(credentials::default_path) {
+        Some(path) => Credentials::load(&path)?,
+        None => Credentials::default(),
+    };
+    // 配置文件中的凭据优先于凭据文件
+    credentials.extend(config.credentials());
+    let app = App::new(credentials, &config)?;
+
+    match cli.command {
+        Command::Extract { input, url } => {
+            let report = app.extract(&input, url.as_deref()).await?;
+            print_json(&report)?;
+        }
+        Command::Submit {
+            url,
+            in_contest,
+            dry_run,
+        } => {
+            let submitter = app.submitter()?;
+            if dry_run {
+                print_json(&app.prepare(&submitter, &url, in_contest).await?)?;
+                return Ok(ExitCode::SUCCESS);
+            }
+            match app.submit(&submitter, &url, in_contest).await? {
+                SubmitOutcome::Duplicate { oj, rid } => println!("{oj} {rid}: already submitted"),
+                outcome => println!(
+                    "{url}: submitted, record {}",
+                    outcome.record_id().unwrap_or("-")
+                ),
+            }
+        }
+        Command::Batch {
+            source,
+            jobs,
+            submit,
+            in_contest,
+        } => {
+            let items = batch::items(&source)?;
+            let submitter = submit.then(|| app.submitter()).transpose()?;
+            let jobs = jobs.unwrap_or(config.cli.jobs);
+            let statuses = batch::run(&app, &items, jobs, submitter.as_ref(), in_contest).await;
+            batch::print_summary(&items, &statuses);
+            if statuses.iter().any(batch::ItemStatus::is_failed) {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Watch {
+            oj,
+            user,
+            interval,
+            state,
+            skip_existing,
+            once,
+        } => {
+            let state_path = state
+                .or_else(|| watch::WatchState::default_path(oj, &user))
+                .ok_or_else(|| {
+                    Error::Usage("cannot locate the data directory, pass --state".into())
+                })?;
+            let submitter = app.submitter()?;
+            let watch = watch::Watch {
+                app: &app,
+                submitter: &submitter,
+                source: oj,
+                user: &user,
+                state_path,
+            };
+            run_watch(
+                &watch,
+                Duration::from_secs(interval.unwrap_or(config.cli.watch_interval_secs).max(1)),
+                skip_existing,
+                once,
+            )
+            .await?;
+        }
+        Command::Verify => {
+            let results = extractor::verify_all();
+            for result in &results {
+                match &result.error {
+                    Some(error) => println!("failed  {}: {error}", result.name),
+                    None => println!("ok      {}", result.name),
+                }
+            }
+            if !results.iter().all(extractor::VerifyResult::passed) {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// 轮询直到收到 Ctrl-C, 单次轮询失败只记录错误
+async fn run_watch(
+    watch: &watch::Watch<'_>,
+    interval: Duration,
+    skip_existing: bool,
+    once: bool,
+) -> Result<()> {
+    let mut state = watch::WatchState::load(&watch.state_path)?;
+    if skip_existing && state.seen.is_empty() {
+        let count = watch.mark_existing(&mut state).await?;
+        println!("marked {count} existing records as seen");
+    }
+    loop {
+        match watch.poll(&mut state).await {
+            Ok(count) => tracing::info!(count, "poll finished"),
+            Err(e) if once => return Err(e),
+            Err(e) => eprintln!("poll failed: {e}"),
+        }
+        if once {
+            return Ok(());
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+// 提取器的异步接口不要求 `Send`, 使用单线程运行时
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let default_level = if cli.verbose { "debug" } else { "warn" };
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level)),
+        )
+        .init();
+
+    match run(cli).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
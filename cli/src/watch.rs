@@ -0,0 +1,232 @@
+//! 自动同步
+//!
+//! 定期轮询用户在 oj 的通过记录列表, 将新出现的记录提取后推送到 7fa4.
+//! 已处理的记录保存在状态文件中, 重启后不会重复提交.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use extractor::models::SubmissionStatus;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use submitter::{SubmitOutcome, Submitter};
+
+use crate::commands::App;
+use crate::{Error, Result};
+
+/// 可轮询记录列表的 oj
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Source {
+    Luogu,
+    Vjudge,
+}
+
+impl Source {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Source::Luogu => "luogu",
+            Source::Vjudge => "vjudge",
+        }
+    }
+
+    /// 用户最近通过记录的列表地址
+    fn list_url(&self, user: &str) -> String {
+        match self {
+            Source::Luogu => {
+                format!("https://www.luogu.com.cn/record/list?user={user}&status=12&_contentOnly=1")
+            }
+            Source::Vjudge => format!(
+                "https://vjudge.net/status/data?draw=1&start=0&length=20&un={user}&OJId=All&res=1"
+            ),
+        }
+    }
+
+    /// 从列表接口的响应中取出记录编号, 按列表顺序 (新记录在前)
+    fn parse_list(&self, body: &str) -> Result<Vec<String>> {
+        let json: Value = serde_json::from_str(body)
+            .map_err(|e| Error::List(format!("{}: {e}", self.as_str())))?;
+        let (records, id_key) = match self {
+            Source::Luogu => (
+                json.pointer("/currentData/records/result")
+                    .or_else(|| json.pointer("/data/records/result")),
+                "id",
+            ),
+            Source::Vjudge => (json.get("data"), "runId"),
+        };
+        let records = records.and_then(Value::as_array).ok_or_else(|| {
+            Error::List(format!(
+                "{}: unexpected record list response",
+                self.as_str()
+            ))
+        })?;
+        Ok(records
+            .iter()
+            .filter_map(|record| match &record[id_key] {
+                Value::Number(n) => Some(n.to_string()),
+                Value::String(s) if !s.is_empty() => Some(s.clone()),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// 提交记录页面地址
+    fn record_url(&self, rid: &str) -> String {
+        match self {
+            Source::Luogu => format!("https://www.luogu.com.cn/record/{rid}"),
+            Source::Vjudge => format!("https://vjudge.net/solution/{rid}"),
+        }
+    }
+}
+
+/// 持久化的轮询状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchState {
+    /// 已处理的记录编号
+    pub seen: BTreeSet<String>,
+}
+
+impl WatchState {
+    /// 默认的状态文件路径, 每个 oj 与用户一个文件
+    pub fn default_path(source: Source, user: &str) -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| {
+            dir.join("rsubmitter")
+                .join(format!("watch-{}-{user}.json", source.as_str()))
+        })
+    }
+
+    /// 读取状态文件, 文件不存在时为空
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| Error::Config(format!("{}: {e}", path.display()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 先写入临时文件再替换, 避免中断时损坏状态
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+/// 轮询参数
+pub struct Watch<'a> {
+    pub app: &'a App,
+    pub submitter: &'a Submitter,
+    pub source: Source,
+    pub user: &'a str,
+    pub state_path: PathBuf,
+}
+
+impl Watch<'_> {
+    /// 只记录当前列表中的记录, 不提交
+    pub async fn mark_existing(&self, state: &mut WatchState) -> Result<usize> {
+        let rids = self.list().await?;
+        let count = rids.len();
+        state.seen.extend(rids);
+        state.save(&self.state_path)?;
+        Ok(count)
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        let url = self.source.list_url(self.user);
+        let page = self.app.client.fetch_page(&url).await?;
+        self.source.parse_list(&page.content)
+    }
+
+    /// 轮询一次, 返回本次提交的记录数
+    ///
+    /// 提取或提交失败的记录不计入状态, 下次轮询时重试.
+    pub async fn poll(&self, state: &mut WatchState) -> Result<usize> {
+        let oj = self.source.as_str();
+        let mut submitted = 0;
+        // 列表中新记录在前, 按提交时间顺序处理
+        for rid in self.list().await?.into_iter().rev() {
+            if state.seen.contains(&rid) {
+                continue;
+            }
+            let url = self.source.record_url(&rid);
+            match self.sync(&url).await {
+                Ok(Some(SubmitOutcome::Duplicate { .. })) => {
+                    println!("{oj} {rid}: already submitted")
+                }
+                Ok(Some(outcome)) => {
+                    submitted += 1;
+                    println!(
+                        "{oj} {rid}: submitted, record {}",
+                        outcome.record_id().unwrap_or("-")
+                    );
+                }
+                Ok(None) => tracing::info!(oj, rid, "skipped record that is not accepted"),
+                Err(e) => {
+                    eprintln!("{oj} {rid}: {e}");
+                    continue;
+                }
+            }
+            state.seen.insert(rid);
+            state.save(&self.state_path)?;
+        }
+        Ok(submitted)
+    }
+
+    /// 提取并提交单条记录, 未通过的记录返回 `None`
+    async fn sync(&self, url: &str) -> Result<Option<SubmitOutcome>> {
+        let report = self.app.extract(url, None).await?;
+        if report.submission.status != SubmissionStatus::Accepted {
+            return Ok(None);
+        }
+        let outcome = self
+            .app
+            .submit_report(self.submitter, &report, false)
+            .await?;
+        Ok(Some(outcome))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        let body =
+            r#"{"currentData": {"records": {"result": [{"id": 2}, {"id": 1}], "count": 2}}}"#;
+        assert_eq!(Source::Luogu.parse_list(body).unwrap(), ["2", "1"]);
+
+        let body = r#"{"data": [{"runId": 65377961, "status": "Accepted"}], "recordsTotal": 1}"#;
+        assert_eq!(Source::Vjudge.parse_list(body).unwrap(), ["65377961"]);
+
+        assert!(Source::Luogu.parse_list("<html></html>").is_err());
+        assert!(Source::Vjudge.parse_list("{}").is_err());
+    }
+
+    #[test]
+    fn test_state() {
+        let path = std::env::temp_dir()
+            .join(format!("rsubmitter-watch-{}", std::process::id()))
+            .join("state.json");
+        assert_eq!(WatchState::load(&path).unwrap(), WatchState::default());
+
+        let state = WatchState {
+            seen: BTreeSet::from(["1".to_string(), "2".to_string()]),
+        };
+        state.save(&path).unwrap();
+        assert_eq!(WatchState::load(&path).unwrap(), state);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
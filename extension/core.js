@@ -24,6 +24,9 @@ class RSubmitterCore {
                 module_or_path: chrome.runtime.getURL('wasm/runtime_bg.wasm')
             });
             this.wasmModule = module;
+            if (typeof module.set_observer === 'function') {
+                module.set_observer(w => this.recordUnknown(w));
+            }
             return module;
         } catch (e) {
             console.error('WASM 加载失败:', e);
@@ -32,7 +35,65 @@ class RSubmitterCore {
     }
 
     async init() {
-        await this.loadWasm();
+        const module = await this.loadWasm();
+        // 非中文环境下以英文输出运行时消息
+        if (typeof module.set_locale === 'function' && !navigator.language.startsWith('zh')) {
+            try { module.set_locale('en'); } catch (e) { }
+        }
+        // 自检失败的提取器多半是页面结构已变化, 仅提示不影响使用
+        if (typeof module.verify_extractors === 'function') {
+            for (const result of module.verify_extractors()) {
+                if (result.error) console.warn(`提取器 ${result.name} 自检失败:`, result.error);
+            }
+        }
+    }
+
+    // 收集无法识别的状态 / 语言文本, 便于后续扩充
+    async recordUnknown(warning) {
+        try {
+            const { unknownStrings = [] } = await chrome.storage.local.get('unknownStrings');
+            const key = `${warning.kind}:${warning.detail}`;
+            if (unknownStrings.includes(key)) return;
+            unknownStrings.push(key);
+            await chrome.storage.local.set({ unknownStrings: unknownStrings.slice(-100) });
+        } catch (e) {
+            console.warn('记录未知文本失败:', e);
+        }
+    }
+
+    // 已注册的提取器, 由 Rust 注册表提供
+    async listExtractors() {
+        const module = await this.loadWasm();
+        if (typeof module.list_extractors !== 'function') return [];
+        return module.list_extractors();
+    }
+
+    // 从拦截到的接口 JSON 提取, 旧版本 wasm 返回 null
+    async extractSubmissionJson(url, json) {
+        const module = await this.loadWasm();
+        if (typeof module.extract_submission_json !== 'function') return null;
+        return module.extract_submission_json(url, typeof json === 'string' ? json : JSON.stringify(json));
+    }
+
+    // 从同一提交的多个页面提取并合并, 旧版本 wasm 返回 null
+    async extractSubmissionMulti(url, htmls) {
+        const module = await this.loadWasm();
+        if (typeof module.extract_submission_multi !== 'function') return null;
+        return module.extract_submission_multi(url, htmls);
+    }
+
+    // 调整 wasm 控制台日志级别, 如 'debug'
+    async setLogLevel(level) {
+        const module = await this.loadWasm();
+        if (typeof module.set_log_level !== 'function') return false;
+        module.set_log_level(level);
+        return true;
+    }
+
+    async detectPage(url, html) {
+        const module = await this.loadWasm();
+        if (typeof module.detect_page !== 'function') return null;
+        return module.detect_page(url, html);
     }
 
     async getActiveTab() {
@@ -91,7 +152,7 @@ class RSubmitterCore {
 
             const extractResult = module.extract_submission(url, html);
 
-            if (!extractResult?.success || !extractResult?.partial) {
+            if (!extractResult?.success || !extractResult?.submission) {
                 return {
                     ok: false,
                     err: extractResult?.error || '无法提取提交信息',
@@ -102,18 +163,15 @@ class RSubmitterCore {
             // 可选: 若开启映射 VJudge 为来源并且提取器是 vjudge, 调用 wasm 的映射函数
             if (options.mapVjudge && extractResult.extractor_name && typeof module.map_vjudge_submission === 'function') {
                 const ename = (extractResult.extractor_name || '').toString().toLowerCase();
-                if (ename.includes('vj') && ename.includes('vjudge')) {
+                if (ename.includes('vj')) {
                     try {
-                        const mapped = module.map_vjudge_submission(extractResult.partial);
-                        if (mapped) {
-                            try {
-                                const arr = mapped;
-                                if (Array.isArray(arr) && arr.length >= 3) {
-                                    extractResult.partial.oj = arr[0];
-                                    extractResult.partial.pid = arr[1];
-                                    extractResult.partial.rid = arr[2];
-                                }
-                            } catch (e) { }
+                        const mapped = module.map_vjudge_submission(extractResult.submission, options.ojMap ?? null);
+                        if (mapped && typeof mapped === 'object') {
+                            extractResult.submission.oj = mapped.oj;
+                            extractResult.submission.pid = mapped.pid;
+                            if (mapped.rid) {
+                                extractResult.submission.rid = mapped.rid;
+                            }
                         }
                     } catch (e) { }
                 }
@@ -122,7 +180,7 @@ class RSubmitterCore {
             // 如果仅观察模式, 不发送请求, 仅输出到控制台
             if (options.observe) {
                 try {
-                    console.log('观测模式输出 submission:', extractResult.partial);
+                    console.log('观测模式输出 submission:', extractResult.submission);
                 } catch (e) {
                     // ignore
                 }
@@ -135,8 +193,29 @@ class RSubmitterCore {
                 };
             }
 
+            // 由 wasm 发送请求, 旧版本 wasm 回退到手动构建
+            if (typeof module.submit_to_7fa4 === 'function') {
+                try {
+                    const resp = await module.submit_to_7fa4(extractResult.submission, cookies, in_contest);
+                    return {
+                        ok: true,
+                        resp: resp,
+                        parsed: extractResult,
+                        businessSuccess: resp.success,
+                        statusCode: resp.status_code,
+                        recordId: resp.record_id
+                    };
+                } catch (e) {
+                    return {
+                        ok: false,
+                        err: e?.message || String(e),
+                        parsed: extractResult
+                    };
+                }
+            }
+
             // 构建请求
-            const request = this.buildRequest(extractResult.partial, cookies, in_contest);
+            const request = this.buildRequest(extractResult.submission, cookies, in_contest);
 
             if (!request) {
                 return {
@@ -205,7 +284,7 @@ class RSubmitterCore {
 
             const extractResult = module.extract_submission(url, html);
 
-            if (!extractResult?.success || !extractResult?.partial) {
+            if (!extractResult?.success || !extractResult?.submission) {
                 return {
                     ok: false,
                     err: extractResult?.error || '无法提取提交信息',
@@ -214,14 +293,14 @@ class RSubmitterCore {
             }
 
             try {
-                console.log('观测到的 submission:', extractResult.partial);
+                console.log('观测到的 submission:', extractResult.submission);
             } catch (e) {
                 // 忽略 console 输出错误
             }
 
             return {
                 ok: true,
-                submission: extractResult.partial,
+                submission: extractResult.submission,
                 parsed: extractResult
             };
         } catch (e) {
@@ -231,10 +310,15 @@ class RSubmitterCore {
 
     buildRequest(submission, cookies, in_contest) {
         try {
-            let body = JSON.parse(JSON.stringify(submission)); // 深拷贝
+            if (typeof this.wasmModule.build_submit_request === 'function') {
+                return this.wasmModule.build_submit_request(submission, cookies, in_contest);
+            }
 
-            // 添加 in_contest 字段
-            body.in_contest = in_contest;
+            // 由 wasm 生成 7fa4 接口格式的请求体
+            const body = this.wasmModule.to_7fa4_payload(submission, in_contest);
+            if (typeof body === 'string') {
+                throw new Error(body);
+            }
 
             const chost = cookies.chost || "oj.7fa4.cn";
             const target = `http://${chost}/foreign_oj`;
@@ -273,6 +357,17 @@ class RSubmitterCore {
         }
     }
 
+    // 向 7fa4 确认会话是否有效, 旧版本 wasm 返回 null
+    async checkLogin() {
+        const { cookies } = await chrome.storage.sync.get('cookies');
+        if (!cookies) {
+            return { logged_in: false, status_code: 0, user_id: null, username: null };
+        }
+        const module = await this.loadWasm();
+        if (typeof module.check_login !== 'function') return null;
+        return module.check_login(cookies);
+    }
+
     async isLoggedIn() {
         const status = await this.getLoginStatus();
         return status === '已登录';
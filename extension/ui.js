@@ -138,12 +138,25 @@ class RSubmitterUI {
 
                 if (response.ok) {
                     if (response.businessSuccess) {
-                        this.setStatus('提交成功！');
+                        const defaulted = (response.parsed?.warnings || [])
+                            .filter(w => w.kind === 'field_defaulted')
+                            .map(w => w.detail);
+                        this.setStatus(defaulted.length
+                            ? `提交成功！(未提取到: ${defaulted.join(', ')})`
+                            : '提交成功！');
                     } else {
                         const errorMsg = response.resp?.err || response.resp?.error ||
                             response.resp?.message || '服务器处理失败';
                         this.setStatus('提交失败: ' + errorMsg, true);
                     }
+                } else if (response.parsed?.error_code === 'judging_in_progress') {
+                    this.setStatus('评测尚未完成, 请稍后重试', true);
+                } else if (response.parsed?.error_code === 'not_authenticated') {
+                    this.setStatus('请先登录来源 OJ 后重试', true);
+                } else if (response.parsed?.error_code === 'permission_denied') {
+                    this.setStatus('无权查看该提交记录', true);
+                } else if (response.parsed?.error_code === 'layout_mismatch') {
+                    this.setStatus('暂不支持该页面版式, 请更新扩展', true);
                 } else {
                     let errorMessage = '发送失败: ';
 
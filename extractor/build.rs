@@ -0,0 +1,222 @@
+//! 自动收集提取器
+//!
+//! 扫描 `src/extractors/` 下的源文件, 为其中 `#[derive(Extractable)]` 的类型生成模块声明与 `registry_items()`,
+//! 并汇总 `#[extractor(...)]` 中的元数据生成提取器清单 (JSON). 每个源文件由同名 cargo feature 控制.
+//!
+//! 设置 `RSUBMITTER_MANIFEST_OUT` 时, 清单同时写入该路径, 供扩展构建脚本使用.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use serde_json::{Value, json};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+use syn::{Expr, Lit};
+
+const MANIFEST_OUT: &str = "RSUBMITTER_MANIFEST_OUT";
+
+/// `#[extractor(...)]` 中与清单相关的元数据
+#[derive(Default)]
+struct ExtractorMeta {
+    ty: String,
+    name: String,
+    id: Option<String>,
+    display_name: Option<String>,
+    homepage: Option<String>,
+    tags: Vec<String>,
+    hosts: Vec<String>,
+    domains: Vec<String>,
+    patterns: Vec<String>,
+}
+
+struct ExtractorModule {
+    name: String,
+    path: PathBuf,
+    extractors: Vec<ExtractorMeta>,
+}
+
+fn lit_str(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(lit) if let Lit::Str(s) = &lit.lit => Some(s.value()),
+        _ => None,
+    }
+}
+
+fn lit_list(expr: &Expr) -> Vec<String> {
+    match expr {
+        Expr::Array(array) => array.elems.iter().filter_map(lit_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 解析派生了 `Extractable` 的结构体, 属性格式错误交由宏报告
+fn extractor_meta(item: &syn::ItemStruct) -> Option<ExtractorMeta> {
+    let derives_extractable = item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .any(|attr| {
+            let mut found = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                found |= meta
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == "Extractable");
+                Ok(())
+            });
+            found
+        });
+    if !derives_extractable {
+        return None;
+    }
+
+    let mut meta = ExtractorMeta {
+        ty: item.ident.to_string(),
+        ..Default::default()
+    };
+    for attr in item.attrs.iter().filter(|a| a.path().is_ident("extractor")) {
+        let _ = attr.parse_nested_meta(|nested| {
+            let key = nested
+                .path
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+            let value: Expr = nested.value()?.parse()?;
+            match key.as_str() {
+                "name" => meta.name = lit_str(&value).unwrap_or_default(),
+                "id" => meta.id = lit_str(&value),
+                "display_name" => meta.display_name = lit_str(&value),
+                "homepage" => meta.homepage = lit_str(&value),
+                "tags" => meta.tags = lit_list(&value),
+                "hosts" => meta.hosts = lit_list(&value),
+                "domains" => meta.domains = lit_list(&value),
+                "patterns" => meta.patterns = lit_list(&value),
+                _ => {}
+            }
+            Ok(())
+        });
+    }
+    Some(meta)
+}
+
+fn scan(dir: &Path) -> Vec<ExtractorModule> {
+    let mut modules: Vec<_> = fs::read_dir(dir)
+        .expect("failed to read src/extractors")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            if path.extension()? != "rs" || name == "mod" {
+                return None;
+            }
+            // 语法错误留给编译器报告
+            let file = syn::parse_file(&fs::read_to_string(&path).ok()?).ok();
+            let extractors = file
+                .iter()
+                .flat_map(|file| &file.items)
+                .filter_map(|item| match item {
+                    syn::Item::Struct(item) => extractor_meta(item),
+                    _ => None,
+                })
+                .collect();
+            Some(ExtractorModule {
+                name,
+                path,
+                extractors,
+            })
+        })
+        .collect();
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+    modules
+}
+
+fn generate(modules: &[ExtractorModule]) -> String {
+    let mut out = String::new();
+    for module in modules {
+        let _ = writeln!(out, "#[cfg(feature = {:?})]", module.name);
+        let _ = writeln!(out, "#[path = {:?}]", module.path.display().to_string());
+        let _ = writeln!(out, "pub mod {};", module.name);
+    }
+
+    out.push_str(
+        "\n/// 收集注册的提取器, 由 build.rs 生成\n\
+         pub(crate) fn registry_items() -> Vec<crate::factory::ExtractorRegistryItem> {\n    vec![\n",
+    );
+    for module in modules {
+        for meta in &module.extractors {
+            let _ = writeln!(out, "        #[cfg(feature = {:?})]", module.name);
+            let _ = writeln!(
+                out,
+                "        {}::__EXTRACTOR_REGISTRY_{}(),",
+                module.name,
+                meta.ty.to_uppercase()
+            );
+        }
+    }
+    out.push_str("    ]\n}\n");
+    out
+}
+
+/// 浏览器扩展的匹配模式, 无法表示的通配符将被忽略
+fn match_patterns(host: &str) -> Vec<String> {
+    let host = host.trim().to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    match host.strip_prefix("*.") {
+        Some(rest) if !rest.contains('*') => vec![format!("*://*.{rest}/*")],
+        _ if host.contains('*') => Vec::new(),
+        _ => vec![format!("*://{host}/*"), format!("*://*.{host}/*")],
+    }
+}
+
+/// 已启用 feature 的提取器清单
+fn manifest(modules: &[ExtractorModule]) -> Value {
+    let enabled = modules.iter().filter(|module| {
+        let feature = module.name.to_uppercase().replace('-', "_");
+        env::var_os(format!("CARGO_FEATURE_{feature}")).is_some()
+    });
+
+    let mut matches = Vec::new();
+    let mut extractors = Vec::new();
+    for meta in enabled.flat_map(|module| &module.extractors) {
+        let hosts: Vec<&String> = meta.hosts.iter().chain(&meta.domains).collect();
+        for pattern in hosts.iter().flat_map(|host| match_patterns(host)) {
+            if !matches.contains(&pattern) {
+                matches.push(pattern);
+            }
+        }
+        extractors.push(json!({
+            "id": meta.id.as_deref().unwrap_or(&meta.name),
+            "name": meta.name,
+            "display_name": meta.display_name.as_deref().unwrap_or(&meta.name),
+            "homepage": meta.homepage,
+            "tags": meta.tags,
+            "hosts": hosts,
+            "patterns": meta.patterns,
+        }));
+    }
+    json!({ "extractors": extractors, "matches": matches })
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let dir = manifest_dir.join("src").join("extractors");
+    println!("cargo:rerun-if-changed={}", dir.display());
+    println!("cargo:rerun-if-env-changed={MANIFEST_OUT}");
+
+    let modules = scan(&dir);
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("extractors.rs"), generate(&modules))
+        .expect("failed to write extractors.rs");
+
+    let manifest = serde_json::to_string_pretty(&manifest(&modules)).unwrap();
+    fs::write(out_dir.join("manifest.json"), &manifest).expect("failed to write manifest.json");
+    if let Some(path) = env::var_os(MANIFEST_OUT) {
+        fs::write(&path, &manifest).expect("failed to write extractor manifest");
+    }
+}
@@ -0,0 +1,349 @@
+//! 声明式字段提取
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, Path};
+
+use crate::{check_regex, check_selector, parse_lit, parse_str, set_once};
+
+/// 支持声明的 `PartialSubmission` 字段
+const FIELDS: &[&str] = &[
+    "code",
+    "pid",
+    "rid",
+    "oj",
+    "language",
+    "status",
+    "total_time",
+    "max_memory",
+    "score",
+];
+
+/// 结构体上的 `#[layout(name = "...", detect = "...")]` 声明
+struct Layout {
+    name: String,
+    detect: String,
+}
+
+impl Layout {
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Vec<Self>> {
+        let mut layouts: Vec<Layout> = Vec::new();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("layout")) {
+            let mut name = None;
+            let mut detect = None;
+            attr.parse_nested_meta(|meta| {
+                let Some(key) = meta.path.get_ident().map(Ident::to_string) else {
+                    return Err(meta.error("expected an attribute name"));
+                };
+                match key.as_str() {
+                    "name" => set_once(&meta, &mut name, parse_str(&meta)?),
+                    "detect" => {
+                        let css = check_selector(&parse_lit(&meta)?)?;
+                        set_once(&meta, &mut detect, css)
+                    }
+                    _ => Err(meta.error(format!("unknown layout attribute `{key}`"))),
+                }
+            })?;
+            let (Some(name), Some(detect)) = (name, detect) else {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected `name = \"...\"` and `detect = \"...\"`",
+                ));
+            };
+            if layouts.iter().any(|l| l.name == name) {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!("duplicate layout `{name}`"),
+                ));
+            }
+            layouts.push(Layout { name, detect });
+        }
+        Ok(layouts)
+    }
+}
+
+/// 单个字段的 `#[field(...)]` 声明
+struct FieldSpec {
+    field: Ident,
+    /// 所属布局, `None` 表示适用于全部布局
+    layout: Option<String>,
+    source: Source,
+    regex: Option<String>,
+    parse_with: Option<Path>,
+}
+
+/// 字段文本来源
+enum Source {
+    /// 首个匹配元素的文本或属性
+    Selector { css: String, attr: Option<String> },
+    /// 固定值
+    Value(String),
+}
+
+impl FieldSpec {
+    /// 解析字段上的全部 `#[field(...)]`, 每个布局至多一个
+    fn from_field(field: &syn::Field, layouts: &[Layout]) -> syn::Result<Vec<Self>> {
+        let mut specs: Vec<Self> = Vec::new();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("field")) {
+            let spec = Self::from_attr(field, attr)?;
+            if let Some(layout) = &spec.layout
+                && !layouts.iter().any(|l| &l.name == layout)
+            {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!("unknown layout `{layout}`, declare it with `#[layout(...)]`"),
+                ));
+            }
+            if specs.iter().any(|s| s.layout == spec.layout) {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "duplicate `#[field(...)]` for the same layout",
+                ));
+            }
+            specs.push(spec);
+        }
+        Ok(specs)
+    }
+
+    fn from_attr(field: &syn::Field, attr: &syn::Attribute) -> syn::Result<Self> {
+        let ident = field.ident.clone().expect("named field");
+        if !FIELDS.contains(&ident.to_string().as_str()) {
+            return Err(syn::Error::new_spanned(
+                &ident,
+                format!("unsupported field `{ident}`, expected one of {FIELDS:?}"),
+            ));
+        }
+
+        let mut layout = None;
+        let mut selector = None;
+        let mut attr_name = None;
+        let mut value = None;
+        let mut regex = None;
+        let mut parse_with = None;
+        attr.parse_nested_meta(|meta| {
+            let Some(key) = meta.path.get_ident().map(Ident::to_string) else {
+                return Err(meta.error("expected an attribute name"));
+            };
+            match key.as_str() {
+                "layout" => set_once(&meta, &mut layout, parse_str(&meta)?),
+                "selector" => {
+                    let css = check_selector(&parse_lit(&meta)?)?;
+                    set_once(&meta, &mut selector, css)
+                }
+                "attr" => set_once(&meta, &mut attr_name, parse_str(&meta)?),
+                "value" => set_once(&meta, &mut value, parse_str(&meta)?),
+                "regex" => {
+                    let pattern = check_regex(&parse_lit(&meta)?)?;
+                    set_once(&meta, &mut regex, pattern)
+                }
+                "parse_with" => {
+                    let path = syn::parse_str::<Path>(&parse_str(&meta)?)
+                        .map_err(|e| meta.error(format!("invalid `parse_with` path: {e}")))?;
+                    set_once(&meta, &mut parse_with, path)
+                }
+                _ => Err(meta.error(format!("unknown field attribute `{key}`"))),
+            }
+        })?;
+
+        let source = match (selector, value) {
+            (Some(css), None) => Source::Selector {
+                css,
+                attr: attr_name,
+            },
+            (None, Some(value)) if attr_name.is_none() => Source::Value(value),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "expected exactly one of `selector = \"...\"` or `value = \"...\"`",
+                ));
+            }
+        };
+        Ok(Self {
+            field: ident,
+            layout,
+            source,
+            regex,
+            parse_with,
+        })
+    }
+
+    /// 生成提取并写入该字段的代码
+    fn generate(&self) -> TokenStream {
+        let text = match &self.source {
+            Source::Selector { css, attr: None } => {
+                quote! { crate::utils::select_text(&document, #css)? }
+            }
+            Source::Selector {
+                css,
+                attr: Some(attr),
+            } => quote! { crate::utils::select_attr(&document, #css, #attr)? },
+            Source::Value(value) => quote! { Some(#value.to_string()) },
+        };
+
+        // 有捕获组时取第一组, 否则取整个匹配
+        let regex = self.regex.as_ref().map(|pattern| {
+            quote! {
+                let text = text.and_then(|text| {
+                    static REGEX: once_cell::sync::Lazy<regex::Regex> =
+                        once_cell::sync::Lazy::new(|| regex::Regex::new(#pattern).unwrap());
+                    let caps = REGEX.captures(&text)?;
+                    caps.get(1)
+                        .or_else(|| caps.get(0))
+                        .map(|m| m.as_str().to_string())
+                });
+            }
+        });
+
+        let field = &self.field;
+        let assign = match &self.parse_with {
+            Some(path) => quote! { partial.#field = #path(&text); },
+            None => match field.to_string().as_str() {
+                "code" => quote! { partial.code = Some(crate::utils::normalize_code(&text)); },
+                "language" => quote! { partial.parse_language(&text); },
+                "status" => quote! { partial.parse_status(&text); },
+                "total_time" => quote! { partial.parse_time(&text); },
+                "max_memory" => quote! { partial.parse_memory(&text); },
+                "score" => quote! { partial.score = crate::utils::parse_score(&text); },
+                _ => quote! { partial.#field = Some(text); },
+            },
+        };
+
+        quote! {
+            {
+                let text: Option<String> = #text;
+                #regex
+                if let Some(text) = text {
+                    #assign
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "DeclarativeExtractor can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "DeclarativeExtractor expects named `#[field(...)]` fields",
+        ));
+    };
+
+    let layouts = Layout::from_attrs(&input.attrs)?;
+    let specs = fields
+        .named
+        .iter()
+        .map(|f| FieldSpec::from_field(f, &layouts))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // 各字段在指定布局下使用的声明: 优先取该布局专属的声明, 否则取通用声明
+    let blocks_for = |layout: Option<&str>| -> Vec<TokenStream> {
+        specs
+            .iter()
+            .filter_map(|field| {
+                field
+                    .iter()
+                    .find(|s| s.layout.is_some() && s.layout.as_deref() == layout)
+                    .or_else(|| field.iter().find(|s| s.layout.is_none()))
+            })
+            .map(FieldSpec::generate)
+            .collect()
+    };
+
+    let names: Vec<&str> = layouts.iter().map(|l| l.name.as_str()).collect();
+    let detects = layouts.iter().map(|l| &l.detect);
+    let extract = match layouts.is_empty() {
+        true => {
+            let blocks = blocks_for(None);
+            quote! { #(#blocks)* }
+        }
+        false => {
+            let arms = layouts.iter().map(|l| {
+                let name = &l.name;
+                let blocks = blocks_for(Some(name));
+                quote! { #name => { #(#blocks)* } }
+            });
+            quote! {
+                let Some(layout) = Self::declarative_layout(&document)? else {
+                    return Err(crate::error::ExtractErrorKind::LayoutMismatch {
+                        detected: None,
+                        expected: Self::DECLARED_LAYOUTS,
+                    }
+                    .into());
+                };
+                partial.layout = Some(layout.to_string());
+                match layout {
+                    #(#arms)*
+                    _ => unreachable!(),
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #name {
+            /// 按声明顺序排列的页面布局
+            const DECLARED_LAYOUTS: &'static [&'static str] = &[#(#names),*];
+
+            /// 首个 `detect` 选择器命中的布局
+            fn declarative_layout(
+                document: &scraper::Html,
+            ) -> crate::error::Result<Option<&'static str>> {
+                #(
+                    if document.select(&crate::utils::parse_selector(#detects)?).next().is_some() {
+                        return Ok(Some(#names));
+                    }
+                )*
+                let _ = document;
+                Ok(None)
+            }
+
+            /// 按 `#[field(...)]` 声明从 HTML 中提取
+            fn declarative_partial(
+                content: &str,
+            ) -> crate::error::Result<crate::models::PartialSubmission> {
+                let document = scraper::Html::parse_document(content);
+                let mut partial = crate::models::PartialSubmission::default();
+                #extract
+                Ok(partial)
+            }
+        }
+
+        impl crate::traits::Extractor for #name {
+            fn layouts(&self) -> &'static [&'static str] {
+                Self::DECLARED_LAYOUTS
+            }
+
+            fn detect_layout(&self, content: &str) -> Option<&'static str> {
+                Self::declarative_layout(&scraper::Html::parse_document(content))
+                    .ok()
+                    .flatten()
+            }
+
+            fn extract_partial(
+                &self,
+                _ctx: &crate::models::ExtractionContext<'_>,
+                input: &crate::models::ExtractorInput<'_>,
+            ) -> crate::error::Result<crate::models::PartialSubmission> {
+                if input.is_empty() {
+                    return Err(crate::error::ExtractErrorKind::EmptyContent.into());
+                }
+                Self::declarative_partial(input.html()?)
+            }
+        }
+    })
+}
@@ -8,16 +8,14 @@
 
 extern crate proc_macro;
 
-use once_cell::sync::Lazy;
+mod declarative;
+
 use proc_macro::TokenStream;
-use quote::ToTokens;
 use quote::{format_ident, quote};
-use regex::Regex;
-use syn::{Attribute, DeriveInput, parse_macro_input};
-
-static NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"name\s*=\s*\"([^\"]+)\""#).unwrap());
-static TAGS_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"tags\s*=\s*\[(?P<inner>[^\]]*)\]").unwrap());
+use syn::meta::ParseNestedMeta;
+use syn::{
+    Attribute, Data, DeriveInput, Expr, ExprArray, Ident, Lit, LitStr, UnOp, parse_macro_input,
+};
 
 /// 提取器属性
 ///
@@ -27,56 +25,175 @@ static TAGS_REGEX: Lazy<Regex> =
 ///
 /// ```ignore
 /// #[derive(Extractable)]
-/// #[extractor(name = "洛谷", tags = ["luogu", "Luogu"]) ]
+/// #[extractor(name = "luogu", display_name = "洛谷", tags = ["洛谷"], hosts = ["luogu.com.cn"])]
 /// pub struct LuoguExtractor;
 /// ```
 ///
 /// 支持的属性:
-/// - `name = "..."`: 提取器显示名称 (必须)
+/// - `name = "..."`: 提取器名称 (必须)
+/// - `id = "..."`: 稳定标识 (可选, 默认与名称相同)
+/// - `display_name = "..."`: 面向用户的显示名称 (可选, 默认与名称相同)
+/// - `homepage = "..."`: OJ 主页 (可选)
 /// - `tags = ["t1", "t2"]`: 用于基于 URL 的匹配标签 (可选)
+/// - `version = "..."`: 提取器版本 (可选, 默认为所在 crate 的版本)
+/// - `hosts = ["luogu.com.cn", "*.luogu.com.cn"]`: 域名通配符 (可选)
+/// - `domains = ["vjudge.net", "www.luogu.com.cn"]`: 精确域名, 得分高于通配符 (可选, 忽略 `www.` 前缀)
+/// - `paths = [r"^/record/\d+"]`: 域名命中后进一步匹配的路径正则 (可选)
+/// - `patterns = [r"^https?://(www\.)?luogu\.com\.cn/record/\d+"]`: 完整 URL 正则, 命中时得分最高 (可选)
+/// - `priority = 10`: 得分相同时优先级高者胜出 (可选, 默认为 0)
+/// - `constructor = path`: 构造提取器的无参函数 (可选, 带字段的结构体默认使用 `Default`)
+///
+/// 域名命中时得分远高于名称 / 标签的子串匹配, 子串匹配仅作为回退.
+/// `paths` 与 `patterns` 中的正则在宏展开期校验, 无效时报告编译错误.
 ///
-/// 该宏会为类型生成 `ExtractorRegistry` 的实现, 并把提取器注册到 `crate::factory::EXTRACTOR_REGISTRY` 分布式切片中.
+/// 该宏会为类型生成 `ExtractorRegistry` 的实现, 以及返回注册项的函数 `__EXTRACTOR_REGISTRY_<TYPE>()`;
+/// 构建脚本扫描 `src/extractors/` 并在生成的 `registry_items()` 中调用这些函数.
 #[derive(Debug)]
 struct ExtractorAttributes {
     name: String,
+    id: Option<String>,
+    display_name: Option<String>,
+    homepage: Option<String>,
     tags: Vec<String>,
+    version: Option<String>,
+    hosts: Vec<String>,
+    domains: Vec<String>,
+    paths: Vec<String>,
+    patterns: Vec<String>,
+    priority: i32,
+    constructor: Option<syn::Path>,
+}
+
+/// 解析 `key = "..."`
+fn parse_lit(meta: &ParseNestedMeta) -> syn::Result<LitStr> {
+    meta.value()?.parse::<LitStr>()
+}
+
+/// 解析 `key = "..."` 的字符串值
+fn parse_str(meta: &ParseNestedMeta) -> syn::Result<String> {
+    Ok(parse_lit(meta)?.value())
+}
+
+/// 在展开期编译正则, 出错时指向对应字面量
+fn check_regex(lit: &LitStr) -> syn::Result<String> {
+    let pattern = lit.value();
+    regex::Regex::new(&pattern)
+        .map_err(|e| syn::Error::new_spanned(lit, format!("invalid regex: {e}")))?;
+    Ok(pattern)
+}
+
+/// 在展开期解析 CSS 选择器, 出错时指向对应字面量
+fn check_selector(lit: &LitStr) -> syn::Result<String> {
+    let css = lit.value();
+    scraper::Selector::parse(&css)
+        .map_err(|e| syn::Error::new_spanned(lit, format!("invalid selector `{css}`: {e}")))?;
+    Ok(css)
+}
+
+/// 解析 `key = 10` 或 `key = -1`
+fn parse_int(meta: &ParseNestedMeta) -> syn::Result<i32> {
+    let expr: Expr = meta.value()?.parse()?;
+    let (negative, inner) = match &expr {
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => (true, unary.expr.as_ref()),
+        _ => (false, &expr),
+    };
+    match inner {
+        Expr::Lit(lit) if let Lit::Int(int) = &lit.lit => {
+            let value = int.base10_parse::<i32>()?;
+            Ok(if negative { -value } else { value })
+        }
+        _ => Err(syn::Error::new_spanned(
+            &expr,
+            "expected an integer literal",
+        )),
+    }
+}
+
+/// 解析形如 `key = ["a", r"b"]` 的字面量列表
+fn parse_lits(meta: &ParseNestedMeta) -> syn::Result<Vec<LitStr>> {
+    let array: ExprArray = meta.value()?.parse()?;
+    array
+        .elems
+        .iter()
+        .map(|elem| match elem {
+            Expr::Lit(lit) if let Lit::Str(s) = &lit.lit => Ok(s.clone()),
+            _ => Err(syn::Error::new_spanned(elem, "expected a string literal")),
+        })
+        .collect()
+}
+
+/// 解析字符串列表
+fn parse_list(meta: &ParseNestedMeta) -> syn::Result<Vec<String>> {
+    Ok(parse_lits(meta)?.iter().map(LitStr::value).collect())
+}
+
+/// 解析正则列表, 逐个校验
+fn parse_regex_list(meta: &ParseNestedMeta) -> syn::Result<Vec<String>> {
+    parse_lits(meta)?.iter().map(check_regex).collect()
+}
+
+/// 同一属性只允许出现一次
+fn set_once<T>(meta: &ParseNestedMeta, slot: &mut Option<T>, value: T) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(meta.error("duplicate extractor attribute"));
+    }
+    *slot = Some(value);
+    Ok(())
 }
 
 impl ExtractorAttributes {
-    fn from_attrs(attrs: &[Attribute]) -> Result<Self, syn::Error> {
-        // 使用简单的字符串解析方式 (兼容不同版本的 syn) :
-        // attr.tokens 的文本里包含形如: (name = "xxx", tags = ["a","b"]).
+    fn from_attrs(ident: &Ident, attrs: &[Attribute]) -> Result<Self, syn::Error> {
         let mut name = None;
-        let mut tags = Vec::new();
-
-        for attr in attrs {
-            if attr.path().is_ident("extractor") {
-                // 将 Attribute 转为 token 字符串以便用正则解析
-                let mut ts = proc_macro2::TokenStream::new();
-                attr.to_tokens(&mut ts);
-                let s = ts.to_string();
-                if name.is_none()
-                    && let Some(cap) = NAME_REGEX.captures(&s)
-                {
-                    name = Some(cap.get(1).unwrap().as_str().to_string());
-                }
-                if let Some(cap) = TAGS_REGEX.captures(&s) {
-                    let inner = cap.name("inner").unwrap().as_str();
-                    for part in inner.split(',') {
-                        let t = part.trim().trim_matches('"').trim().to_string();
-                        if !t.is_empty() {
-                            tags.push(t);
-                        }
-                    }
+        let mut id = None;
+        let mut display_name = None;
+        let mut homepage = None;
+        let mut tags = None;
+        let mut version = None;
+        let mut hosts = None;
+        let mut domains = None;
+        let mut paths = None;
+        let mut patterns = None;
+        let mut priority = None;
+        let mut constructor = None;
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("extractor")) {
+            attr.parse_nested_meta(|meta| {
+                let Some(key) = meta.path.get_ident().map(Ident::to_string) else {
+                    return Err(meta.error("expected an attribute name"));
+                };
+                match key.as_str() {
+                    "name" => set_once(&meta, &mut name, parse_str(&meta)?),
+                    "id" => set_once(&meta, &mut id, parse_str(&meta)?),
+                    "display_name" => set_once(&meta, &mut display_name, parse_str(&meta)?),
+                    "homepage" => set_once(&meta, &mut homepage, parse_str(&meta)?),
+                    "version" => set_once(&meta, &mut version, parse_str(&meta)?),
+                    "tags" => set_once(&meta, &mut tags, parse_list(&meta)?),
+                    "hosts" => set_once(&meta, &mut hosts, parse_list(&meta)?),
+                    "domains" => set_once(&meta, &mut domains, parse_list(&meta)?),
+                    "paths" => set_once(&meta, &mut paths, parse_regex_list(&meta)?),
+                    "patterns" => set_once(&meta, &mut patterns, parse_regex_list(&meta)?),
+                    "priority" => set_once(&meta, &mut priority, parse_int(&meta)?),
+                    "constructor" => set_once(&meta, &mut constructor, meta.value()?.parse()?),
+                    _ => Err(meta.error(format!("unknown extractor attribute `{key}`"))),
                 }
-            }
+            })?;
         }
 
         Ok(ExtractorAttributes {
             name: name.ok_or_else(|| {
-                syn::Error::new_spanned(attrs.first().unwrap(), "Missing required attribute 'name'")
+                syn::Error::new_spanned(ident, "missing `#[extractor(name = \"...\")]`")
             })?,
-            tags,
+            id,
+            display_name,
+            homepage,
+            tags: tags.unwrap_or_default(),
+            version,
+            hosts: hosts.unwrap_or_default(),
+            domains: domains.unwrap_or_default(),
+            paths: paths.unwrap_or_default(),
+            patterns: patterns.unwrap_or_default(),
+            priority: priority.unwrap_or_default(),
+            constructor,
         })
     }
 }
@@ -86,23 +203,61 @@ pub fn derive_extractable(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
-    let attrs = match ExtractorAttributes::from_attrs(&input.attrs) {
+    let attrs = match ExtractorAttributes::from_attrs(name, &input.attrs) {
         Ok(attrs) => attrs,
         Err(e) => return e.to_compile_error().into(),
     };
+    let construct = match generate_construct(&input, attrs.constructor.as_ref()) {
+        Ok(construct) => construct,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     let extractor_name = attrs.name;
     let tags = attrs.tags;
+    let version = match attrs.version {
+        Some(v) => quote! { #v },
+        None => quote! { env!("CARGO_PKG_VERSION") },
+    };
+    // 域名预检同时接受通配符与精确域名
+    let hosts: Vec<String> = attrs.hosts.iter().chain(&attrs.domains).cloned().collect();
+
+    // 子串回退关键字: 名称与标签, 与 `UrlMatcher` 的子串匹配一致
+    let keywords: Vec<String> = std::iter::once(extractor_name.to_lowercase())
+        .chain(tags.iter().map(|t| t.to_lowercase()))
+        .collect();
+
+    // 展示用的匹配规则: URL 正则与域名, 其后为子串回退关键字
+    let patterns: Vec<String> = attrs
+        .patterns
+        .iter()
+        .chain(&hosts)
+        .chain(&keywords)
+        .cloned()
+        .collect();
 
     // literal for extractor name
-    let extractor_name_lit = syn::LitStr::new(&extractor_name, proc_macro2::Span::call_site());
+    let extractor_name_lit = LitStr::new(&extractor_name, proc_macro2::Span::call_site());
 
     // 为每个提取器生成唯一的静态变量名 (全部大写以符合静态变量命名规范)
     let registry_item_name =
         format_ident!("__EXTRACTOR_REGISTRY_{}", name.to_string().to_uppercase());
 
     // 生成 rank 方法的实现
-    let rank_impl = generate_rank_impl(&extractor_name, &tags);
+    let rank_impl = generate_rank_impl(
+        &extractor_name,
+        &tags,
+        &attrs.hosts,
+        &attrs.domains,
+        &attrs.paths,
+        &attrs.patterns,
+    );
+    let priority = attrs.priority;
+    let id = attrs.id.as_deref().unwrap_or(&extractor_name);
+    let display_name = attrs.display_name.as_deref().unwrap_or(&extractor_name);
+    let homepage = match &attrs.homepage {
+        Some(h) => quote! { Some(#h) },
+        None => quote! { None },
+    };
 
     let expanded = quote! {
         impl crate::traits::ExtractorRegistry for #name {
@@ -111,21 +266,30 @@ pub fn derive_extractable(input: TokenStream) -> TokenStream {
             }
 
             fn new() -> Box<dyn crate::traits::Extractor> {
-                Box::new(Self {})
+                Box::new(#construct)
             }
         }
 
-        // 生成一个返回注册项的函数, 由手动注册表收集调用
+        // 生成一个返回注册项的函数, 由构建脚本生成的注册表收集调用
         #[allow(non_snake_case)]
         pub fn #registry_item_name() -> crate::factory::ExtractorRegistryItem {
             crate::factory::ExtractorRegistryItem {
                 // 提取器的显示名称 (由宏属性提供)
                 name_fn: || -> &'static str { #extractor_name_lit },
+                id: #id,
+                display_name: #display_name,
+                homepage: #homepage,
+                tags: &[#(#tags),*],
+                url_patterns: &[#(#patterns),*],
+                keywords: &[#(#keywords),*],
+                hosts: &[#(#hosts),*],
+                version: #version,
+                priority: #priority,
                 rank_fn: |url: &str| -> u32 {
                     #rank_impl
                 },
                 creator: || -> Box<dyn crate::traits::Extractor> {
-                    Box::new(#name {})
+                    Box::new(#construct)
                 },
             }
         }
@@ -134,34 +298,165 @@ pub fn derive_extractable(input: TokenStream) -> TokenStream {
     expanded.into()
 }
 
-/// 生成 rank 方法的实现
-fn generate_rank_impl(name: &str, tags: &[String]) -> proc_macro2::TokenStream {
-    let tag_checks: Vec<proc_macro2::TokenStream> = tags
-        .iter()
-        .map(|tag| {
-            let tag_lower = tag.to_lowercase();
-            let lit = syn::LitStr::new(&tag_lower, proc_macro2::Span::call_site());
-            quote! {
-                if url.to_lowercase().contains(#lit) {
-                    score += 10;
-                }
-            }
-        })
-        .collect();
+/// 基于样例文件的提取器回归测试
+///
+/// 生成一个 `#[test]`: 读取 `fixture` 页面, 按 `url` 选择提取器提取部分结果,
+/// 并逐个比较 `expect` JSON 对象中列出的字段. 路径相对于 crate 根目录.
+/// 被标注函数的函数体在比较之后执行, 可通过 `partial` 追加断言.
+///
+/// ```ignore
+/// #[extractor_test(
+///     url = "https://www.luogu.com.cn/record/1",
+///     fixture = "fixtures/luogu_record.html",
+///     expect = "fixtures/luogu_record.json"
+/// )]
+/// fn luogu_record() {}
+/// ```
+#[proc_macro_attribute]
+pub fn extractor_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut url = None;
+    let mut fixture = None;
+    let mut expect = None;
+    let parser = syn::meta::parser(|meta| {
+        let Some(key) = meta.path.get_ident().map(Ident::to_string) else {
+            return Err(meta.error("expected an attribute name"));
+        };
+        match key.as_str() {
+            "url" => set_once(&meta, &mut url, parse_str(&meta)?),
+            "fixture" => set_once(&meta, &mut fixture, parse_str(&meta)?),
+            "expect" => set_once(&meta, &mut expect, parse_str(&meta)?),
+            _ => Err(meta.error(format!("unknown extractor_test attribute `{key}`"))),
+        }
+    });
+    parse_macro_input!(attr with parser);
+    let func = parse_macro_input!(item as syn::ItemFn);
+
+    let (Some(url), Some(fixture), Some(expect)) = (url, fixture, expect) else {
+        return syn::Error::new_spanned(
+            &func.sig.ident,
+            "expected `url = \"...\"`, `fixture = \"...\"` and `expect = \"...\"`",
+        )
+        .to_compile_error()
+        .into();
+    };
+    let ident = &func.sig.ident;
+    let attrs = &func.attrs;
+    let body = &func.block.stmts;
 
-    let name_lower = name.to_lowercase();
-    let name_lit = syn::LitStr::new(&name_lower, proc_macro2::Span::call_site());
     quote! {
-        let mut score = 0u32;
+        #[test]
+        #(#attrs)*
+        fn #ident() {
+            let content = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #fixture));
+            let expected: serde_json::Value = serde_json::from_str(include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/",
+                #expect
+            )))
+            .expect(concat!("invalid expected json: ", #expect));
+            let serde_json::Value::Object(expected) = expected else {
+                panic!(concat!("expected json must be an object: ", #expect));
+            };
+
+            let partial = crate::extract_partial(#url, content)
+                .unwrap_or_else(|e| panic!("{}: {e}", #fixture));
+            let actual = serde_json::to_value(&partial).unwrap();
+            for (key, value) in &expected {
+                assert_eq!(actual.get(key), Some(value), "{}: field `{key}`", #fixture);
+            }
+
+            #(#body)*
+        }
+    }
+    .into()
+}
 
-        // 基于标签匹配
-        #(#tag_checks)*
+/// 生成构造提取器的表达式
+///
+/// 优先使用 `constructor`; 无字段或全部为 `()` 标记字段 (如声明式提取器的字段) 时直接构造,
+/// 其余结构体通过 `Default` 构造.
+fn generate_construct(
+    input: &DeriveInput,
+    constructor: Option<&syn::Path>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "Extractable can only be derived for structs",
+        ));
+    };
+    if let Some(constructor) = constructor {
+        return Ok(quote! { #constructor() });
+    }
 
-        // 基于名称的精确匹配
-        if url.to_lowercase().contains(#name_lit) {
-            score += 20;
+    let mut fields = Vec::new();
+    for field in &data.fields {
+        match (&field.ident, &field.ty) {
+            (Some(ident), syn::Type::Tuple(tuple)) if tuple.elems.is_empty() => fields.push(ident),
+            _ => return Ok(quote! { <#name as ::core::default::Default>::default() }),
         }
+    }
+    Ok(quote! { #name { #(#fields: ()),* } })
+}
 
-        score
+/// 声明式提取器
+///
+/// 为带有 `#[field(...)]` 字段的类型生成 `Extractor` 实现, 字段名即 `PartialSubmission` 的字段名, 类型为 `()`.
+/// 通常与 `#[derive(Extractable)]` 一同使用.
+///
+/// ```ignore
+/// #[derive(Extractable, DeclarativeExtractor)]
+/// #[extractor(name = "demo", hosts = ["demo.oj"])]
+/// pub struct DemoExtractor {
+///     #[field(selector = "pre code")]
+///     code: (),
+///     #[field(selector = ".stat .value", regex = r"(\d+)", parse_with = "crate::utils::parse_time_to_ms")]
+///     total_time: (),
+///     #[field(value = "demo")]
+///     oj: (),
+/// }
+/// ```
+///
+/// 支持的字段属性:
+/// - `selector = "..."`: 取首个匹配元素的文本
+/// - `attr = "..."`: 配合 `selector`, 改取元素的属性值 (可选)
+/// - `value = "..."`: 固定值, 与 `selector` 二选一
+/// - `regex = r"..."`: 对文本应用正则, 有捕获组时取第一组 (可选)
+/// - `parse_with = "path"`: 以 `fn(&str) -> Option<T>` 解析并直接赋值 (可选)
+///
+/// - `layout = "..."`: 仅用于指定布局 (可选), 同一字段可为不同布局各声明一次
+///
+/// 未指定 `parse_with` 时, 语言、状态、耗时与内存按 `PartialSubmission` 的同名解析方法处理.
+///
+/// 页面有多个版本时, 在结构体上按顺序声明 `#[layout(name = "new", detect = "#app")]`,
+/// 提取时取首个 `detect` 选择器命中的布局, 各字段优先使用该布局专属的声明;
+/// 均未命中时返回 `LayoutMismatch`.
+/// 选择器与正则在宏展开期校验, 无效时报告编译错误.
+#[proc_macro_derive(DeclarativeExtractor, attributes(field, layout))]
+pub fn derive_declarative_extractor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    declarative::derive(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// 生成 rank 方法的实现
+fn generate_rank_impl(
+    name: &str,
+    tags: &[String],
+    hosts: &[String],
+    domains: &[String],
+    paths: &[String],
+    patterns: &[String],
+) -> proc_macro2::TokenStream {
+    quote! {
+        static MATCHER: once_cell::sync::Lazy<crate::matcher::UrlMatcher> =
+            once_cell::sync::Lazy::new(|| {
+                crate::matcher::UrlMatcher::new(#name, &[#(#tags),*], &[#(#hosts),*], &[#(#paths),*])
+                    .with_domains(&[#(#domains),*])
+                    .with_patterns(&[#(#patterns),*])
+            });
+        MATCHER.rank(url)
     }
 }
@@ -0,0 +1,147 @@
+//! 原始响应体解码
+//!
+//! 依次解压 (gzip / deflate) 与转换字符集, 取得可交给提取器的文本. 字符集按显式声明, BOM,
+//! 页面 `<meta>` 的顺序确定, 均未声明时按 UTF-8 解码, 失败则回退到 GB18030 (兼容 GBK 页面).
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::borrow::Cow;
+use std::io::Read;
+
+use encoding_rs::{Encoding, GB18030, UTF_8};
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use once_cell::sync::Lazy;
+use regex::bytes::Regex;
+
+use crate::error::*;
+
+/// 查找 `<meta>` 字符集声明的范围
+const META_SNIFF_LEN: usize = 1024;
+
+// `<meta charset="...">` 或 `content="text/html; charset=..."`
+static META_CHARSET_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)<meta[^>]+charset\s*=\s*["']?\s*([a-z0-9_\-:.]+)"#).unwrap());
+
+/// 解码响应体
+///
+/// `content_encoding` 对应 `Content-Encoding` 响应头, 缺省时依据魔数识别压缩格式;
+/// `charset` 对应 `Content-Type` 中的字符集.
+pub fn decode_body(
+    bytes: &[u8],
+    content_encoding: Option<&str>,
+    charset: Option<&str>,
+) -> Result<String> {
+    let bytes = decompress(bytes, content_encoding)?;
+    Ok(decode_text(&bytes, charset))
+}
+
+/// 解压响应体, 不支持的压缩格式报错
+pub fn decompress<'a>(bytes: &'a [u8], content_encoding: Option<&str>) -> Result<Cow<'a, [u8]>> {
+    let encoding = match content_encoding.map(|e| e.trim().to_ascii_lowercase()) {
+        Some(e) if !e.is_empty() => e,
+        _ if bytes.starts_with(&[0x1f, 0x8b]) => "gzip".to_string(),
+        _ => return Ok(Cow::Borrowed(bytes)),
+    };
+
+    let mut out = Vec::new();
+    let result = match encoding.as_str() {
+        "identity" => return Ok(Cow::Borrowed(bytes)),
+        "gzip" | "x-gzip" => GzDecoder::new(bytes).read_to_end(&mut out),
+        // 部分服务器发送不带 zlib 头的原始 deflate 流
+        "deflate" if has_zlib_header(bytes) => ZlibDecoder::new(bytes).read_to_end(&mut out),
+        "deflate" => DeflateDecoder::new(bytes).read_to_end(&mut out),
+        other => {
+            return Err(ExtractErrorKind::UnsupportedInput(format!(
+                "unsupported content encoding `{other}`"
+            ))
+            .into());
+        }
+    };
+    result.map_err(|e| {
+        Error::from(ExtractErrorKind::UnsupportedInput(format!(
+            "invalid {encoding} stream: {e}"
+        )))
+    })?;
+    Ok(Cow::Owned(out))
+}
+
+/// zlib 头: 压缩方法为 deflate, 且前两字节构成 31 的倍数
+fn has_zlib_header(bytes: &[u8]) -> bool {
+    match bytes {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && u16::from_be_bytes([*cmf, *flg]) % 31 == 0,
+        _ => false,
+    }
+}
+
+/// 按字符集解码文本, 无法识别的字节替换为 U+FFFD
+pub fn decode_text(bytes: &[u8], charset: Option<&str>) -> String {
+    let declared = charset
+        .and_then(|label| Encoding::for_label(label.trim().as_bytes()))
+        .or_else(|| Encoding::for_bom(bytes).map(|(encoding, _)| encoding))
+        .or_else(|| sniff_meta_charset(bytes));
+
+    let encoding = match declared {
+        Some(encoding) => encoding,
+        None if std::str::from_utf8(bytes).is_ok() => UTF_8,
+        None => GB18030,
+    };
+    encoding.decode(bytes).0.into_owned()
+}
+
+/// 页面开头 `<meta>` 声明的字符集
+fn sniff_meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(META_SNIFF_LEN)];
+    let label = META_CHARSET_REGEX.captures(head)?.get(1)?;
+    Encoding::for_label(label.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use std::io::Write;
+
+    const GBK_PAGE: &[u8] =
+        b"<html><head><meta charset=\"gbk\"></head><body>\xd5\xfd\xc8\xb7</body></html>";
+
+    #[test]
+    fn test_decompress() {
+        let html = "<html><body>Accepted</body></html>";
+
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(html.as_bytes()).unwrap();
+        let gz = gz.finish().unwrap();
+        assert_eq!(decode_body(&gz, Some("gzip"), None).unwrap(), html);
+        assert_eq!(decode_body(&gz, None, None).unwrap(), html);
+
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(html.as_bytes()).unwrap();
+        let zlib = zlib.finish().unwrap();
+        assert_eq!(decode_body(&zlib, Some("deflate"), None).unwrap(), html);
+
+        let mut raw = flate2::write::DeflateEncoder::new(Vec::new(), Compression::default());
+        raw.write_all(html.as_bytes()).unwrap();
+        let raw = raw.finish().unwrap();
+        assert_eq!(decode_body(&raw, Some("deflate"), None).unwrap(), html);
+
+        let err = decode_body(b"plain", Some("gzip"), None).unwrap_err();
+        assert_eq!(err.code(), "unsupported_input");
+        assert!(decode_body(b"plain", Some("br"), None).is_err());
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert!(decode_text(GBK_PAGE, None).contains("正确"));
+        // 未声明字符集且不是合法 UTF-8 时按 GB18030 解码
+        assert_eq!(decode_text(b"\xd5\xfd\xc8\xb7", None), "正确");
+        assert_eq!(decode_text("正确".as_bytes(), None), "正确");
+        assert_eq!(decode_text(b"\xd5\xfd\xc8\xb7", Some("GBK")), "正确");
+    }
+}
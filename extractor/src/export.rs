@@ -0,0 +1,184 @@
+//! 提交记录导出
+//!
+//! 将提交记录批量转换为 Hydro 与 SYZOJ 的导入格式.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use serde::Serialize;
+
+use crate::models::*;
+
+/// Hydro 导出选项
+#[derive(Debug, Clone)]
+pub struct HydroExportOptions {
+    /// 目标域
+    pub domain_id: String,
+    /// 记录所属用户
+    pub uid: i64,
+}
+
+impl Default for HydroExportOptions {
+    fn default() -> Self {
+        Self {
+            domain_id: "system".to_string(),
+            uid: 0,
+        }
+    }
+}
+
+/// Hydro 评测记录
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HydroRecord {
+    pub domain_id: String,
+    pub uid: i64,
+    pub pid: String,
+    pub lang: String,
+    pub code: String,
+    pub status: u32,
+    pub score: u16,
+    pub time: u64,
+    pub memory: u64,
+    pub judge_texts: Vec<String>,
+}
+
+/// Hydro 状态码
+fn hydro_status(status: &SubmissionStatus) -> u32 {
+    match status {
+        SubmissionStatus::Unknown => 10,
+        SubmissionStatus::Accepted => 1,
+        SubmissionStatus::WrongAnswer | SubmissionStatus::PartiallyCorrect => 2,
+        SubmissionStatus::TimeLimitExceeded => 3,
+        SubmissionStatus::MemoryLimitExceeded => 4,
+        SubmissionStatus::RuntimeError => 6,
+        SubmissionStatus::CompileError => 7,
+    }
+}
+
+/// 转换为 Hydro 评测记录
+pub fn to_hydro(subs: &[Submission], options: &HydroExportOptions) -> Vec<HydroRecord> {
+    subs.iter()
+        .map(|sub| HydroRecord {
+            domain_id: options.domain_id.clone(),
+            uid: options.uid,
+            pid: sub.pid.clone(),
+            lang: sub.language.code_for(LanguageTarget::Hydro).to_string(),
+            code: sub.code.clone(),
+            status: hydro_status(&sub.status),
+            score: sub.score.unwrap_or(0),
+            time: sub.total_time,
+            memory: sub.max_memory,
+            judge_texts: vec![format!("Mirrored from {} #{}", sub.oj, sub.rid)],
+        })
+        .collect()
+}
+
+/// 以 JSON 格式导出 Hydro 评测记录
+pub fn to_hydro_json(
+    subs: &[Submission],
+    options: &HydroExportOptions,
+) -> serde_json::Result<String> {
+    serde_json::to_string(&to_hydro(subs, options))
+}
+
+/// SYZOJ 评测记录 (`judge_state`)
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SyzojJudgeState {
+    pub problem_id: String,
+    pub code: String,
+    pub code_length: usize,
+    pub language: SubmissionLanguage,
+    pub status: SubmissionStatus,
+    pub score: u16,
+    pub total_time: u64,
+    pub max_memory: u64,
+    pub is_public: bool,
+}
+
+/// 转换为 SYZOJ 评测记录
+pub fn to_syzoj(subs: &[Submission]) -> Vec<SyzojJudgeState> {
+    subs.iter()
+        .map(|sub| SyzojJudgeState {
+            problem_id: sub.pid.clone(),
+            code: sub.code.clone(),
+            code_length: sub.code.len(),
+            language: sub.language.clone(),
+            status: sub.status.clone(),
+            score: sub.score.unwrap_or(0),
+            total_time: sub.total_time,
+            max_memory: sub.max_memory,
+            is_public: true,
+        })
+        .collect()
+}
+
+/// 以 JSON 格式导出 SYZOJ 评测记录
+pub fn to_syzoj_json(subs: &[Submission]) -> serde_json::Result<String> {
+    serde_json::to_string(&to_syzoj(subs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Submission {
+        Submission {
+            code: "int main() {}".to_string(),
+            pid: "P1001".to_string(),
+            rid: "1".to_string(),
+            oj: "luogu".to_string(),
+            language: SubmissionLanguage::Cpp14,
+            status: SubmissionStatus::TimeLimitExceeded,
+            total_time: 1000,
+            max_memory: 256,
+            score: Some(60),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hydro() {
+        let records = to_hydro(&[sample()], &HydroExportOptions::default());
+        assert_eq!(
+            serde_json::to_value(&records).unwrap(),
+            json!([{
+                "domainId": "system",
+                "uid": 0,
+                "pid": "P1001",
+                "lang": "cc.cc14",
+                "code": "int main() {}",
+                "status": 3,
+                "score": 60,
+                "time": 1000,
+                "memory": 256,
+                "judgeTexts": ["Mirrored from luogu #1"],
+            }])
+        );
+    }
+
+    #[test]
+    fn test_syzoj() {
+        let states = to_syzoj(&[sample()]);
+        assert_eq!(
+            serde_json::to_value(&states).unwrap(),
+            json!([{
+                "problem_id": "P1001",
+                "code": "int main() {}",
+                "code_length": 13,
+                "language": "cpp14",
+                "status": "Time Limit Exceeded",
+                "score": 60,
+                "total_time": 1000,
+                "max_memory": 256,
+                "is_public": true,
+            }])
+        );
+    }
+}
@@ -8,16 +8,18 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use registry::Extractable;
+use registry::{Extractable, extractor_test};
 
-use scraper::{Html, Selector};
+use scraper::Html;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
 
 use crate::error::*;
 use crate::models::*;
-use crate::traits::Extractor;
+use crate::overrides;
+use crate::policy::ValidationPolicy;
+use crate::traits::{Extractor, Fixture};
 use crate::utils::*;
 
 // 题目链接
@@ -27,204 +29,357 @@ static PROBLEM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"/problem/(P?\d+)")
 static RECORD_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?:https?://(?:www\.)?luogu\.com\.cn)?/record/(\d+)").unwrap());
 
+// 提交记录列表链接
+static RECORD_LIST_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"/record/list\b").unwrap());
+
 // 从文本中提取分数
 static SCORE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+)").unwrap());
 
+// 新版前端的页面标记
+const LENTILLE_MARKER: &str = "lentille-context";
+
+// 未登录页面标记
+const LOGIN_MARKERS: &[&str] = &["/auth/login", "请先登录", "您需要先登录"];
+
+// 无权限页面标记
+const FORBIDDEN_MARKERS: &[&str] = &["403 Forbidden", "您无权查看", "无权访问"];
+
+// 支持的页面布局
+static LAYOUTS: &[&str] = &[
+    "legacy (.stat.color-inverse / .info-rows)",
+    "lentille (script#lentille-context data.record)",
+];
+
+// 各字段的默认选择器, 提取与诊断共用
+const LANGUAGE_CODE_SELECTOR: &str = "code[class*=language-]";
+const CODE_SELECTOR: &str = "code";
+const CODE_FALLBACK_SELECTOR: &str = "pre";
+const PROBLEM_LINK_SELECTOR: &str = "a[href*='/problem/']";
+const BASIC_INFO_SELECTOR: &str = ".stat.color-inverse .field";
+const INFO_ROWS_SELECTOR: &str = ".info-rows div";
+
+// 自检样例
+const FIXTURE_CONTENT: &str = r#"<div class="info-rows"><div><span>评测状态</span><span>Accepted</span></div></div><a href="/problem/P1001">P1001</a><pre><code class="language-cpp">int main() {}</code></pre>"#;
+
+// 各字段的提取策略, 用于诊断
+static STRATEGIES: FieldStrategies = &[
+    (
+        "code",
+        &[
+            LANGUAGE_CODE_SELECTOR,
+            CODE_SELECTOR,
+            CODE_FALLBACK_SELECTOR,
+            "record.sourceCode",
+        ],
+    ),
+    (
+        "pid",
+        &[PROBLEM_LINK_SELECTOR, "PROBLEM_REGEX", "record.problem.pid"],
+    ),
+    ("rid", &["RECORD_REGEX (url)"]),
+    ("language", &[BASIC_INFO_SELECTOR]),
+    ("status", &[INFO_ROWS_SELECTOR]),
+];
+
 /// 洛谷提取器
 #[derive(Extractable)]
-#[extractor(name = "luogu", tags = ["洛谷"])]
+#[extractor(
+    name = "luogu",
+    display_name = "洛谷",
+    homepage = "https://www.luogu.com.cn",
+    tags = ["洛谷"],
+    hosts = ["luogu.com.cn"],
+    paths = [r"^/record/\d+"],
+    patterns = [r"^https?://(www\.)?luogu\.com\.cn/record/\d+"]
+)]
 pub struct LuoguExtractor {}
 
 impl LuoguExtractor {
-    fn extract_basic_info(document: &Html) -> (String, i32, i32) {
-        let mut language = String::new();
-        let mut total_time = 0;
-        let mut max_memory = 0;
-
-        let Ok(stat_sel) = Selector::parse(".stat.color-inverse") else {
-            return (language, total_time, max_memory);
-        };
-
-        if let Some(stat_el) = document.select(&stat_sel).next() {
-            let Ok(field_sel) = Selector::parse(".field") else {
-                return (language, total_time, max_memory);
-            };
+    /// 提取语言、时间与内存文本
+    fn extract_basic_info(
+        document: &Html,
+    ) -> Result<(Option<String>, Option<String>, Option<String>)> {
+        let mut fields = kv_pairs(document, BASIC_INFO_SELECTOR, ".key", ".value")?;
+
+        Ok((
+            fields.remove("language"),
+            fields.remove("time"),
+            fields.remove("memory"),
+        ))
+    }
 
-            for field in stat_el.select(&field_sel) {
-                let (Ok(key_sel), Ok(value_sel)) =
-                    (Selector::parse(".key"), Selector::parse(".value"))
-                else {
-                    continue;
-                };
+    fn extract_code(document: &Html) -> Result<Option<String>> {
+        // 优先带语言标记的代码块
+        let language_code_css =
+            overrides::selector("luogu", "language_code", LANGUAGE_CODE_SELECTOR);
+        if let Some(code) = select_text(document, &language_code_css)? {
+            return Ok(Some(code));
+        }
 
-                let key = field
-                    .select(&key_sel)
-                    .next()
-                    .map(|e| e.text().collect::<String>().trim().to_string())
-                    .unwrap_or_default();
-                let value = field
-                    .select(&value_sel)
-                    .next()
-                    .map(|e| e.text().collect::<String>().trim().to_string())
-                    .unwrap_or_default();
-
-                match key.as_str() {
-                    "编程语言" => language = value,
-                    "用时" => total_time = parse_time_to_ms(&value).unwrap_or(0),
-                    "内存" => max_memory = parse_mem_to_kb(&value).unwrap_or(0),
-                    _ => {}
-                }
-            }
+        if let Some(code) = select_text(
+            document,
+            &overrides::selector("luogu", "code", CODE_SELECTOR),
+        )? {
+            return Ok(Some(code));
         }
 
-        (language, total_time, max_memory)
+        select_text(
+            document,
+            &overrides::selector("luogu", "code_fallback", CODE_FALLBACK_SELECTOR),
+        )
     }
 
-    fn extract_code(document: &Html) -> String {
-        let Ok(code_sel) = Selector::parse("code") else {
-            return String::new();
-        };
+    fn extract_pid(document: &Html) -> Result<Option<String>> {
+        let a_sel = parse_selector(&overrides::selector(
+            "luogu",
+            "problem_link",
+            PROBLEM_LINK_SELECTOR,
+        ))?;
+        let problem_regex = overrides::regex("luogu", "problem", &PROBLEM_REGEX);
 
-        for el in document.select(&code_sel) {
-            if let Some(cl) = el.value().attr("class")
-                && cl.contains("language-")
+        for a in document.select(&a_sel) {
+            if let Some(href) = a.value().attr("href")
+                && href.contains("/problem/")
+                && let Some(caps) = problem_regex.captures(href)
+                && let Some(m) = caps.get(1)
             {
-                return el.text().collect::<String>().trim().to_string();
+                return Ok(Some(m.as_str().to_string()));
             }
         }
 
-        if let Some(el) = document.select(&code_sel).next() {
-            return el.text().collect::<String>().trim().to_string();
-        }
+        Ok(None)
+    }
 
-        let Ok(pre_sel) = Selector::parse("pre") else {
-            return String::new();
-        };
+    fn extract_status_and_score(document: &Html) -> Result<(Option<String>, Option<String>)> {
+        let rows = overrides::selector("luogu", "info_rows", INFO_ROWS_SELECTOR);
+        let status = find_field_by_label(document, &rows, &["评测状态", "Status"])?;
 
-        document
-            .select(&pre_sel)
-            .next()
-            .map(|el| el.text().collect::<String>().trim().to_string())
-            .unwrap_or_default()
+        let score_text = find_field_by_label(document, &rows, &["评测分数", "Score"])?;
+        let score = score_text
+            .as_deref()
+            .and_then(|text| overrides::regex("luogu", "score", &SCORE_REGEX).captures(text))
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_string());
+
+        Ok((status, score))
     }
 
-    fn extract_pid(document: &Html) -> String {
-        let Ok(a_sel) = Selector::parse("a") else {
-            return String::new();
-        };
+    fn extract_rid(url: &str) -> Option<String> {
+        overrides::regex("luogu", "record", &RECORD_REGEX)
+            .captures(url)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_string())
+    }
 
-        for a in document.select(&a_sel) {
-            if let Some(href) = a.value().attr("href")
-                && href.contains("/problem/")
-                && let Some(caps) = PROBLEM_REGEX.captures(href)
-                && let Some(m) = caps.get(1)
-            {
-                return m.as_str().to_string();
+    /// 识别登录墙、403 页面与不支持的页面布局
+    fn detect_cause(content: &str) -> Option<ExtractErrorKind> {
+        if LOGIN_MARKERS.iter().any(|m| content.contains(m)) {
+            return Some(ExtractErrorKind::NotAuthenticated("luogu".to_string()));
+        }
+        if FORBIDDEN_MARKERS.iter().any(|m| content.contains(m)) {
+            return Some(ExtractErrorKind::PermissionDenied("luogu".to_string()));
+        }
+        (content.contains(LENTILLE_MARKER) && !content.contains("info-rows")).then(|| {
+            ExtractErrorKind::LayoutMismatch {
+                detected: Some("lentille".to_string()),
+                expected: LAYOUTS,
             }
+        })
+    }
+
+    /// 识别页面布局: 旧版服务端渲染页面或新版 lentille 前端
+    fn layout_of(content: &str) -> &'static str {
+        match content.contains(LENTILLE_MARKER) && !content.contains("info-rows") {
+            true => "lentille",
+            false => "legacy",
         }
+    }
 
-        String::new()
+    /// 从页面 HTML 尽力提取, 按布局选择提取策略
+    fn partial_from_html(ctx: &ExtractionContext<'_>, content: &str) -> Result<PartialSubmission> {
+        let layout = Self::layout_of(content);
+        let mut partial = match layout {
+            "lentille" => {
+                let document = Html::parse_document(content);
+                let json = select_text(&document, "script#lentille-context")?.unwrap_or_default();
+                Self::partial_from_json(ctx, &json)?
+            }
+            _ => Self::partial_from_legacy(ctx, content)?,
+        };
+        partial.layout = Some(layout.to_string());
+        Ok(partial)
     }
 
-    fn extract_status_and_score(document: &Html) -> (SubmissionStatus, i32) {
-        let mut status = SubmissionStatus::Unknown;
-        let mut score = 0;
+    /// 从 lentille 注入的数据或 `_contentOnly` 接口返回的 JSON 提取
+    fn partial_from_json(ctx: &ExtractionContext<'_>, json: &str) -> Result<PartialSubmission> {
+        let mut partial = PartialSubmission {
+            rid: Self::extract_rid(ctx.url),
+            oj: Some("luogu".to_string()),
+            ..Default::default()
+        };
 
-        let Ok(rows_sel) = Selector::parse(".info-rows div") else {
-            return (status, score);
+        let value: serde_json::Value = serde_json::from_str(json).unwrap_or_default();
+        let Some(record) = value
+            .pointer("/data/record")
+            .or_else(|| value.pointer("/currentData/record"))
+        else {
+            return Ok(partial);
         };
 
-        for row in document.select(&rows_sel) {
-            let row_text = row.text().collect::<String>();
-            if row_text.contains("评测状态") {
-                let txt = row_text
-                    .split_whitespace()
-                    .last()
-                    .map(|s| s.trim())
-                    .unwrap_or("");
-                status = txt.parse().unwrap_or(SubmissionStatus::Unknown);
-            }
+        let str_field = |path: &str| record.pointer(path).and_then(|v| v.as_str());
+        let num_field = |path: &str| record.pointer(path).and_then(|v| v.as_u64());
 
-            if row_text.contains("评测分数")
-                && let Some(caps) = SCORE_REGEX.captures(&row_text)
-            {
-                score = caps
-                    .get(1)
-                    .and_then(|m| m.as_str().parse().ok())
-                    .unwrap_or(0);
-            }
+        partial.code = str_field("/sourceCode").map(|c| strip_line_numbers(&normalize_code(c)));
+        partial.pid = str_field("/problem/pid").map(str::to_string);
+        if let Some(id) = num_field("/id") {
+            partial.rid = Some(id.to_string());
         }
-
-        (status, score)
+        partial.score = num_field("/score").and_then(|s| u16::try_from(s).ok());
+        partial.total_time = num_field("/time");
+        partial.max_memory = num_field("/memory");
+        partial.submitted_at = num_field("/submitTime").map(|s| s.saturating_mul(1000));
+        if let Some(code) = num_field("/status") {
+            Self::apply_status_code(&mut partial, code);
+        }
+        Ok(partial)
     }
 
-    fn extract_rid(url: &str) -> String {
-        RECORD_REGEX
-            .captures(url)
-            .and_then(|c| c.get(1))
-            .map(|m| m.as_str().to_string())
-            .unwrap_or_default()
+    /// 洛谷接口的数字评测状态
+    fn apply_status_code(partial: &mut PartialSubmission, code: u64) {
+        partial.status = Some(match code {
+            0 | 1 => {
+                partial.judging = true;
+                SubmissionStatus::Unknown
+            }
+            2 => SubmissionStatus::CompileError,
+            4 => SubmissionStatus::MemoryLimitExceeded,
+            5 => SubmissionStatus::TimeLimitExceeded,
+            3 | 6 => SubmissionStatus::WrongAnswer,
+            7 => SubmissionStatus::RuntimeError,
+            12 => SubmissionStatus::Accepted,
+            14 => SubmissionStatus::PartiallyCorrect,
+            _ => {
+                partial
+                    .warnings
+                    .push(ExtractWarning::UnrecognizedStatus(code.to_string()));
+                SubmissionStatus::Unknown
+            }
+        });
     }
 
-    fn extract_partial(&self, url: &str, content: &str) -> Submission {
+    /// 旧版页面
+    fn partial_from_legacy(
+        ctx: &ExtractionContext<'_>,
+        content: &str,
+    ) -> Result<PartialSubmission> {
+        let url = ctx.url;
         let document = Html::parse_document(content);
 
-        let (language_text, total_time, max_memory) = Self::extract_basic_info(&document);
-        let code = Self::extract_code(&document);
-        let pid = Self::extract_pid(&document);
-        let (status, score) = Self::extract_status_and_score(&document);
-        let rid = Self::extract_rid(url);
-
-        let language = language_text.parse().unwrap_or_default();
-
-        Submission {
-            code,
-            pid,
-            rid,
-            oj: "luogu".to_string(),
-            language,
-            status,
-            total_time,
-            max_memory,
-            score,
-        }
-    }
+        let (language_text, time_text, memory_text) = Self::extract_basic_info(&document)?;
+        let (status, score) = Self::extract_status_and_score(&document)?;
 
-    fn validate_submission(sub: &Submission) -> Result<()> {
-        if sub.pid.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("pid".to_string()),
-                sub.clone(),
-            )));
+        let mut partial = PartialSubmission {
+            code: Self::extract_code(&document)?.map(|c| strip_line_numbers(&normalize_code(&c))),
+            pid: Self::extract_pid(&document)?,
+            rid: Self::extract_rid(url),
+            oj: Some("luogu".to_string()),
+            ..Default::default()
+        };
+        if let Some(text) = language_text {
+            partial.parse_language(&text);
+        }
+        if let Some(text) = status {
+            partial.parse_status(&text);
         }
-        if sub.rid.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("rid".to_string()),
-                sub.clone(),
-            )));
+        if let Some(text) = score {
+            partial.parse_score(&text);
         }
-        if sub.code.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("code".to_string()),
-                sub.clone(),
-            )));
+        if let Some(text) = time_text {
+            partial.parse_time(&text);
         }
-        Ok(())
+        if let Some(text) = memory_text {
+            partial.parse_memory(&text);
+        }
+        Ok(partial)
     }
 }
 
 impl Extractor for LuoguExtractor {
-    fn extract(&self, url: &str, content: &str) -> Result<Submission> {
-        if content.trim().is_empty() {
-            return Err(Error::Extract(ExtractError::new(
-                ExtractErrorKind::EmptyContent,
-            )));
+    fn fixture(&self) -> Option<Fixture> {
+        Some(Fixture {
+            url: "https://www.luogu.com.cn/record/1",
+            content: FIXTURE_CONTENT,
+            pid: "P1001",
+            rid: "1",
+            status: SubmissionStatus::Accepted,
+        })
+    }
+
+    fn layouts(&self) -> &'static [&'static str] {
+        LAYOUTS
+    }
+
+    fn detect_layout(&self, content: &str) -> Option<&'static str> {
+        Some(Self::layout_of(content))
+    }
+
+    fn inputs(&self) -> &'static [InputKind] {
+        &[InputKind::Html, InputKind::Json]
+    }
+
+    fn classify(&self, url: &str, content: &str) -> PageKind {
+        if matches!(
+            Self::detect_cause(content),
+            Some(ExtractErrorKind::NotAuthenticated(_))
+        ) {
+            PageKind::Login
+        } else if RECORD_REGEX.is_match(url) {
+            PageKind::SubmissionDetail
+        } else if RECORD_LIST_REGEX.is_match(url) {
+            PageKind::RecordList
+        } else if PROBLEM_REGEX.is_match(url) {
+            PageKind::Problem
+        } else {
+            PageKind::Unknown
         }
+    }
 
-        let submission = self.extract_partial(url, content);
+    fn record_id(&self, url: &str, _content: &str) -> Option<String> {
+        Self::extract_rid(url)
+    }
 
-        Self::validate_submission(&submission)?;
-        Ok(submission)
+    fn extract_partial(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+    ) -> Result<PartialSubmission> {
+        if input.is_empty() {
+            return Err(ExtractErrorKind::EmptyContent.into());
+        }
+        match input {
+            ExtractorInput::JsonApi(json) => {
+                let mut partial = Self::partial_from_json(ctx, json)?;
+                partial.layout = Some("json".to_string());
+                Ok(partial)
+            }
+            _ => Self::partial_from_html(ctx, input.html()?),
+        }
+    }
+
+    fn extract_input(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+        policy: &ValidationPolicy,
+    ) -> Result<ExtractionReport> {
+        let partial = self.extract_merged(ctx, input)?;
+        let content = input.first(InputKind::Html).unwrap_or_default();
+        ExtractionReport::build(partial, policy).map_err(|e| {
+            Error::Extract(
+                e.with_diagnostics(STRATEGIES)
+                    .with_cause(Self::detect_cause(content)),
+            )
+        })
     }
 }
 
@@ -307,3 +462,193 @@ fn test_extract() -> Result<()> {
 
     Ok(())
 }
+
+#[extractor_test(
+    url = "https://www.luogu.com.cn/record/241494617",
+    fixture = "fixtures/luogu_record.html",
+    expect = "fixtures/luogu_record.json"
+)]
+fn test_fixture_record() {}
+
+#[test]
+fn test_code_entities() -> Result<()> {
+    // 页面中的 `&amp;amp;` 经 HTML 解析后为代码原文 `&amp;`, 不应再次解码
+    let url = "https://www.luogu.com.cn/record/1";
+    let content = r#"<pre><code class="language-cpp">puts("&amp;amp; &amp;lt;");</code></pre>"#;
+    let ctx = ExtractionContext::new(url);
+    let partial = LuoguExtractor {}.extract_partial(&ctx, &ExtractorInput::HtmlPage(content))?;
+    assert_eq!(partial.code.as_deref(), Some(r#"puts("&amp; &lt;");"#));
+    Ok(())
+}
+
+#[test]
+fn test_diagnostics() {
+    let url = "https://www.luogu.com.cn/record/241494617";
+    let content = "<html><body><p>Loading...</p></body></html>";
+
+    let Err(Error::Extract(err)) = LuoguExtractor {}.extract(url, content) else {
+        panic!("extraction should fail");
+    };
+    assert_eq!(err.missing_fields, ["pid", "code"]);
+    assert_eq!(err.diagnostics[0].field, "pid");
+    assert_eq!(
+        err.diagnostics[1].tried,
+        ["code[class*=language-]", "code", "pre", "record.sourceCode"]
+    );
+}
+
+#[test]
+fn test_strategies() {
+    // 诊断中的选择器须与提取时的默认值一致
+    let selectors = [
+        LANGUAGE_CODE_SELECTOR,
+        CODE_SELECTOR,
+        CODE_FALLBACK_SELECTOR,
+        PROBLEM_LINK_SELECTOR,
+        BASIC_INFO_SELECTOR,
+        INFO_ROWS_SELECTOR,
+    ];
+    let others = [
+        "PROBLEM_REGEX",
+        "RECORD_REGEX (url)",
+        "record.sourceCode",
+        "record.problem.pid",
+    ];
+
+    for (field, tried) in STRATEGIES {
+        for css in *tried {
+            assert!(
+                selectors.contains(css) || others.contains(css),
+                "{field}: `{css}` is not used by the extractor"
+            );
+        }
+    }
+    for css in selectors {
+        assert!(parse_selector(css).is_ok(), "invalid selector `{css}`");
+    }
+}
+
+#[test]
+fn test_layout_mismatch() {
+    let url = "https://www.luogu.com.cn/record/241494617";
+    let content = r#"<html><body><div id="app"></div><script id="lentille-context" type="application/json">{}</script></body></html>"#;
+
+    let Err(Error::Extract(err)) = LuoguExtractor {}.extract(url, content) else {
+        panic!("extraction should fail");
+    };
+    assert_eq!(err.kind.code(), "layout_mismatch");
+    assert_eq!(err.kind.detail(), Some("lentille"));
+    assert_eq!(err.missing_fields, ["pid", "code"]);
+}
+
+#[test]
+fn test_classify() {
+    let extractor = LuoguExtractor {};
+    let classify = |url| extractor.classify(url, "<html></html>");
+
+    assert_eq!(
+        classify("https://www.luogu.com.cn/record/241494617"),
+        PageKind::SubmissionDetail
+    );
+    assert_eq!(
+        classify("https://www.luogu.com.cn/record/list?user=1"),
+        PageKind::RecordList
+    );
+    assert_eq!(
+        classify("https://www.luogu.com.cn/problem/P1001"),
+        PageKind::Problem
+    );
+    assert_eq!(
+        extractor.classify("https://www.luogu.com.cn/record/1", "<p>请先登录</p>"),
+        PageKind::Login
+    );
+}
+
+#[test]
+fn test_invalid_score() -> Result<()> {
+    let url = "https://www.luogu.com.cn/record/241494617";
+    let content = r#"<div class="info-rows"><div><span>评测状态</span><span>Accepted</span></div><div><span>评测分数</span><span>99999999</span></div></div><a href="/problem/P1001">P1001</a><pre><code class="language-cpp">int main() {}</code></pre>"#;
+
+    // 分数越界时记录警告而非中止提取
+    let report =
+        LuoguExtractor {}.extract_with_report(url, content, &ValidationPolicy::default())?;
+    assert!(report.warnings.contains(&ExtractWarning::InvalidNumber {
+        field: "score".to_string(),
+        text: "99999999".to_string(),
+    }));
+    Ok(())
+}
+
+#[test]
+fn test_lentille() -> Result<()> {
+    let url = "https://www.luogu.com.cn/record/241494617";
+    let data = r##"{"data": {"record": {"id": 241494617, "status": 12, "score": 100, "time": 2330, "memory": 1587, "sourceCode": "#include <cstdio>\nint main() {}", "problem": {"pid": "P4198"}}}}"##;
+    let content = format!(
+        r#"<html><body><div id="app"></div><script id="lentille-context" type="application/json">{data}</script></body></html>"#
+    );
+
+    let report =
+        LuoguExtractor {}.extract_with_report(url, &content, &ValidationPolicy::default())?;
+    assert_eq!(report.layout.as_deref(), Some("lentille"));
+    assert_eq!(report.submission.pid, "P4198");
+    assert_eq!(report.submission.status, SubmissionStatus::Accepted);
+    assert_eq!(report.submission.total_time, 2330);
+
+    let ctx = ExtractionContext::new(url);
+    let input = ExtractorInput::JsonApi(data);
+    let report = LuoguExtractor {}.extract_input(&ctx, &input, &ValidationPolicy::default())?;
+    assert_eq!(report.layout.as_deref(), Some("json"));
+    assert_eq!(report.submission.max_memory, 1587);
+
+    // 过大的提交时间饱和而非溢出
+    let data = data.replace(
+        r#""time": 2330"#,
+        r#""submitTime": 18446744073709551615, "time": 2330"#,
+    );
+    let input = ExtractorInput::JsonApi(&data);
+    let report = LuoguExtractor {}.extract_input(&ctx, &input, &ValidationPolicy::default())?;
+    assert_eq!(report.submission.submitted_at, Some(u64::MAX));
+    Ok(())
+}
+
+#[test]
+fn test_multi_document() -> Result<()> {
+    let url = "https://www.luogu.com.cn/record/241494617";
+    let record = r#"{"data": {"record": {"id": 241494617, "status": 12, "score": 100, "time": 2330, "memory": 1587, "problem": {"pid": "P4198"}}}}"#;
+    let code = r#"{"data": {"record": {"sourceCode": "int main() {}", "time": 1}}}"#;
+    let input = ExtractorInput::MultiDocument(vec![
+        Document {
+            url,
+            kind: InputKind::Json,
+            content: record,
+        },
+        Document {
+            url: "https://www.luogu.com.cn/record/241494617?_contentOnly=1",
+            kind: InputKind::Json,
+            content: code,
+        },
+        Document {
+            url: "",
+            kind: InputKind::Html,
+            content: " ",
+        },
+    ]);
+
+    let ctx = ExtractionContext::new(url);
+    let report = LuoguExtractor {}.extract_input(&ctx, &input, &ValidationPolicy::default())?;
+    assert_eq!(report.submission.code, "int main() {}");
+    assert_eq!(report.submission.pid, "P4198");
+    assert_eq!(report.submission.total_time, 2330);
+    Ok(())
+}
+
+#[test]
+fn test_not_authenticated() {
+    let url = "https://www.luogu.com.cn/record/241494617";
+    let content = r#"<html><body><a href="/auth/login">登录</a><p>请先登录</p></body></html>"#;
+
+    let Err(err) = LuoguExtractor {}.extract(url, content) else {
+        panic!("extraction should fail");
+    };
+    assert_eq!(err.code(), "not_authenticated");
+}
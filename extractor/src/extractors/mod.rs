@@ -1,4 +1,10 @@
 //! 提取器实现
+//!
+//! 本目录下的每个源文件即一个提取器模块, 由 build.rs 自动声明并收集其中 `#[derive(Extractable)]` 的类型,
+//! 新增提取器时无需修改本文件. 各模块由同名 cargo feature 控制, 未启用的提取器不参与注册.
+//!
+//! 由于 linkme 分布式注册表在 wasm 下无法使用, 注册表在编译期由构建脚本生成.
+//! 构建脚本同时汇总各提取器的元数据生成 [`MANIFEST`], 供扩展生成匹配规则与界面.
 
 /*
  * Copyright (c) 2025 fltLi
@@ -8,18 +14,7 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-pub mod luogu;
-pub mod vjudge;
-pub mod xyd;
+include!(concat!(env!("OUT_DIR"), "/extractors.rs"));
 
-/// 收集注册的提取器
-/// 
-/// 由于 linkme 分布式注册表的依赖问题, wasm 编译将报错.
-/// 现已移除 linkme 并全部替换为手动实现的注册表.
-pub(crate) fn registry_items() -> Vec<crate::factory::ExtractorRegistryItem> {
-	vec![
-		luogu::__EXTRACTOR_REGISTRY_LUOGUEXTRACTOR(),
-		vjudge::__EXTRACTOR_REGISTRY_VJUDGEEXTRACTOR(),
-		xyd::__EXTRACTOR_REGISTRY_XINYOUDUIEXTRACTOR(),
-	]
-}
+/// 已启用提取器的清单 (JSON), 含 `extractors` 元数据与浏览器匹配模式 `matches`
+pub const MANIFEST: &str = include_str!(concat!(env!("OUT_DIR"), "/manifest.json"));
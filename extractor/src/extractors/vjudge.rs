@@ -11,11 +11,15 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 use registry::Extractable;
-use scraper::{Html, Selector};
+use scraper::Html;
+use std::collections::HashMap;
 
+use crate::constants::CST_OFFSET_MINUTES;
 use crate::error::*;
 use crate::models::*;
-use crate::traits::Extractor;
+use crate::overrides;
+use crate::policy::ValidationPolicy;
+use crate::traits::{Extractor, Fixture};
 use crate::utils::*;
 
 // 提交记录链接
@@ -25,334 +29,475 @@ static RECORD_REGEX: Lazy<Regex> =
 // 题目链接正则
 static PROBLEM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"/problem/([^/]+)").unwrap());
 
+// 提交记录列表链接
+static STATUS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"vjudge\.net/status\b").unwrap());
+
 // 远程提交 ID 提取
 static REMOTE_RUN_ID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[a-f0-9]{24}").unwrap());
 
+// 未登录页面标记
+const LOGIN_MARKERS: &[&str] = &["Please login", "please login first"];
+
+// 无权限页面标记
+const FORBIDDEN_MARKERS: &[&str] = &["Permission denied", "You have no permission"];
+
+// 各字段的默认选择器, 提取与诊断共用
+const CODE_SELECTOR: &str = "pre code";
+const CODE_FALLBACK_SELECTOR: &str = "pre";
+const PROBLEM_LINK_SELECTOR: &str = ".modal-title a";
+const SOLUTION_LINK_SELECTOR: &str = ".modal-title a[href^='/solution/']";
+const SOLUTION_ROW_SELECTOR: &str = "tr[id]";
+const INFO_PANEL_SELECTOR: &str = "#info-panel table";
+const LANGUAGE_TOOLTIP_SELECTOR: &str = ".language div[data-original-title]";
+const STATUS_SELECTOR: &str = ".status .view-solution";
+
+// 自检样例
+const FIXTURE_CONTENT: &str = r#"<h5 class="modal-title"><a href="/solution/1">#1</a><a href="/problem/HDU-1000">[HDU-1000]</a></h5><div id="info-panel"><table><tr><th>Status</th><td class="status">Accepted</td></tr></table></div><pre><code>int main() {}</code></pre>"#;
+
+// 各字段的提取策略, 用于诊断
+static STRATEGIES: FieldStrategies = &[
+    ("code", &[CODE_SELECTOR, CODE_FALLBACK_SELECTOR]),
+    ("pid", &[PROBLEM_LINK_SELECTOR, "PROBLEM_REGEX"]),
+    (
+        "rid",
+        &[
+            "RECORD_REGEX (url)",
+            SOLUTION_LINK_SELECTOR,
+            SOLUTION_ROW_SELECTOR,
+        ],
+    ),
+    (
+        "language",
+        &[INFO_PANEL_SELECTOR, LANGUAGE_TOOLTIP_SELECTOR],
+    ),
+    ("status", &[STATUS_SELECTOR, INFO_PANEL_SELECTOR]),
+];
+
 /// VJudge 提取器
 #[derive(Extractable)]
-#[extractor(name = "vj", tags = ["vjudge", "Virtual Judge"])]
+#[extractor(
+    name = "vj",
+    id = "vjudge",
+    display_name = "Virtual Judge",
+    homepage = "https://vjudge.net",
+    tags = ["vjudge", "Virtual Judge"],
+    domains = ["vjudge.net"],
+    paths = [r"^/solution/\d+"],
+    patterns = [r"^https?://(www\.)?vjudge\.net/solution/\d+"],
+    priority = 10
+)]
 pub struct VjudgeExtractor;
 
 impl VjudgeExtractor {
     /// 提取代码
-    fn extract_code(document: &Html) -> String {
-        let Ok(code_selector) = Selector::parse("pre code") else {
-            return String::new();
-        };
-
-        if let Some(code_element) = document.select(&code_selector).next() {
-            return code_element.text().collect::<String>().trim().to_string();
+    fn extract_code(document: &Html) -> Result<Option<String>> {
+        if let Some(code) = select_text(
+            document,
+            &overrides::selector("vjudge", "code", CODE_SELECTOR),
+        )? {
+            return Ok(Some(code));
         }
 
         // 备用选择器
-        let Ok(pre_selector) = Selector::parse("pre") else {
-            return String::new();
-        };
-
-        document
-            .select(&pre_selector)
-            .next()
-            .map(|el| el.text().collect::<String>().trim().to_string())
-            .unwrap_or_default()
+        select_text(
+            document,
+            &overrides::selector("vjudge", "code_fallback", CODE_FALLBACK_SELECTOR),
+        )
     }
 
     /// 提取题目 ID
-    fn extract_pid(document: &Html) -> String {
+    fn extract_pid(document: &Html) -> Result<Option<String>> {
         // 从 modal title 中提取题目链接
-        let Ok(modal_title_selector) = Selector::parse(".modal-title a") else {
-            return String::new();
-        };
+        let modal_title_selector = parse_selector(&overrides::selector(
+            "vjudge",
+            "problem_link",
+            PROBLEM_LINK_SELECTOR,
+        ))?;
+        let problem_regex = overrides::regex("vjudge", "problem", &PROBLEM_REGEX);
 
         for link in document.select(&modal_title_selector) {
             if let Some(href) = link.value().attr("href")
                 && href.contains("/problem/")
-                && let Some(caps) = PROBLEM_REGEX.captures(href)
+                && let Some(caps) = problem_regex.captures(href)
                 && let Some(pid_match) = caps.get(1)
             {
-                return pid_match.as_str().to_string();
+                return Ok(Some(pid_match.as_str().to_string()));
             }
         }
 
-        String::new()
+        Ok(None)
     }
 
     /// 提取提交 ID
-    fn extract_rid(url: &str, document: &Html) -> String {
+    fn extract_rid(url: &str, document: &Html) -> Result<Option<String>> {
+        let record_regex = overrides::regex("vjudge", "record", &RECORD_REGEX);
+
         // 首先尝试从 URL 中提取
-        if let Some(caps) = RECORD_REGEX.captures(url)
+        if let Some(caps) = record_regex.captures(url)
             && let Some(rid_match) = caps.get(1)
         {
-            return rid_match.as_str().to_string();
+            return Ok(Some(rid_match.as_str().to_string()));
         }
 
         // 备用方案: 从模态框标题中提取
-        let Ok(modal_title_selector) = Selector::parse(".modal-title a[href^='/solution/']") else {
-            return String::new();
-        };
+        let modal_title_selector = parse_selector(&overrides::selector(
+            "vjudge",
+            "solution_link",
+            SOLUTION_LINK_SELECTOR,
+        ))?;
 
         for link in document.select(&modal_title_selector) {
             if let Some(href) = link.value().attr("href") {
-                if let Some(caps) = RECORD_REGEX.captures(href)
+                if let Some(caps) = record_regex.captures(href)
                     && let Some(rid_match) = caps.get(1)
                 {
-                    return rid_match.as_str().to_string();
+                    return Ok(Some(rid_match.as_str().to_string()));
                 }
                 // 备用: 直接解析 /solution/ 后面的数字
                 if href.starts_with("/solution/")
                     && let Some(rid) = href.strip_prefix("/solution/")
                 {
-                    return rid.to_string();
+                    return Ok(Some(rid.to_string()));
                 }
             }
         }
 
         // 从表格行的 id 属性中提取
-        let Ok(row_selector) = Selector::parse("tr[id]") else {
-            return String::new();
-        };
+        let row_selector = parse_selector(SOLUTION_ROW_SELECTOR)?;
 
         for row in document.select(&row_selector) {
             if let Some(id) = row.value().attr("id") {
-                // 检查 id 是否是纯数字 (提交ID) 
+                // 检查 id 是否是纯数字 (提交ID)
                 if id.chars().all(|c| c.is_ascii_digit()) {
-                    return id.to_string();
+                    return Ok(Some(id.to_string()));
                 }
             }
         }
 
-        String::new()
+        Ok(None)
     }
 
-    /// 提取远程提交 ID
-    fn extract_remote_run_id(document: &Html) -> String {
-        let Ok(remote_run_selector) = Selector::parse(".remote-run-id a") else {
-            return String::new();
-        };
+    /// 提取远程提交 ID 与链接
+    fn extract_remote_run_id(document: &Html) -> Result<Option<(String, Option<String>)>> {
+        let remote_run_selector = parse_selector(&overrides::selector(
+            "vjudge",
+            "remote_run",
+            ".remote-run-id a",
+        ))?;
 
-        if let Some(link) = document.select(&remote_run_selector).next() {
-            let text = link.text().collect::<String>();
-            if let Some(caps) = REMOTE_RUN_ID_REGEX.captures(&text) {
-                return caps
-                    .get(0)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default();
-            }
-        }
-
-        String::new()
-    }
-
-    /// 提取编程语言
-    fn extract_language(document: &Html) -> SubmissionLanguage {
-        let Ok(info_table_selector) = Selector::parse("#info-panel table tbody tr") else {
-            return SubmissionLanguage::default();
+        let Some(link) = document.select(&remote_run_selector).next() else {
+            return Ok(None);
         };
+        let text = link.text().collect::<String>().trim().to_string();
+        let id = overrides::regex("vjudge", "remote_run_id", &REMOTE_RUN_ID_REGEX)
+            .captures(&text)
+            .and_then(|caps| caps.get(0))
+            .map(|m| m.as_str().to_string())
+            .unwrap_or(text);
+        let url = link.value().attr("href").map(str::to_string);
+
+        Ok((!id.is_empty()).then_some((id, url)))
+    }
 
-        for row in document.select(&info_table_selector) {
-            let (Ok(th_selector), Ok(td_selector)) = (Selector::parse("th"), Selector::parse("td"))
-            else {
-                continue;
-            };
-
-            if let Some(th) = row.select(&th_selector).next() {
-                let header_text = th.text().collect::<String>().to_lowercase();
-                if header_text.contains("语言")
-                    && let Some(td) = row.select(&td_selector).next()
-                {
-                    let lang_text = td.text().collect::<String>().trim().to_string();
-                    return lang_text.parse().unwrap_or(SubmissionLanguage::Cpp17);
-                }
-            }
+    /// 组装远程评测信息
+    fn extract_remote(document: &Html, oj: &str, pid: Option<&str>) -> Result<Option<RemoteInfo>> {
+        if oj == "vj" {
+            return Ok(None);
         }
 
-        // 备用: 从语言列的 tooltip 中提取
-        let Ok(lang_tooltip_selector) = Selector::parse(".language div[data-original-title]")
+        let Some(remote_pid) = pid
+            .and_then(split_remote_pid)
+            .map(|(_, p)| p)
+            .or_else(|| pid.map(str::to_string))
         else {
-            return SubmissionLanguage::default();
+            return Ok(None);
         };
+        let (rid, url) = Self::extract_remote_run_id(document)?.unwrap_or_default();
 
-        if let Some(lang_div) = document.select(&lang_tooltip_selector).next()
-            && let Some(tooltip) = lang_div.value().attr("data-original-title")
-        {
-            return tooltip.parse().unwrap_or(SubmissionLanguage::Cpp17);
-        }
+        let (oj, pid) = map_remote_oj(oj, &remote_pid);
 
-        SubmissionLanguage::default()
+        Ok(Some(RemoteInfo { oj, pid, rid, url }))
     }
 
-    /// 提取评测状态
-    fn extract_status(document: &Html) -> SubmissionStatus {
-        let Ok(status_selector) = Selector::parse(".status .view-solution") else {
-            return SubmissionStatus::default();
-        };
+    /// 提取信息面板的 标签 → 值 映射
+    fn info_panel(document: &Html) -> Result<HashMap<String, String>> {
+        kv_table(
+            document,
+            &overrides::selector("vjudge", "info_panel", INFO_PANEL_SELECTOR),
+        )
+    }
 
-        if let Some(status_div) = document.select(&status_selector).next() {
-            let status_text = status_div.text().collect::<String>().trim().to_string();
-            return status_text.parse().unwrap_or(SubmissionStatus::Unknown);
+    /// 提取编程语言
+    fn extract_language(document: &Html, info: &HashMap<String, String>) -> Result<Option<String>> {
+        if let Some(language) = info.get("language") {
+            return Ok(Some(language.clone()));
         }
 
-        // 从 info panel 中提取
-        let Ok(info_table_selector) = Selector::parse("#info-panel table tbody tr") else {
-            return SubmissionStatus::default();
-        };
-
-        for row in document.select(&info_table_selector) {
-            let (Ok(th_selector), Ok(td_selector)) = (Selector::parse("th"), Selector::parse("td"))
-            else {
-                continue;
-            };
+        // 备用: 从语言列的 tooltip 中提取
+        select_attr(document, LANGUAGE_TOOLTIP_SELECTOR, "data-original-title")
+    }
 
-            if let Some(th) = row.select(&th_selector).next() {
-                let header_text = th.text().collect::<String>().to_lowercase();
-                if header_text.contains("评测结果")
-                    && let Some(td) = row.select(&td_selector).next()
-                {
-                    let status_text = td.text().collect::<String>().trim().to_string();
-                    return status_text.parse().unwrap_or(SubmissionStatus::Unknown);
-                }
-            }
+    /// 提取评测状态
+    fn extract_status(document: &Html, info: &HashMap<String, String>) -> Result<Option<String>> {
+        let status_css = overrides::selector("vjudge", "status", STATUS_SELECTOR);
+        if let Some(status) = select_text(document, &status_css)? {
+            return Ok(Some(status));
         }
 
-        SubmissionStatus::default()
+        // 从 info panel 中提取
+        Ok(info.get("status").cloned())
     }
 
-    /// 提取时间和内存
-    fn extract_time_and_memory(document: &Html) -> (i32, i32) {
-        let mut total_time = 0;
-        let mut max_memory = 0;
-
-        // 从表格中提取
-        let Ok(runtime_selector) = Selector::parse(".runtime") else {
-            return (total_time, max_memory);
+    /// 提取时间和内存文本
+    fn extract_time_and_memory(
+        document: &Html,
+        info: &HashMap<String, String>,
+    ) -> Result<(Option<String>, Option<String>)> {
+        // 从表格中提取, 为空或为 0 时用 info panel 备用
+        let pick = |table: Option<String>, key: &str, parse: fn(&str) -> Option<u64>| match table {
+            Some(text) if parse(&text).is_some_and(|v| v > 0) => Some(text),
+            table => info.get(key).cloned().or(table),
         };
-        let Ok(memory_selector) = Selector::parse(".memory") else {
-            return (total_time, max_memory);
-        };
-
-        if let Some(runtime_td) = document.select(&runtime_selector).next() {
-            let time_text = runtime_td.text().collect::<String>().trim().to_string();
-            total_time = parse_time_to_ms(&time_text).unwrap_or(0);
-        }
 
-        if let Some(memory_td) = document.select(&memory_selector).next() {
-            let mem_text = memory_td.text().collect::<String>().trim().to_string();
-            max_memory = parse_mem_to_kb(&mem_text).unwrap_or(0);
-        }
+        Ok((
+            pick(
+                select_text(document, &overrides::selector("vjudge", "time", ".runtime"))?,
+                "time",
+                parse_time_to_ms,
+            ),
+            pick(
+                select_text(
+                    document,
+                    &overrides::selector("vjudge", "memory", ".memory"),
+                )?,
+                "memory",
+                parse_mem_to_kb,
+            ),
+        ))
+    }
 
-        // 从 info panel 中提取 (备用)
-        if total_time == 0 || max_memory == 0 {
-            let Ok(info_table_selector) = Selector::parse("#info-panel table tbody tr") else {
-                return (total_time, max_memory);
-            };
-
-            for row in document.select(&info_table_selector) {
-                let (Ok(th_selector), Ok(td_selector)) =
-                    (Selector::parse("th"), Selector::parse("td"))
-                else {
-                    continue;
-                };
-
-                if let Some(th) = row.select(&th_selector).next() {
-                    let header_text = th.text().collect::<String>().to_lowercase();
-                    if let Some(td) = row.select(&td_selector).next() {
-                        let value_text = td.text().collect::<String>().trim().to_string();
-
-                        if header_text.contains("耗时") {
-                            total_time = parse_time_to_ms(&value_text).unwrap_or(total_time);
-                        } else if header_text.contains("内存消耗") {
-                            max_memory = parse_mem_to_kb(&value_text).unwrap_or(max_memory);
-                        }
-                    }
-                }
-            }
+    /// 提取得分, 页面未展示分数时依据评测状态推断
+    fn extract_score(
+        info: &HashMap<String, String>,
+        status: Option<&SubmissionStatus>,
+    ) -> Option<u16> {
+        if let Some(score) = info.get("score").and_then(|t| parse_score(t)) {
+            return Some(score);
         }
 
-        (total_time, max_memory)
-    }
-
-    /// 提取得分
-    fn extract_score(status: &SubmissionStatus) -> i32 {
+        // 部分正确时无法推断具体分数
         match status {
-            SubmissionStatus::Accepted => 100,
-            SubmissionStatus::PartiallyCorrect => 50, // 部分正确的情况
-            _ => 0,
+            None | Some(SubmissionStatus::Unknown | SubmissionStatus::PartiallyCorrect) => None,
+            Some(SubmissionStatus::Accepted) => Some(100),
+            Some(_) => Some(0),
         }
     }
 
     /// 提取 OJ 名称
-    fn extract_oj(document: &Html) -> String {
-        let Ok(oj_selector) = Selector::parse(".oj") else {
-            return "vj".to_string();
-        };
+    fn extract_oj(document: &Html) -> Result<String> {
+        Ok(
+            select_text(document, &overrides::selector("vjudge", "oj", ".oj"))?
+                .unwrap_or_else(|| "vj".to_string()),
+        )
+    }
 
-        if let Some(oj_td) = document.select(&oj_selector).next() {
-            return oj_td.text().collect::<String>().trim().to_string();
+    /// 识别登录墙与无权限页面
+    fn detect_cause(content: &str) -> Option<ExtractErrorKind> {
+        if LOGIN_MARKERS.iter().any(|m| content.contains(m)) {
+            return Some(ExtractErrorKind::NotAuthenticated("vjudge".to_string()));
         }
-
-        "vj".to_string()
+        FORBIDDEN_MARKERS
+            .iter()
+            .any(|m| content.contains(m))
+            .then(|| ExtractErrorKind::PermissionDenied("vjudge".to_string()))
     }
 
-    fn extract_partial(&self, url: &str, content: &str) -> Submission {
+    /// 从页面 HTML 尽力提取
+    fn partial_from_html(ctx: &ExtractionContext<'_>, content: &str) -> Result<PartialSubmission> {
+        let url = ctx.url;
         let document = Html::parse_document(content);
 
-        let code = Self::extract_code(&document);
-        let pid = Self::extract_pid(&document);
-        let rid = Self::extract_rid(url, &document);
-        let language = Self::extract_language(&document);
-        let status = Self::extract_status(&document);
-        let (total_time, max_memory) = Self::extract_time_and_memory(&document);
-        let score = Self::extract_score(&status);
-        let oj = Self::extract_oj(&document);
-
-        Submission {
-            code,
+        let info = Self::info_panel(&document)?;
+        let (time_text, memory_text) = Self::extract_time_and_memory(&document, &info)?;
+        let pid = Self::extract_pid(&document)?;
+        let oj = Self::extract_oj(&document)?;
+        let remote = Self::extract_remote(&document, &oj, pid.as_deref())?;
+        // VJudge 按浏览者的本地时区显示时间, 调用方未提供时按北京时间解析
+        let offset = ctx.utc_offset_minutes.unwrap_or(CST_OFFSET_MINUTES);
+        let submitted_at = info
+            .get("submitted_at")
+            .and_then(|text| parse_relative_time(text, ctx.now, offset));
+
+        let mut partial = PartialSubmission {
+            code: Self::extract_code(&document)?.map(|c| strip_line_numbers(&normalize_code(&c))),
             pid,
-            rid,
-            oj,
-            language,
-            status,
-            total_time,
-            max_memory,
-            score,
+            rid: Self::extract_rid(url, &document)?,
+            oj: Some(oj),
+            remote,
+            submitted_at,
+            ..Default::default()
+        };
+        if let Some(text) = Self::extract_language(&document, &info)? {
+            partial.parse_language(&text);
+        }
+        if let Some(text) = Self::extract_status(&document, &info)? {
+            partial.parse_status(&text);
         }
+        if let Some(text) = time_text {
+            partial.parse_time(&text);
+        }
+        if let Some(text) = memory_text {
+            partial.parse_memory(&text);
+        }
+        partial.score = Self::extract_score(&info, partial.status.as_ref());
+        Ok(partial)
     }
 
-    /// 验证提取结果
-    fn validate_submission(sub: &Submission) -> Result<()> {
-        if sub.pid.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("pid".to_string()),
-                sub.clone(),
-            )));
-        }
-        if sub.rid.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("rid".to_string()),
-                sub.clone(),
-            )));
+    /// 从 `/solution/data/<id>` 接口返回的 JSON 提取
+    fn partial_from_json(ctx: &ExtractionContext<'_>, json: &str) -> Result<PartialSubmission> {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap_or_default();
+        let str_field = |key: &str| value.get(key).and_then(|v| v.as_str());
+        let num_field = |key: &str| value.get(key).and_then(|v| v.as_u64());
+
+        let oj = str_field("oj").unwrap_or("vj").to_string();
+        let prob = str_field("probNum");
+        let remote = prob.filter(|_| oj != "vj").map(|prob| {
+            let (oj, pid) = map_remote_oj(&oj, prob);
+            RemoteInfo {
+                oj,
+                pid,
+                rid: str_field("remoteRunId").unwrap_or_default().to_string(),
+                url: None,
+            }
+        });
+
+        let mut partial = PartialSubmission {
+            code: str_field("code").map(|c| strip_line_numbers(&normalize_code(c))),
+            pid: prob.map(|prob| format!("{oj}-{prob}")),
+            rid: num_field("runId").map(|id| id.to_string()).or_else(|| {
+                RECORD_REGEX
+                    .captures(ctx.url)
+                    .map(|caps| caps[1].to_string())
+            }),
+            oj: Some(oj),
+            remote,
+            total_time: num_field("runtime"),
+            max_memory: num_field("memory"),
+            submitted_at: num_field("submitTime"),
+            ..Default::default()
+        };
+        if let Some(text) = str_field("language") {
+            partial.parse_language(text);
         }
-        if sub.code.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("code".to_string()),
-                sub.clone(),
-            )));
+        if value.get("processing").and_then(|v| v.as_bool()) == Some(true) {
+            partial.judging = true;
+            partial.status = Some(SubmissionStatus::Unknown);
+        } else if let Some(text) = str_field("status") {
+            partial.parse_status(text);
         }
-        Ok(())
+        partial.score = Self::extract_score(&HashMap::new(), partial.status.as_ref());
+        Ok(partial)
     }
 }
 
 impl Extractor for VjudgeExtractor {
-    fn extract(&self, url: &str, content: &str) -> Result<Submission> {
-        if content.trim().is_empty() {
-            return Err(Error::Extract(ExtractError::new(
-                ExtractErrorKind::EmptyContent,
-            )));
+    fn fixture(&self) -> Option<Fixture> {
+        Some(Fixture {
+            url: "https://vjudge.net/solution/1",
+            content: FIXTURE_CONTENT,
+            pid: "HDU-1000",
+            rid: "1",
+            status: SubmissionStatus::Accepted,
+        })
+    }
+
+    fn classify(&self, url: &str, content: &str) -> PageKind {
+        if matches!(
+            Self::detect_cause(content),
+            Some(ExtractErrorKind::NotAuthenticated(_))
+        ) {
+            PageKind::Login
+        } else if RECORD_REGEX.is_match(url) {
+            PageKind::SubmissionDetail
+        } else if STATUS_REGEX.is_match(url) {
+            PageKind::RecordList
+        } else if PROBLEM_REGEX.is_match(url) {
+            PageKind::Problem
+        } else {
+            PageKind::Unknown
         }
+    }
 
-        let submission = self.extract_partial(url, content);
+    fn record_id(&self, url: &str, content: &str) -> Option<String> {
+        Self::extract_rid(url, &Html::parse_document(content))
+            .ok()
+            .flatten()
+    }
 
-        Self::validate_submission(&submission)?;
-        Ok(submission)
+    fn inputs(&self) -> &'static [InputKind] {
+        &[InputKind::Html, InputKind::Json]
+    }
+
+    fn extract_partial(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+    ) -> Result<PartialSubmission> {
+        if input.is_empty() {
+            return Err(ExtractErrorKind::EmptyContent.into());
+        }
+        match input {
+            ExtractorInput::JsonApi(json) => {
+                let mut partial = Self::partial_from_json(ctx, json)?;
+                partial.layout = Some("json".to_string());
+                Ok(partial)
+            }
+            _ => Self::partial_from_html(ctx, input.html()?),
+        }
+    }
+
+    fn extract_input(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+        policy: &ValidationPolicy,
+    ) -> Result<ExtractionReport> {
+        let partial = self.extract_merged(ctx, input)?;
+        let content = input.first(InputKind::Html).unwrap_or_default();
+        ExtractionReport::build(partial, policy).map_err(|e| {
+            Error::Extract(
+                e.with_diagnostics(STRATEGIES)
+                    .with_cause(Self::detect_cause(content)),
+            )
+        })
+    }
+}
+
+#[test]
+fn test_strategies() {
+    // 诊断中的选择器须与提取时的默认值一致
+    let selectors = [
+        CODE_SELECTOR,
+        CODE_FALLBACK_SELECTOR,
+        PROBLEM_LINK_SELECTOR,
+        SOLUTION_LINK_SELECTOR,
+        SOLUTION_ROW_SELECTOR,
+        INFO_PANEL_SELECTOR,
+        LANGUAGE_TOOLTIP_SELECTOR,
+        STATUS_SELECTOR,
+    ];
+    let others = ["PROBLEM_REGEX", "RECORD_REGEX (url)"];
+
+    for (field, tried) in STRATEGIES {
+        for css in *tried {
+            assert!(
+                selectors.contains(css) || others.contains(css),
+                "{field}: `{css}` is not used by the extractor"
+            );
+        }
+    }
+    for css in selectors {
+        assert!(parse_selector(css).is_ok(), "invalid selector `{css}`");
     }
 }
 
@@ -375,6 +520,10 @@ fn test_extract() -> Result<()> {
                                 <th>评测结果</th>
                                 <td class="status">Accepted</td>
                             </tr>
+                            <tr>
+                                <th>提交时间</th>
+                                <td>2025-01-02 12:00:00</td>
+                            </tr>
                             <tr>
                                 <th>耗时</th>
                                 <td class="time">1886ms</td>
@@ -403,6 +552,7 @@ fn test_extract() -> Result<()> {
         <table>
             <tbody>
                 <tr>
+                    <td class="remote-run-id"><a href="https://acm.uestc.edu.cn/status/3288812">3288812</a></td>
                     <td class="oj">UESTC</td>
                     <td class="status">Accepted</td>
                     <td class="runtime">1886</td>
@@ -422,9 +572,56 @@ fn test_extract() -> Result<()> {
     assert_eq!(submission.status, SubmissionStatus::Accepted);
     assert_eq!(submission.total_time, 1886);
     assert_eq!(submission.max_memory, 10752);
-    assert_eq!(submission.score, 100);
+    assert_eq!(submission.score, Some(100));
+    assert_eq!(submission.submitted_at, Some(1_735_790_400_000));
+
+    let remote = submission.remote.expect("remote info");
+    assert_eq!(remote.oj, "UESTC");
+    assert_eq!(remote.pid, "126");
+    assert_eq!(remote.rid, "3288812");
+    assert_eq!(
+        remote.url.as_deref(),
+        Some("https://acm.uestc.edu.cn/status/3288812")
+    );
 
     // println!("{}", submission.code);
 
+    // 页面时间按上下文给出的时区解析
+    let ctx = ExtractionContext::new(url).with_utc_offset(0);
+    let report = extractor.extract_input(
+        &ctx,
+        &ExtractorInput::HtmlPage(content),
+        &ValidationPolicy::default(),
+    )?;
+    assert_eq!(
+        report.submission.submitted_at,
+        Some(1_735_790_400_000 + 8 * 3_600_000)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_solution_data() -> Result<()> {
+    let url = "https://vjudge.net/solution/data/65377961";
+    let data = r#"{"runId": 65377961, "oj": "UESTC", "probNum": "126", "remoteRunId": "3288812", "status": "Accepted", "processing": false, "runtime": 1886, "memory": 10752, "language": "C++17", "submitTime": 1735790400000, "code": "int main() {}"}"#;
+
+    let ctx = ExtractionContext::new(url);
+    let input = ExtractorInput::JsonApi(data);
+    let report = VjudgeExtractor.extract_input(&ctx, &input, &ValidationPolicy::default())?;
+    assert_eq!(report.layout.as_deref(), Some("json"));
+
+    let submission = report.submission;
+    assert_eq!(submission.pid, "UESTC-126");
+    assert_eq!(submission.rid, "65377961");
+    assert_eq!(submission.status, SubmissionStatus::Accepted);
+    assert_eq!(submission.max_memory, 10752);
+    assert_eq!(submission.score, Some(100));
+
+    let remote = submission.remote.expect("remote info");
+    assert_eq!(
+        (remote.pid.as_str(), remote.rid.as_str()),
+        ("126", "3288812")
+    );
     Ok(())
 }
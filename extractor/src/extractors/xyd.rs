@@ -11,11 +11,13 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 use registry::Extractable;
-use scraper::{Html, Selector};
+use scraper::Html;
 
 use crate::error::*;
 use crate::models::*;
-use crate::traits::Extractor;
+use crate::overrides;
+use crate::policy::ValidationPolicy;
+use crate::traits::{Extractor, Fixture};
 use crate::utils::*;
 
 // 题目链接
@@ -26,6 +28,12 @@ static RECORD_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"https://(?:www\.)?xinyoudui\.com/ac/contest/.*?/problem/(\d+)").unwrap()
 });
 
+// 选中提交记录所在的行
+const SELECTED_ROW_MARKER: &str = "ac-ant-table-row-selected";
+
+// 提交记录面板
+const SUBMISSIONS_PANEL_MARKER: &str = "panel-submissions";
+
 // 从编译结果中提取时间和内存
 // 捕获带单位的 time 与 memory 字符串 (支持大小写) , 例如: "time: 350ms, memory: 141628kb" 或 "time: 0.2s, memory: 1MB"
 static TIME_MEM_REGEX: Lazy<Regex> = Lazy::new(|| {
@@ -36,17 +44,53 @@ static TIME_MEM_REGEX: Lazy<Regex> = Lazy::new(|| {
 // 从得分文本中提取分数
 static SCORE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+)\s*分").unwrap());
 
+// 未登录时跳转的登录页
+static LOGIN_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"xinyoudui\.com/(?:ac/)?(?:login|signin)\b").unwrap());
+
+// 未登录页面标记
+const LOGIN_MARKERS: &[&str] = &["请先登录", "登录后查看"];
+
+// 无权限页面标记
+const FORBIDDEN_MARKERS: &[&str] = &["暂无权限", "没有权限"];
+
+// 各字段的默认选择器, 提取与诊断共用
+const CODE_SELECTOR: &str = ".cm-line";
+const TAG_SELECTOR: &str = ".ac-ant-tag";
+const SELECTED_ROW_SELECTOR: &str = "tr.ac-ant-table-row-selected";
+
+// 自检样例
+const FIXTURE_CONTENT: &str = r#"<span class="ac-ant-tag">题目ID: 1001</span><table><tr class="ac-ant-table-row-selected"><td>1</td><td>C++17</td><td>Accepted</td><td>100 分</td></tr></table><div class="cm-line">int main() {}</div>"#;
+
+// 各字段的提取策略, 用于诊断
+static STRATEGIES: FieldStrategies = &[
+    ("code", &[CODE_SELECTOR]),
+    (
+        "pid",
+        &[TAG_SELECTOR, "PROBLEM_REGEX", "RECORD_REGEX (url)"],
+    ),
+    ("rid", &[SELECTED_ROW_SELECTOR]),
+    ("language", &[SELECTED_ROW_SELECTOR]),
+    ("status", &[SELECTED_ROW_SELECTOR]),
+];
+
 /// 信友队提取器
 #[derive(Extractable)]
-#[extractor(name = "xyd", tags = ["xinyoudui", "信友队"])]
+#[extractor(
+    name = "xyd",
+    display_name = "信友队",
+    homepage = "https://www.xinyoudui.com",
+    tags = ["xinyoudui", "信友队"],
+    hosts = ["xinyoudui.com"],
+    paths = [r"^/ac/contest/[^/]+/problem/\d+"],
+    patterns = [r"^https?://(www\.)?xinyoudui\.com/ac/contest/[^/]+/problem/\d+"]
+)]
 pub struct XinyouduiExtractor;
 
 impl XinyouduiExtractor {
     /// 提取代码
-    fn extract_code(document: &Html) -> String {
-        let Ok(code_selector) = Selector::parse(".cm-line") else {
-            return String::new();
-        };
+    fn extract_code(document: &Html) -> Result<Option<String>> {
+        let code_selector = parse_selector(&overrides::selector("xyd", "code", CODE_SELECTOR))?;
 
         let code_lines: Vec<String> = document
             .select(&code_selector)
@@ -57,205 +101,244 @@ impl XinyouduiExtractor {
             .collect();
 
         if code_lines.is_empty() {
-            return String::new();
+            return Ok(None);
         }
 
-        code_lines.join("\n") + "\n"
+        Ok(Some(code_lines.join("\n") + "\n"))
     }
 
     /// 提取题目 ID
-    fn extract_pid(url: &str, document: &Html) -> String {
-        if let Some(pid_from_page) = Self::extract_pid_from_page(document) {
-            return pid_from_page;
+    fn extract_pid(url: &str, document: &Html) -> Result<Option<String>> {
+        if let Some(pid_from_page) = Self::extract_pid_from_page(document)? {
+            return Ok(Some(pid_from_page));
         }
 
-        RECORD_REGEX
+        Ok(overrides::regex("xyd", "record", &RECORD_REGEX)
             .captures(url)
             .and_then(|caps| caps.get(1))
-            .map(|pid_match| pid_match.as_str().to_string())
-            .unwrap_or_default()
+            .map(|pid_match| pid_match.as_str().to_string()))
     }
 
     /// 从页面中提取题目 ID
-    fn extract_pid_from_page(document: &Html) -> Option<String> {
-        let Ok(tag_selector) = Selector::parse(".ac-ant-tag") else {
-            return None;
-        };
+    fn extract_pid_from_page(document: &Html) -> Result<Option<String>> {
+        let tag_selector = parse_selector(&overrides::selector("xyd", "tag", TAG_SELECTOR))?;
+        let problem_regex = overrides::regex("xyd", "problem", &PROBLEM_REGEX);
 
         for element in document.select(&tag_selector) {
             let text = element.text().collect::<String>();
-            if let Some(caps) = PROBLEM_REGEX.captures(&text)
+            if let Some(caps) = problem_regex.captures(&text)
                 && let Some(pid_match) = caps.get(1)
             {
-                return Some(pid_match.as_str().to_string());
+                return Ok(Some(pid_match.as_str().to_string()));
             }
         }
 
-        None
+        Ok(None)
     }
 
-    /// 提取提交ID
-    fn extract_rid(document: &Html) -> String {
-        let (Ok(selected_row_selector), Ok(td_selector)) = (
-            Selector::parse("tr.ac-ant-table-row-selected"),
-            Selector::parse("td"),
-        ) else {
-            return String::new();
-        };
-
-        if let Some(selected_row) = document.select(&selected_row_selector).next()
-            && let Some(first_td) = selected_row.select(&td_selector).next()
-        {
-            return first_td.text().collect::<String>().trim().to_string();
-        }
-
-        String::new()
+    /// 提取选中行的单元格文本
+    fn selected_row_cells(document: &Html) -> Result<Option<Vec<String>>> {
+        let selected_row_selector = parse_selector(&overrides::selector(
+            "xyd",
+            "selected_row",
+            SELECTED_ROW_SELECTOR,
+        ))?;
+        let td_selector = parse_selector("td")?;
+
+        Ok(document.select(&selected_row_selector).next().map(|row| {
+            row.select(&td_selector)
+                .map(|td| td.text().collect::<String>().trim().to_string())
+                .collect()
+        }))
     }
 
-    /// 提取编程语言
-    fn extract_language(document: &Html) -> SubmissionLanguage {
-        let (Ok(selected_row_selector), Ok(td_selector)) = (
-            Selector::parse("tr.ac-ant-table-row-selected"),
-            Selector::parse("td"),
-        ) else {
-            return SubmissionLanguage::Cpp17;
-        };
-
-        if let Some(selected_row) = document.select(&selected_row_selector).next() {
-            let tds: Vec<_> = selected_row.select(&td_selector).collect();
-            if tds.len() >= 2 {
-                let language_text = tds[1].text().collect::<String>().trim().to_string();
-                return language_text.parse().unwrap_or(SubmissionLanguage::Cpp17);
-            }
-        }
-
-        SubmissionLanguage::default()
+    /// 提取提交ID (第一列)
+    fn extract_rid(cells: &[String]) -> Option<String> {
+        cells.first().cloned()
     }
 
-    /// 提取状态和得分
-    fn extract_status_and_score(document: &Html) -> (SubmissionStatus, i32) {
-        let (Ok(selected_row_selector), Ok(td_selector)) = (
-            Selector::parse("tr.ac-ant-table-row-selected"),
-            Selector::parse("td"),
-        ) else {
-            return (SubmissionStatus::default(), 0);
-        };
+    /// 提取编程语言 (第二列)
+    fn extract_language(cells: &[String]) -> Option<&str> {
+        cells.get(1).map(String::as_str)
+    }
 
-        if let Some(selected_row) = document.select(&selected_row_selector).next() {
-            let tds: Vec<_> = selected_row.select(&td_selector).collect();
-
-            // 提取状态 (第三列)
-            let status = if tds.len() >= 3 {
-                tds[2]
-                    .text()
-                    .collect::<String>()
-                    .trim()
-                    .parse()
-                    .unwrap_or(SubmissionStatus::Unknown)
-            } else {
-                SubmissionStatus::default()
-            };
-
-            // 提取得分 (第四列)
-            let score = if tds.len() >= 4 {
-                let score_text = tds[3].text().collect::<String>();
-                SCORE_REGEX
-                    .captures(&score_text)
-                    .and_then(|caps| caps.get(1))
-                    .and_then(|m| m.as_str().parse().ok())
-                    .unwrap_or(0)
-            } else {
-                0
-            };
-
-            return (status, score);
-        }
+    /// 提取状态 (第三列)
+    fn extract_status(cells: &[String]) -> Option<&str> {
+        cells.get(2).map(String::as_str)
+    }
 
-        (SubmissionStatus::default(), 0)
+    /// 提取得分 (第四列)
+    fn extract_score(cells: &[String]) -> Option<&str> {
+        cells
+            .get(3)
+            .and_then(|text| overrides::regex("xyd", "score", &SCORE_REGEX).captures(text))
+            .and_then(|caps| caps.get(1))
+            .map(|m| m.as_str())
     }
 
-    /// 提取时间和内存
-    fn extract_time_and_memory(document: &Html) -> (i32, i32) {
-        let Ok(compilation_selector) = Selector::parse("._compilation_1f8cm_53") else {
-            return (0, 0);
-        };
+    /// 提取时间和内存文本
+    fn extract_time_and_memory(document: &Html) -> Result<(Option<String>, Option<String>)> {
+        let compilation_selector = parse_selector(&overrides::selector(
+            "xyd",
+            "compilation",
+            "._compilation_1f8cm_53",
+        ))?;
 
         if let Some(compilation_div) = document.select(&compilation_selector).next() {
             let compilation_text = compilation_div.text().collect::<String>();
 
-            if let Some(caps) = TIME_MEM_REGEX.captures(&compilation_text) {
-                let time_str = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-                let mem_str = caps.get(2).map(|m| m.as_str()).unwrap_or("");
-
-                let time = parse_time_to_ms(time_str).unwrap_or(0);
-                let memory = parse_mem_to_kb(mem_str).unwrap_or(0);
-                return (time, memory);
+            if let Some(caps) =
+                overrides::regex("xyd", "time_memory", &TIME_MEM_REGEX).captures(&compilation_text)
+            {
+                let time = caps.get(1).map(|m| m.as_str().to_string());
+                let memory = caps.get(2).map(|m| m.as_str().to_string());
+                return Ok((time, memory));
             }
         }
 
-        (0, 0)
+        Ok((None, None))
     }
 
-    fn extract_partial(&self, url: &str, content: &str) -> Submission {
-        let document = Html::parse_document(content);
-
-        let code = Self::extract_code(&document);
-        let pid = Self::extract_pid(url, &document);
-        let rid = Self::extract_rid(&document);
-        let language = Self::extract_language(&document);
-        let (status, score) = Self::extract_status_and_score(&document);
-        let (total_time, max_memory) = Self::extract_time_and_memory(&document);
-
-        Submission {
-            code,
-            pid,
-            rid,
-            oj: "xyd".to_string(),
-            language,
-            status,
-            total_time,
-            max_memory,
-            score,
+    /// 识别登录跳转与无权限页面
+    fn detect_cause(url: &str, content: &str) -> Option<ExtractErrorKind> {
+        if LOGIN_REGEX.is_match(url) || LOGIN_MARKERS.iter().any(|m| content.contains(m)) {
+            return Some(ExtractErrorKind::NotAuthenticated("xyd".to_string()));
         }
+        FORBIDDEN_MARKERS
+            .iter()
+            .any(|m| content.contains(m))
+            .then(|| ExtractErrorKind::PermissionDenied("xyd".to_string()))
     }
 
-    /// 验证提取结果
-    fn validate_submission(sub: &Submission) -> Result<()> {
-        if sub.pid.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("pid".to_string()),
-                sub.clone(),
-            )));
+    /// 从页面 HTML 尽力提取
+    fn partial_from_html(ctx: &ExtractionContext<'_>, content: &str) -> Result<PartialSubmission> {
+        let url = ctx.url;
+        let document = Html::parse_document(content);
+
+        let cells = Self::selected_row_cells(&document)?.unwrap_or_default();
+        let (time_text, memory_text) = Self::extract_time_and_memory(&document)?;
+
+        let mut partial = PartialSubmission {
+            code: Self::extract_code(&document)?.map(|c| strip_line_numbers(&normalize_code(&c))),
+            pid: Self::extract_pid(url, &document)?,
+            rid: Self::extract_rid(&cells),
+            oj: Some("xyd".to_string()),
+            ..Default::default()
+        };
+        if let Some(text) = Self::extract_language(&cells) {
+            partial.parse_language(text);
+        }
+        if let Some(text) = Self::extract_status(&cells) {
+            partial.parse_status(text);
         }
-        if sub.rid.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("rid".to_string()),
-                sub.clone(),
-            )));
+        if let Some(text) = Self::extract_score(&cells) {
+            partial.parse_score(text);
         }
-        if sub.code.is_empty() {
-            return Err(Error::Extract(ExtractError::with_partial(
-                ExtractErrorKind::MissingField("code".to_string()),
-                sub.clone(),
-            )));
+        if let Some(text) = time_text {
+            partial.parse_time(&text);
         }
-        Ok(())
+        if let Some(text) = memory_text {
+            partial.parse_memory(&text);
+        }
+        Ok(partial)
     }
 }
 
 impl Extractor for XinyouduiExtractor {
-    fn extract(&self, url: &str, content: &str) -> Result<Submission> {
-        if content.trim().is_empty() {
-            return Err(Error::Extract(ExtractError::new(
-                ExtractErrorKind::EmptyContent,
-            )));
+    fn fixture(&self) -> Option<Fixture> {
+        Some(Fixture {
+            url: "https://www.xinyoudui.com/ac/contest/1/problem/1",
+            content: FIXTURE_CONTENT,
+            pid: "1001",
+            rid: "1",
+            status: SubmissionStatus::Accepted,
+        })
+    }
+
+    /// 提交记录在题目页的提交面板中展示, 选中某行时视为详情页
+    fn classify(&self, url: &str, content: &str) -> PageKind {
+        if matches!(
+            Self::detect_cause(url, content),
+            Some(ExtractErrorKind::NotAuthenticated(_))
+        ) {
+            PageKind::Login
+        } else if content.contains(SELECTED_ROW_MARKER) {
+            PageKind::SubmissionDetail
+        } else if content.contains(SUBMISSIONS_PANEL_MARKER) {
+            PageKind::RecordList
+        } else if RECORD_REGEX.is_match(url) {
+            PageKind::Problem
+        } else {
+            PageKind::Unknown
+        }
+    }
+
+    /// 记录编号位于选中行的首列
+    fn record_id(&self, _url: &str, content: &str) -> Option<String> {
+        let cells = Self::selected_row_cells(&Html::parse_document(content)).ok()??;
+        Self::extract_rid(&cells)
+    }
+
+    fn extract_partial(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+    ) -> Result<PartialSubmission> {
+        if input.is_empty() {
+            return Err(ExtractErrorKind::EmptyContent.into());
         }
+        Self::partial_from_html(ctx, input.html()?)
+    }
 
-        let submission = self.extract_partial(url, content);
+    fn extract_input(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+        policy: &ValidationPolicy,
+    ) -> Result<ExtractionReport> {
+        let partial = self.extract_merged(ctx, input)?;
+        let content = input.html()?;
+        ExtractionReport::build(partial, policy).map_err(|e| {
+            Error::Extract(
+                e.with_diagnostics(STRATEGIES)
+                    .with_cause(Self::detect_cause(ctx.url, content)),
+            )
+        })
+    }
+}
 
-        Self::validate_submission(&submission)?;
-        Ok(submission)
+#[test]
+fn test_strategies() {
+    // 诊断中的选择器须与提取时的默认值一致
+    let selectors = [CODE_SELECTOR, TAG_SELECTOR, SELECTED_ROW_SELECTOR];
+    let others = ["PROBLEM_REGEX", "RECORD_REGEX (url)"];
+
+    for (field, tried) in STRATEGIES {
+        for css in *tried {
+            assert!(
+                selectors.contains(css) || others.contains(css),
+                "{field}: `{css}` is not used by the extractor"
+            );
+        }
     }
+    for css in selectors {
+        assert!(parse_selector(css).is_ok(), "invalid selector `{css}`");
+    }
+}
+
+#[test]
+fn test_invalid_score() -> Result<()> {
+    let url = "https://www.xinyoudui.com/ac/contest/74700B6AA0008E906FED34/problem/15569";
+    let content = FIXTURE_CONTENT.replace("100 分", "99999999 分");
+
+    // 分数越界时记录警告而非中止提取
+    let report =
+        XinyouduiExtractor.extract_with_report(url, &content, &ValidationPolicy::default())?;
+    assert!(report.warnings.contains(&ExtractWarning::InvalidNumber {
+        field: "score".to_string(),
+        text: "99999999".to_string(),
+    }));
+    Ok(())
 }
 
 #[test]
@@ -307,7 +390,7 @@ fn test_extractor() -> Result<()> {
     assert_eq!(submission.rid, "2542938");
     assert_eq!(submission.language, SubmissionLanguage::Cpp17);
     assert_eq!(submission.status, SubmissionStatus::Accepted);
-    assert_eq!(submission.score, 100);
+    assert_eq!(submission.score, Some(100));
     assert_eq!(submission.total_time, 350);
     assert_eq!(submission.max_memory, 141628);
 
@@ -8,24 +8,236 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use crate::constants::NO_EXTRACTOR_CANDIDATES;
 use crate::error::*;
+use crate::matcher;
 use crate::models::*;
-use crate::traits::Extractor;
+use crate::policy::ValidationPolicy;
+use crate::traits::{Extractor, Fetcher};
+use crate::utils::canonicalize_url;
 
+use arc_swap::ArcSwap;
 use once_cell::sync::Lazy;
-use std::sync::Mutex;
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::RwLock;
+
+/// 域名别名命中时的得分, 等同于域名与路径均匹配
+const ALIAS_SCORE: u32 = matcher::HOST_SCORE + matcher::PATH_SCORE;
+
+/// 无法识别的状态 / 语言的观察回调
+pub type Observer = Box<dyn Fn(&ExtractWarning) + Send + Sync>;
+
+static OBSERVER: Lazy<RwLock<Option<Observer>>> = Lazy::new(|| RwLock::new(None));
+
+/// 设置观察回调, 状态或语言文本无法识别时调用
+///
+/// 回调内不可再调用 [`set_observer`] / [`clear_observer`].
+pub fn set_observer(observer: impl Fn(&ExtractWarning) + Send + Sync + 'static) {
+    *OBSERVER.write().unwrap() = Some(Box::new(observer));
+}
+
+/// 清除观察回调
+pub fn clear_observer() {
+    *OBSERVER.write().unwrap() = None;
+}
+
+/// 通知观察回调
+pub(crate) fn notify(warning: &ExtractWarning) {
+    if let Some(observer) = OBSERVER.read().unwrap().as_ref() {
+        observer(warning);
+    }
+}
+
+/// 提取器元数据
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct ExtractorInfo {
+    /// 稳定标识, 可用于按名称创建与配置
+    pub id: &'static str,
+    pub name: &'static str,
+    /// 面向用户的显示名称
+    pub display_name: &'static str,
+    /// OJ 主页
+    pub homepage: Option<&'static str>,
+    pub tags: Vec<&'static str>,
+    /// URL 中用于匹配的关键字
+    pub url_patterns: Vec<&'static str>,
+    pub version: &'static str,
+    pub priority: i32,
+    /// 支持的页面布局
+    pub layouts: Vec<&'static str>,
+}
 
 /// 提取器注册项
+///
+/// 内置提取器由 `#[derive(Extractable)]` 生成, 外部提取器可通过 [`ExtractorRegistryItem::new`] 构造后 [`register`].
 #[derive(Clone)]
-pub(crate) struct ExtractorRegistryItem {
+pub struct ExtractorRegistryItem {
     pub(crate) name_fn: fn() -> &'static str,
+    pub(crate) id: &'static str,
+    pub(crate) display_name: &'static str,
+    pub(crate) homepage: Option<&'static str>,
+    pub(crate) tags: &'static [&'static str],
+    pub(crate) url_patterns: &'static [&'static str],
+    /// 预检用的子串关键字 (小写), 与子串回退匹配一致
+    pub(crate) keywords: &'static [&'static str],
+    /// 域名白名单, 为空时不做预检
+    pub(crate) hosts: &'static [&'static str],
+    pub(crate) version: &'static str,
+    /// 得分相同时优先级高者胜出
+    pub(crate) priority: i32,
     pub(crate) rank_fn: fn(url: &str) -> u32,
     pub(crate) creator: fn() -> Box<dyn Extractor>,
 }
 
+impl ExtractorRegistryItem {
+    /// 手动构造注册项
+    pub fn new(
+        name_fn: fn() -> &'static str,
+        rank_fn: fn(url: &str) -> u32,
+        creator: fn() -> Box<dyn Extractor>,
+    ) -> Self {
+        Self {
+            name_fn,
+            id: name_fn(),
+            display_name: name_fn(),
+            homepage: None,
+            tags: &[],
+            url_patterns: &[],
+            keywords: &[],
+            hosts: &[],
+            version: "0.0.0",
+            priority: 0,
+            rank_fn,
+            creator,
+        }
+    }
+
+    /// 设置稳定标识, 默认与名称相同
+    pub fn with_id(mut self, id: &'static str) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// 设置显示名称, 默认与名称相同
+    pub fn with_display_name(mut self, display_name: &'static str) -> Self {
+        self.display_name = display_name;
+        self
+    }
+
+    /// 设置 OJ 主页
+    pub fn with_homepage(mut self, homepage: &'static str) -> Self {
+        self.homepage = Some(homepage);
+        self
+    }
+
+    /// 设置标签
+    pub fn with_tags(mut self, tags: &'static [&'static str]) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// 设置 URL 匹配关键字, 仅用于展示
+    pub fn with_url_patterns(mut self, url_patterns: &'static [&'static str]) -> Self {
+        self.url_patterns = url_patterns;
+        self
+    }
+
+    /// 设置预检用的子串关键字, 应为小写
+    pub fn with_keywords(mut self, keywords: &'static [&'static str]) -> Self {
+        self.keywords = keywords;
+        self
+    }
+
+    /// 设置域名白名单
+    pub fn with_hosts(mut self, hosts: &'static [&'static str]) -> Self {
+        self.hosts = hosts;
+        self
+    }
+
+    /// 名称或标识是否为 `name` (大小写不敏感)
+    pub(crate) fn is_named(&self, name: &str) -> bool {
+        let name = name.trim();
+        (self.name_fn)().eq_ignore_ascii_case(name) || self.id.eq_ignore_ascii_case(name)
+    }
+
+    /// 快速预检: 域名命中白名单, 或 url 含有子串关键字
+    ///
+    /// 未命中时无需调用 `rank_fn`, 得分记为 0.
+    pub fn supports(&self, url: &str) -> bool {
+        if self.hosts.is_empty() || matcher::host_matches(self.hosts, url) {
+            return true;
+        }
+        let url = url.to_lowercase();
+        self.keywords.iter().any(|k| url.contains(k))
+    }
+
+    /// 设置版本
+    pub fn with_version(mut self, version: &'static str) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// 设置优先级
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// 提取器自检结果
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct VerifyResult {
+    pub name: &'static str,
+    /// 失败原因, 通过时为空
+    pub error: Option<String>,
+}
+
+impl VerifyResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// 工厂配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct FactoryConfig {
+    /// 不参与 URL 匹配的提取器名称或标识, 仍可按名称显式选择
+    pub disabled: Vec<String>,
+    /// 优先选用的提取器名称或标识, 只要得分大于 0 即先于其他提取器, 按列表顺序
+    pub pinned: Vec<String>,
+    /// 域名别名: 域名通配符 -> 提取器名称或标识, 用于镜像 / 代理域名
+    pub aliases: HashMap<String, String>,
+}
+
+impl FactoryConfig {
+    fn is_disabled(&self, item: &ExtractorRegistryItem) -> bool {
+        self.disabled.iter().any(|n| item.is_named(n))
+    }
+
+    fn is_aliased(&self, item: &ExtractorRegistryItem, url: &str) -> bool {
+        self.aliases.iter().any(|(host, target)| {
+            item.is_named(target) && matcher::host_matches(&[host.as_str()], url)
+        })
+    }
+
+    fn pin_index(&self, item: &ExtractorRegistryItem) -> usize {
+        self.pinned
+            .iter()
+            .position(|n| item.is_named(n))
+            .unwrap_or(usize::MAX)
+    }
+}
+
 /// 提取器工厂
+#[derive(Clone)]
 pub(crate) struct ExtractorFactory {
     extractors: Vec<ExtractorRegistryItem>,
+    config: FactoryConfig,
 }
 
 impl ExtractorFactory {
@@ -33,41 +245,827 @@ impl ExtractorFactory {
     pub fn new() -> Self {
         let mut items: Vec<ExtractorRegistryItem> = Vec::new();
         items.extend(crate::extractors::registry_items());
-        Self { extractors: items }
+        Self {
+            extractors: items,
+            config: FactoryConfig::default(),
+        }
     }
 
-    /// 根据 URL 创建最匹配的提取器返回提取器实例和提取器名称
-    pub fn create_extractor(&self, url: &str) -> Result<(Box<dyn Extractor>, String)> {
+    /// 替换工厂配置
+    pub fn configure(&mut self, config: FactoryConfig) {
+        self.config = config;
+    }
+
+    /// 注册提取器, 同名提取器将被替换
+    pub fn register(&mut self, item: ExtractorRegistryItem) {
+        let name = (item.name_fn)();
+        match self.extractors.iter_mut().find(|i| (i.name_fn)() == name) {
+            Some(existing) => *existing = item,
+            None => self.extractors.push(item),
+        }
+    }
+
+    /// 全部已注册提取器的元数据
+    pub fn list_extractors(&self) -> Vec<ExtractorInfo> {
+        self.extractors
+            .iter()
+            .map(|item| ExtractorInfo {
+                id: item.id,
+                name: (item.name_fn)(),
+                display_name: item.display_name,
+                homepage: item.homepage,
+                tags: item.tags.to_vec(),
+                url_patterns: item.url_patterns.to_vec(),
+                version: item.version,
+                priority: item.priority,
+                layouts: (item.creator)().layouts().to_vec(),
+            })
+            .collect()
+    }
+
+    /// 运行全部提取器的自检样例, 没有样例的提取器不在结果中
+    pub fn verify_all(&self) -> Vec<VerifyResult> {
+        self.extractors
+            .iter()
+            .filter_map(|item| {
+                let extractor = (item.creator)();
+                let fixture = extractor.fixture()?;
+                let error = contain_panic(|| Ok(fixture.check(extractor.as_ref())))
+                    .unwrap_or_else(|e| Err(e.to_string()))
+                    .err();
+                Some(VerifyResult {
+                    name: (item.name_fn)(),
+                    error,
+                })
+            })
+            .collect()
+    }
+
+    /// 按名称或标识创建提取器 (大小写不敏感)
+    pub fn create_extractor_by_name(&self, name: &str) -> Result<(Box<dyn Extractor>, String)> {
+        let name = name.trim();
+        self.extractors
+            .iter()
+            .find(|item| item.is_named(name))
+            .map(|item| ((item.creator)(), (item.name_fn)().to_string()))
+            .ok_or_else(|| ExtractErrorKind::NoExtractor(name.to_string()).into())
+    }
+
+    /// 按选择顺序排列的候选提取器及其得分, 不含已禁用的提取器
+    fn ranked(&self, url: &str) -> Vec<(u32, &ExtractorRegistryItem)> {
         let mut candidates: Vec<_> = self
             .extractors
             .iter()
-            .map(|item| ((item.rank_fn)(url), item))
+            .filter(|item| !self.config.is_disabled(item))
+            .map(|item| {
+                if self.config.is_aliased(item, url) {
+                    (ALIAS_SCORE, item)
+                } else if item.supports(url) {
+                    ((item.rank_fn)(url), item)
+                } else {
+                    (0, item)
+                }
+            })
             .collect();
 
-        // 按分数降序排序
-        candidates.sort_by(|a, b| b.0.cmp(&a.0));
+        // 置顶的提取器优先, 其余依次按分数、优先级降序, 最后按名称排序
+        candidates.sort_by_key(|(score, item)| {
+            (
+                self.pin_index(*score, item),
+                std::cmp::Reverse(*score),
+                std::cmp::Reverse(item.priority),
+                (item.name_fn)(),
+            )
+        });
+        candidates
+    }
 
-        if let Some((highest_score, item)) = candidates.first()
-            && *highest_score > 0
+    fn pin_index(&self, score: u32, item: &ExtractorRegistryItem) -> usize {
+        match score {
+            0 => usize::MAX,
+            _ => self.config.pin_index(item),
+        }
+    }
+
+    /// 全部候选提取器的名称与得分, 按选择顺序排列
+    pub fn rank_all(&self, url: &str) -> Vec<(String, u32)> {
+        let url = &canonicalize_url(url);
+        self.ranked(url)
+            .into_iter()
+            .map(|(score, item)| ((item.name_fn)().to_string(), score))
+            .collect()
+    }
+
+    /// 根据 URL 创建最匹配的提取器返回提取器实例和提取器名称
+    pub fn create_extractor(&self, url: &str) -> Result<(Box<dyn Extractor>, String)> {
+        self.select(url).map(|s| (s.extractor, s.name))
+    }
+
+    /// 选择最匹配的提取器, 并报告未置顶且得分与优先级并列的候选
+    fn select(&self, url: &str) -> Result<Selection> {
+        let url = &canonicalize_url(url);
+        let candidates = self.ranked(url);
+
+        if let Some(&(highest_score, item)) = candidates.first()
+            && highest_score > 0
         {
-            let inst = (item.creator)();
             let name = (item.name_fn)().to_string();
-            return Ok((inst, name));
+            let tied: Vec<String> = candidates[1..]
+                .iter()
+                .filter(|(score, other)| {
+                    self.pin_index(highest_score, item) == usize::MAX
+                        && self.pin_index(*score, other) == usize::MAX
+                        && *score == highest_score
+                        && other.priority == item.priority
+                })
+                .map(|(_, other)| (other.name_fn)().to_string())
+                .collect();
+            tracing::debug!(url, extractor = %name, score = highest_score, ?tied, "selected extractor");
+            return Ok(Selection {
+                extractor: (item.creator)(),
+                ambiguity: (!tied.is_empty()).then(|| ExtractWarning::AmbiguousExtractor {
+                    chosen: name.clone(),
+                    tied,
+                }),
+                name,
+            });
         }
 
-        Err(Error::NoExtractor(url.to_string()))
+        tracing::debug!(url, "no extractor matched");
+        Err(Error::NoExtractor {
+            url: url.to_string(),
+            candidates: candidates
+                .iter()
+                .take(NO_EXTRACTOR_CANDIDATES)
+                .map(|(score, item)| ExtractorCandidate {
+                    name: (item.name_fn)(),
+                    score: *score,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// 工厂的选择结果
+struct Selection {
+    extractor: Box<dyn Extractor>,
+    name: String,
+    ambiguity: Option<ExtractWarning>,
+}
+
+impl Selection {
+    /// 补全来源信息与选择过程中的警告
+    fn finish(self, url: &str, mut report: ExtractionReport) -> ExtractionReport {
+        tracing::debug!(
+            url,
+            extractor = %self.name,
+            layout = ?report.layout,
+            warnings = report.warnings.len(),
+            "extracted submission"
+        );
+        report.submission = report.submission.with_provenance(url, &self.name);
+        report.warnings.extend(self.ambiguity);
+        report
     }
 }
 
-static FACTORY: Lazy<Mutex<ExtractorFactory>> = Lazy::new(|| Mutex::new(ExtractorFactory::new()));
+// 注册与配置极少发生, 以复制后整体替换的方式更新, 提取时无锁读取当前快照
+static FACTORY: Lazy<ArcSwap<ExtractorFactory>> =
+    Lazy::new(|| ArcSwap::from_pointee(ExtractorFactory::new()));
 
 /// 创建提取器
 pub fn create_extractor(url: &str) -> Result<(Box<dyn Extractor>, String)> {
-    FACTORY.lock().unwrap().create_extractor(url)
+    FACTORY.load().create_extractor(url)
+}
+
+/// 替换全局工厂配置, 用于禁用或置顶提取器
+pub fn configure(config: FactoryConfig) {
+    FACTORY.rcu(|factory| {
+        let mut factory = ExtractorFactory::clone(factory);
+        factory.configure(config.clone());
+        factory
+    });
+}
+
+/// 各提取器对 url 的得分, 用于排查选错提取器的问题
+pub fn rank_all(url: &str) -> Vec<(String, u32)> {
+    FACTORY.load().rank_all(url)
+}
+
+/// 按名称创建提取器, 跳过 URL 匹配
+pub fn create_extractor_by_name(name: &str) -> Result<(Box<dyn Extractor>, String)> {
+    FACTORY.load().create_extractor_by_name(name)
+}
+
+/// 判断页面类型, 无匹配提取器时返回 [`PageKind::Unknown`]
+pub fn classify(url: &str, content: &str) -> PageKind {
+    let url = &canonicalize_url(url);
+    match create_extractor(url) {
+        Ok((ext, _)) => contain_panic(|| Ok(ext.classify(url, content))).unwrap_or_default(),
+        Err(_) => PageKind::Unknown,
+    }
+}
+
+/// 识别页面所属 OJ、页面类型与记录编号, 不做完整提取
+pub fn detect_page(url: &str, content: &str) -> PageInfo {
+    let url = &canonicalize_url(url);
+    let (ext, oj) = {
+        let factory = FACTORY.load();
+        let Ok(selection) = factory.select(url) else {
+            return PageInfo::default();
+        };
+        let oj = factory
+            .extractors
+            .iter()
+            .find(|item| (item.name_fn)() == selection.name)
+            .map(|item| item.id.to_string());
+        (selection.extractor, oj)
+    };
+    PageInfo {
+        oj,
+        page_kind: contain_panic(|| Ok(ext.classify(url, content))).unwrap_or_default(),
+        record_id: contain_panic(|| Ok(ext.record_id(url, content))).unwrap_or_default(),
+    }
+}
+
+/// 使用指定名称的提取器直接提取
+pub fn extract_with(name: &str, url: &str, content: &str) -> Result<Submission> {
+    let url = &canonicalize_url(url);
+    let (ext, name) = create_extractor_by_name(name)?;
+    let report =
+        contain_panic(|| ext.extract_with_report(url, content, &ValidationPolicy::default()))?;
+    Ok(report.submission.with_provenance(url, &name))
+}
+
+/// 在运行时注册提取器, 同名提取器将被替换
+pub fn register(item: ExtractorRegistryItem) {
+    FACTORY.rcu(|factory| {
+        let mut factory = ExtractorFactory::clone(factory);
+        factory.register(item.clone());
+        factory
+    });
+}
+
+/// 运行全部提取器的自检样例, 可在启动时调用以提示已失效的提取器
+pub fn verify_all() -> Vec<VerifyResult> {
+    FACTORY.load().verify_all()
+}
+
+/// 列出已注册的提取器
+pub fn list_extractors() -> Vec<ExtractorInfo> {
+    FACTORY.load().list_extractors()
 }
 
 /// 直接提取
 pub fn extract(url: &str, content: &str) -> Result<Submission> {
-    let (ext, _name) = FACTORY.lock().unwrap().create_extractor(url)?;
-    ext.extract(url, content)
+    extract_with_policy(url, content, &ValidationPolicy::default())
+}
+
+/// 直接提取, 并按给定策略校验
+pub fn extract_with_policy(
+    url: &str,
+    content: &str,
+    policy: &ValidationPolicy,
+) -> Result<Submission> {
+    extract_with_report(url, content, policy).map(|report| report.submission)
+}
+
+/// 直接提取, 同时返回非致命的警告
+pub fn extract_with_report(
+    url: &str,
+    content: &str,
+    policy: &ValidationPolicy,
+) -> Result<ExtractionReport> {
+    let url = &canonicalize_url(url);
+    let selection = FACTORY.load().select(url)?;
+    let report = contain_panic(|| {
+        selection
+            .extractor
+            .extract_with_report(url, content, policy)
+    })?;
+    Ok(selection.finish(url, report))
+}
+
+/// 按上下文直接提取任意输入, 同时返回非致命的警告
+pub fn extract_input(
+    ctx: &ExtractionContext<'_>,
+    input: &ExtractorInput<'_>,
+    policy: &ValidationPolicy,
+) -> Result<ExtractionReport> {
+    let url = &canonicalize_url(ctx.url);
+    let ctx = ExtractionContext { url, ..ctx.clone() };
+    let selection = FACTORY.load().select(url)?;
+    let report = contain_panic(|| selection.extractor.extract_input(&ctx, input, policy))?;
+    Ok(selection.finish(url, report))
+}
+
+/// 尽力提取, 不做校验
+pub fn extract_partial(url: &str, content: &str) -> Result<PartialSubmission> {
+    let url = &canonicalize_url(url);
+    let (ext, _) = create_extractor(url)?;
+    let ctx = ExtractionContext::new(url);
+    contain_panic(|| ext.extract_partial(&ctx, &ExtractorInput::HtmlPage(content)))
+}
+
+/// 异步提取, 提取器可通过 `fetcher` 获取补充资源
+///
+/// 仅同步部分的 panic 会被转换为错误.
+pub async fn extract_async(
+    url: &str,
+    content: &str,
+    policy: &ValidationPolicy,
+    fetcher: &dyn Fetcher,
+) -> Result<ExtractionReport> {
+    let url = &canonicalize_url(url);
+    let selection = FACTORY.load().select(url)?;
+    let future = contain_panic(|| {
+        Ok(selection
+            .extractor
+            .extract_async(url, content, policy, fetcher))
+    })?;
+    let report = future.await?;
+    Ok(selection.finish(url, report))
+}
+
+/// 将提取过程中的 panic 转换为 [`ExtractErrorKind::Internal`]
+///
+/// 直接调用提取器方法时应以此包裹. wasm32 目标不支持栈展开, panic 仍会中止实例,
+/// 此时仅由运行时的 panic hook 输出日志.
+pub fn contain_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(ExtractErrorKind::Internal(panic_message(&*payload)).into()))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::BoxFuture;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    struct NoFetch;
+
+    impl Fetcher for NoFetch {
+        fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String>> {
+            Box::pin(std::future::ready(Err(ExtractErrorKind::Other(
+                url.to_string(),
+            )
+            .into())))
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_list_extractors() {
+        let list = list_extractors();
+        let names: Vec<_> = list.iter().map(|info| info.name).collect();
+        assert_eq!(names, ["luogu", "vj", "xyd"]);
+        assert_eq!(list[1].priority, 10);
+        assert_eq!(list[0].priority, 0);
+        assert_eq!(list[0].display_name, "洛谷");
+        assert_eq!(list[1].id, "vjudge");
+        assert_eq!(list[1].homepage, Some("https://vjudge.net"));
+        assert!(create_extractor_by_name("vjudge").is_ok());
+
+        let xyd = &list[2];
+        assert_eq!(xyd.tags, ["xinyoudui", "信友队"]);
+        assert_eq!(xyd.url_patterns.len(), 5);
+        assert!(xyd.url_patterns[0].starts_with("^https?://"));
+        assert_eq!(
+            xyd.url_patterns[1..],
+            ["xinyoudui.com", "xyd", "xinyoudui", "信友队"]
+        );
+        assert_eq!(xyd.version, env!("CARGO_PKG_VERSION"));
+
+        // 预检只使用名称与标签, 不以正则源码做子串匹配
+        let items = crate::extractors::registry_items();
+        let xyd = items.iter().find(|item| item.id == "xyd").unwrap();
+        assert_eq!(xyd.keywords, ["xyd", "xinyoudui", "信友队"]);
+        assert!(xyd.supports("https://mirror.example.com/xyd/submission/1"));
+        assert!(!xyd.supports("https://example.com/submission/1"));
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_manifest() {
+        let manifest: serde_json::Value =
+            serde_json::from_str(crate::extractors::MANIFEST).unwrap();
+        let names: Vec<_> = manifest["extractors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["name"].as_str().unwrap())
+            .collect();
+        let listed: Vec<_> = list_extractors().iter().map(|info| info.name).collect();
+        assert_eq!(names, listed);
+
+        assert_eq!(manifest["extractors"][1]["id"], "vjudge");
+        let matches = manifest["matches"].as_array().unwrap();
+        assert!(matches.contains(&"*://*.luogu.com.cn/*".into()));
+        assert!(matches.contains(&"*://vjudge.net/*".into()));
+    }
+
+    struct EchoExtractor;
+
+    impl Extractor for EchoExtractor {
+        fn extract_partial(
+            &self,
+            ctx: &ExtractionContext<'_>,
+            input: &ExtractorInput<'_>,
+        ) -> Result<PartialSubmission> {
+            Ok(PartialSubmission {
+                code: Some(input.html()?.to_string()),
+                pid: Some("1".to_string()),
+                rid: ctx.url.rsplit('/').next().map(str::to_string),
+                oj: Some("echo".to_string()),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_register() {
+        let mut factory = ExtractorFactory::new();
+        let item = ExtractorRegistryItem::new(
+            || "echo",
+            |url| if url.contains("echo.test") { 100 } else { 0 },
+            || Box::new(EchoExtractor),
+        )
+        .with_tags(&["echo"]);
+        factory.register(item.clone());
+        factory.register(item);
+
+        assert_eq!(factory.list_extractors().len(), 4);
+        let (ext, name) = factory.create_extractor("https://echo.test/r/42").unwrap();
+        assert_eq!(name, "echo");
+        let submission = ext
+            .extract("https://echo.test/r/42", "int main() {}")
+            .unwrap();
+        assert_eq!(submission.rid, "42");
+        let err = ext.extract_input(
+            &ExtractionContext::new("https://echo.test/r/42"),
+            &ExtractorInput::JsonApi("{}"),
+            &ValidationPolicy::default(),
+        );
+        assert_eq!(err.unwrap_err().code(), "unsupported_input");
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_verify_all() {
+        let results = ExtractorFactory::new().verify_all();
+        assert_eq!(results.len(), 3);
+        for result in results {
+            assert!(result.passed(), "{}: {:?}", result.name, result.error);
+        }
+    }
+
+    #[test]
+    fn test_supports() {
+        let item = ExtractorRegistryItem::new(
+            || "echo",
+            |_| panic!("rank_fn should be skipped"),
+            || Box::new(EchoExtractor),
+        )
+        .with_hosts(&["echo.test"])
+        .with_keywords(&["echo"]);
+        assert!(item.supports("https://www.echo.test/r/1"));
+        assert!(item.supports("https://mirror.edu/echo/r/1"));
+        assert!(!item.supports("https://example.com/r/1"));
+
+        let mut factory = ExtractorFactory::new();
+        factory.register(item);
+        let ranks = factory.rank_all("https://example.com/r/1");
+        assert!(ranks.contains(&("echo".to_string(), 0)));
+    }
+
+    #[test]
+    fn test_tie_breaking() {
+        let mut factory = ExtractorFactory::new();
+        let echo = |name: fn() -> &'static str| {
+            ExtractorRegistryItem::new(
+                name,
+                |url| if url.contains("echo.test") { 100 } else { 0 },
+                || Box::new(EchoExtractor),
+            )
+        };
+        factory.register(echo(|| "echo-b"));
+        factory.register(echo(|| "echo-a"));
+
+        let url = "https://echo.test/r/1";
+        let selection = factory.select(url).unwrap();
+        assert_eq!(selection.name, "echo-a");
+        assert_eq!(
+            selection.ambiguity,
+            Some(ExtractWarning::AmbiguousExtractor {
+                chosen: "echo-a".to_string(),
+                tied: vec!["echo-b".to_string()],
+            })
+        );
+
+        factory.register(echo(|| "echo-b").with_priority(1));
+        let selection = factory.select(url).unwrap();
+        assert_eq!(selection.name, "echo-b");
+        assert_eq!(selection.ambiguity, None);
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_configure() {
+        let url = "https://vjudge.net/article/luogu-123";
+        let mut factory = ExtractorFactory::new();
+        assert_eq!(factory.create_extractor(url).unwrap().1, "vj");
+
+        factory.configure(FactoryConfig {
+            pinned: vec!["luogu".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(factory.create_extractor(url).unwrap().1, "luogu");
+
+        factory.configure(FactoryConfig {
+            disabled: vec!["VJ".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(factory.create_extractor(url).unwrap().1, "luogu");
+        assert!(factory.create_extractor_by_name("vj").is_ok());
+        assert_eq!(
+            factory.rank_all(url),
+            [("luogu".to_string(), 20), ("xyd".to_string(), 0)]
+        );
+
+        factory.configure(FactoryConfig {
+            aliases: HashMap::from([("luogu.proxy.*.edu".to_string(), "luogu".to_string())]),
+            ..Default::default()
+        });
+        let proxied = "https://luogu.proxy.school.edu/record/1";
+        assert_eq!(
+            factory.rank_all(proxied)[0],
+            ("luogu".to_string(), ALIAS_SCORE)
+        );
+
+        // 别名可指向提取器标识
+        factory.configure(FactoryConfig {
+            aliases: HashMap::from([("vj.mirror.edu".to_string(), "vjudge".to_string())]),
+            ..Default::default()
+        });
+        assert_eq!(
+            factory.rank_all("https://vj.mirror.edu/solution/1")[0],
+            ("vj".to_string(), ALIAS_SCORE)
+        );
+
+        // 名称与标识均可用于禁用与置顶, VJudge 的名称为 `vj`, 标识为 `vjudge`
+        factory.configure(FactoryConfig {
+            disabled: vec!["vjudge".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(factory.create_extractor(url).unwrap().1, "luogu");
+        factory.configure(FactoryConfig {
+            pinned: vec!["xyd".to_string(), "VJudge".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(factory.create_extractor(url).unwrap().1, "vj");
+
+        let config: FactoryConfig = serde_json::from_str(r#"{"disabled": ["xyd"]}"#).unwrap();
+        assert_eq!(config.disabled, ["xyd"]);
+        assert!(config.pinned.is_empty());
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_extract_with() {
+        // URL 不含任何标签, 仅凭名称选择提取器
+        let url = "https://mirror.example.com/record/1";
+        let content = r#"<a href="/problem/P1001">P1001</a><pre><code>int main() {}</code></pre>"#;
+
+        let submission = extract_with("Luogu", url, content).unwrap();
+        assert_eq!(submission.pid, "P1001");
+        assert_eq!(extract(url, content).unwrap_err().code(), "no_extractor");
+
+        let Err(err) = create_extractor_by_name("codeforces") else {
+            panic!("unknown name should fail");
+        };
+        assert_eq!(err.code(), "no_extractor");
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_extract_partial() {
+        // 缺少代码, 校验失败但仍能拿到已找到的字段
+        let url = "https://www.luogu.com.cn/record/1";
+        let content = r#"<a href="/problem/P1001">P1001</a>"#;
+        assert_eq!(extract(url, content).unwrap_err().code(), "missing_field");
+
+        let partial = extract_partial(url, content).unwrap();
+        assert_eq!(partial.pid.as_deref(), Some("P1001"));
+        assert_eq!(partial.rid.as_deref(), Some("1"));
+        assert_eq!(partial.code, None);
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_classify() {
+        let content = r#"<div id="rc-tabs-0-panel-submissions"><table></table></div>"#;
+        let url = "https://www.xinyoudui.com/ac/contest/74700B6AA0008E906FED34/problem/15569";
+        assert_eq!(classify(url, content), PageKind::RecordList);
+        assert_eq!(classify("https://example.com", content), PageKind::Unknown);
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge", feature = "xyd"))]
+    #[test]
+    fn test_extract_async() {
+        let url = "https://www.luogu.com.cn/record/1";
+        let content = r#"<a href="/problem/P1001">P1001</a><pre><code>int main() {}</code></pre>"#;
+        let policy = ValidationPolicy::default();
+
+        let report = block_on(extract_async(url, content, &policy, &NoFetch)).unwrap();
+        assert_eq!(report.submission.pid, "P1001");
+
+        let err = block_on(extract_async("https://example.com", "", &policy, &NoFetch));
+        assert_eq!(err.unwrap_err().code(), "no_extractor");
+    }
+
+    #[derive(registry::Extractable, registry::DeclarativeExtractor)]
+    #[extractor(name = "demo", domains = ["demo.oj"])]
+    struct DemoExtractor {
+        #[field(selector = "pre code")]
+        code: (),
+        #[field(selector = "a.problem", attr = "href", regex = r"/problem/(\w+)")]
+        pid: (),
+        #[field(value = "demo")]
+        oj: (),
+        #[field(selector = ".status")]
+        status: (),
+        #[field(
+            selector = ".stat .value",
+            regex = r"(\d+)",
+            parse_with = "crate::utils::parse_time_to_ms"
+        )]
+        total_time: (),
+    }
+
+    #[derive(registry::DeclarativeExtractor)]
+    #[layout(name = "new", detect = "#app")]
+    #[layout(name = "old", detect = ".info-rows")]
+    struct LayoutsExtractor {
+        #[field(layout = "new", selector = "#app .pid")]
+        #[field(layout = "old", selector = ".info-rows a")]
+        pid: (),
+        #[field(selector = ".status")]
+        status: (),
+    }
+
+    #[test]
+    fn test_declarative_layouts() {
+        let ext = LayoutsExtractor {
+            pid: (),
+            status: (),
+        };
+        let ctx = ExtractionContext::new("https://demo.oj/r/1");
+        let extract = |content| ext.extract_partial(&ctx, &ExtractorInput::HtmlPage(content));
+
+        let new = r#"<div id="app"><span class="pid">P2</span><span class="status">Accepted</span></div>"#;
+        let partial = extract(new).unwrap();
+        assert_eq!(partial.layout.as_deref(), Some("new"));
+        assert_eq!(partial.pid.as_deref(), Some("P2"));
+        assert_eq!(partial.status, Some(SubmissionStatus::Accepted));
+
+        let old = r#"<div class="info-rows"><a>P1</a></div>"#;
+        let partial = extract(old).unwrap();
+        assert_eq!(partial.layout.as_deref(), Some("old"));
+        assert_eq!(partial.pid.as_deref(), Some("P1"));
+        assert_eq!(ext.detect_layout(old), Some("old"));
+        assert_eq!(ext.layouts(), ["new", "old"]);
+
+        let err = extract("<p>unknown</p>").unwrap_err();
+        assert_eq!(err.code(), "layout_mismatch");
+    }
+
+    #[test]
+    fn test_declarative() {
+        let mut factory = ExtractorFactory::new();
+        factory.register(__EXTRACTOR_REGISTRY_DEMOEXTRACTOR());
+        let content = r#"
+            <a class="problem" href="/problem/A1">A1</a>
+            <span class="status">Accepted</span>
+            <div class="stat"><span class="value">耗时 15 ms</span></div>
+            <pre><code>int main() {}</code></pre>
+        "#;
+
+        let (ext, name) = factory.create_extractor("https://demo.oj/r/1").unwrap();
+        assert_eq!(name, "demo");
+        let partial = ext
+            .extract_partial(
+                &ExtractionContext::new("https://demo.oj/r/1"),
+                &ExtractorInput::HtmlPage(content),
+            )
+            .unwrap();
+        assert_eq!(partial.code.as_deref(), Some("int main() {}"));
+        assert_eq!(partial.pid.as_deref(), Some("A1"));
+        assert_eq!(partial.oj.as_deref(), Some("demo"));
+        assert_eq!(partial.status, Some(SubmissionStatus::Accepted));
+        assert_eq!(partial.total_time, Some(15));
+        assert!(partial.rid.is_none());
+    }
+
+    /// 带配置的提取器, 以记录所在域名作为题号
+    #[derive(Default, registry::Extractable)]
+    #[extractor(name = "configured")]
+    struct ConfiguredExtractor {
+        domains: Vec<String>,
+    }
+
+    impl Extractor for ConfiguredExtractor {
+        fn extract_partial(
+            &self,
+            ctx: &ExtractionContext<'_>,
+            _input: &ExtractorInput<'_>,
+        ) -> Result<PartialSubmission> {
+            Ok(PartialSubmission {
+                pid: self.domains.iter().find(|d| ctx.url.contains(*d)).cloned(),
+                ..Default::default()
+            })
+        }
+    }
+
+    /// 通过 `constructor` 构造的提取器
+    #[derive(registry::Extractable)]
+    #[extractor(name = "mirrored", constructor = MirroredExtractor::mirrors)]
+    struct MirroredExtractor(ConfiguredExtractor);
+
+    impl MirroredExtractor {
+        fn mirrors() -> Self {
+            Self(ConfiguredExtractor {
+                domains: vec!["mirror.test".to_string()],
+            })
+        }
+    }
+
+    impl Extractor for MirroredExtractor {
+        fn extract_partial(
+            &self,
+            ctx: &ExtractionContext<'_>,
+            input: &ExtractorInput<'_>,
+        ) -> Result<PartialSubmission> {
+            self.0.extract_partial(ctx, input)
+        }
+    }
+
+    #[test]
+    fn test_non_unit_extractor() {
+        let url = "https://mirror.test/r/1";
+        let ctx = ExtractionContext::new(url);
+        let input = ExtractorInput::HtmlPage("<html></html>");
+
+        let configured = (__EXTRACTOR_REGISTRY_CONFIGUREDEXTRACTOR().creator)();
+        assert_eq!(configured.extract_partial(&ctx, &input).unwrap().pid, None);
+
+        let mirrored = (__EXTRACTOR_REGISTRY_MIRROREDEXTRACTOR().creator)();
+        let partial = mirrored.extract_partial(&ctx, &input).unwrap();
+        assert_eq!(partial.pid.as_deref(), Some("mirror.test"));
+    }
+
+    #[cfg(all(feature = "luogu", feature = "vjudge"))]
+    #[test]
+    fn test_detect_page() {
+        let page = detect_page("https://www.luogu.com.cn/record/123", "<html></html>");
+        assert_eq!(page.oj.as_deref(), Some("luogu"));
+        assert_eq!(page.page_kind, PageKind::SubmissionDetail);
+        assert_eq!(page.record_id.as_deref(), Some("123"));
+
+        let page = detect_page("https://vjudge.net/solution/456", "<html></html>");
+        assert_eq!(page.oj.as_deref(), Some("vjudge"));
+        assert_eq!(page.record_id.as_deref(), Some("456"));
+
+        assert_eq!(detect_page("https://example.com/", ""), PageInfo::default());
+    }
+
+    #[test]
+    fn test_contain_panic() {
+        let result: Result<()> = contain_panic(|| panic!("pathological page"));
+        let Err(err) = result else {
+            panic!("panic should be contained");
+        };
+        assert_eq!(err.code(), "internal");
+        assert!(err.to_string().contains("pathological page"));
+    }
 }
@@ -10,22 +10,51 @@
 
 #![allow(dead_code)]
 
+#[cfg(feature = "decode")]
+pub mod decode;
+pub mod export;
 pub mod extractors;
 mod factory;
+pub mod matcher;
 pub mod models;
+pub mod overrides;
+pub mod policy;
 mod traits;
 pub mod utils;
 
-pub use factory::{create_extractor, extract};
-pub use traits::Extractor;
+pub use factory::{
+    ExtractorInfo, ExtractorRegistryItem, FactoryConfig, Observer, VerifyResult, classify,
+    clear_observer, configure, contain_panic, create_extractor, create_extractor_by_name,
+    detect_page, extract, extract_async, extract_input, extract_partial, extract_with,
+    extract_with_policy, extract_with_report, list_extractors, rank_all, register, set_observer,
+    verify_all,
+};
+pub use overrides::{clear_overrides, load_overrides};
+pub use policy::ValidationPolicy;
+pub use traits::{BoxFuture, Extractor, Fetcher, Fixture};
 
 pub(crate) mod constants {
     //! 常量
+
+    use std::time::Duration;
+
+    /// 无匹配提取器时, 错误中附带的候选数量
+    pub const NO_EXTRACTOR_CANDIDATES: usize = 3;
+
+    /// 页面未注明时区时采用的 UTC 偏移 (北京时间, 分钟)
+    pub const CST_OFFSET_MINUTES: i32 = 480;
+
+    /// 评测未完成时建议的重试间隔
+    pub const JUDGING_RETRY_AFTER: Duration = Duration::from_secs(3);
 }
 
 pub mod error {
     //! 错误类型
 
+    use serde::ser::{Serialize, SerializeStruct, Serializer};
+    use std::num::{ParseFloatError, ParseIntError};
+    use std::time::Duration;
+
     use crate::models::*;
 
     pub type Result<T> = std::result::Result<T, Error>;
@@ -33,10 +62,67 @@ pub mod error {
     /// 通用错误
     #[derive(Debug, thiserror::Error)]
     pub enum Error {
-        #[error("no extractor found for url: {0}")]
-        NoExtractor(String),
+        #[error("no extractor found for url: {url}{}", format_candidates(candidates))]
+        NoExtractor {
+            url: String,
+            /// 得分最高的若干候选
+            candidates: Vec<ExtractorCandidate>,
+        },
         #[error("extract error: {0}")]
-        Extract(ExtractError),
+        Extract(#[from] ExtractError),
+    }
+
+    /// 工厂匹配时考虑过的提取器
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    pub struct ExtractorCandidate {
+        pub name: &'static str,
+        pub score: u32,
+    }
+
+    fn format_candidates(candidates: &[ExtractorCandidate]) -> String {
+        if candidates.is_empty() {
+            return String::new();
+        }
+        let list: Vec<_> = candidates
+            .iter()
+            .map(|c| format!("{}={}", c.name, c.score))
+            .collect();
+        format!(" (candidates: {})", list.join(", "))
+    }
+
+    impl Error {
+        /// 稳定的字符串错误码
+        pub fn code(&self) -> &'static str {
+            match self {
+                Error::NoExtractor { .. } => "no_extractor",
+                Error::Extract(e) => e.kind.code(),
+            }
+        }
+
+        /// 稳定的数字错误码
+        pub fn numeric_code(&self) -> u16 {
+            match self {
+                Error::NoExtractor { .. } => 1,
+                Error::Extract(e) => e.kind.numeric_code(),
+            }
+        }
+    }
+
+    impl Serialize for Error {
+        fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+            match self {
+                Error::NoExtractor { url, candidates } => {
+                    let mut st = serializer.serialize_struct("Error", 5)?;
+                    st.serialize_field("code", self.code())?;
+                    st.serialize_field("numeric_code", &self.numeric_code())?;
+                    st.serialize_field("message", &self.to_string())?;
+                    st.serialize_field("detail", url)?;
+                    st.serialize_field("candidates", candidates)?;
+                    st.end()
+                }
+                Error::Extract(e) => e.serialize(serializer),
+            }
+        }
     }
 
     /// 提取错误
@@ -45,7 +131,40 @@ pub mod error {
     pub struct ExtractError {
         #[source]
         pub kind: ExtractErrorKind,
-        pub partial: Option<Box<Submission>>,
+        pub partial: Option<Box<PartialSubmission>>,
+        /// 全部缺失的必填字段
+        pub missing_fields: Vec<&'static str>,
+        /// 缺失字段的提取尝试记录
+        pub diagnostics: Vec<FieldDiagnostic>,
+    }
+
+    /// 字段提取策略表: (字段名, 依次尝试的选择器 / 正则)
+    pub type FieldStrategies = &'static [(&'static str, &'static [&'static str])];
+
+    /// 字段级诊断信息
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    pub struct FieldDiagnostic {
+        pub field: &'static str,
+        /// 依次尝试过的选择器 / 正则
+        pub tried: Vec<&'static str>,
+    }
+
+    impl Serialize for ExtractError {
+        fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+            let mut st = serializer.serialize_struct("ExtractError", 8)?;
+            st.serialize_field("code", self.kind.code())?;
+            st.serialize_field("numeric_code", &self.kind.numeric_code())?;
+            st.serialize_field("message", &self.kind.to_string())?;
+            st.serialize_field("detail", &self.kind.detail())?;
+            st.serialize_field(
+                "retry_after_ms",
+                &self.kind.retry_after().map(|d| d.as_millis() as u64),
+            )?;
+            st.serialize_field("missing_fields", &self.missing_fields)?;
+            st.serialize_field("diagnostics", &self.diagnostics)?;
+            st.serialize_field("partial", &self.partial)?;
+            st.end()
+        }
     }
 
     impl ExtractError {
@@ -53,13 +172,67 @@ pub mod error {
             Self {
                 kind,
                 partial: None,
+                missing_fields: Vec::new(),
+                diagnostics: Vec::new(),
             }
         }
 
-        pub fn with_partial(kind: ExtractErrorKind, partial: Submission) -> Self {
+        /// 缺失必填字段, 一次性列出全部字段
+        pub fn missing(fields: Vec<&'static str>) -> Self {
+            Self {
+                kind: ExtractErrorKind::MissingField(fields.join(", ")),
+                partial: None,
+                missing_fields: fields,
+                diagnostics: Vec::new(),
+            }
+        }
+
+        /// 依据策略表为缺失字段附加诊断信息
+        pub fn with_diagnostics(mut self, strategies: FieldStrategies) -> Self {
+            self.diagnostics = self
+                .missing_fields
+                .iter()
+                .filter_map(|field| {
+                    strategies
+                        .iter()
+                        .find(|(name, _)| name == field)
+                        .map(|(name, tried)| FieldDiagnostic {
+                            field: name,
+                            tried: tried.to_vec(),
+                        })
+                })
+                .collect();
+            self
+        }
+
+        /// 替换错误类型, 保留部分结果与诊断信息
+        pub fn with_kind(mut self, kind: ExtractErrorKind) -> Self {
+            self.kind = kind;
+            self
+        }
+
+        /// 缺失字段时, 以页面识别出的具体原因 (未登录 / 版式不符等) 替换错误类型
+        pub fn with_cause(self, cause: Option<ExtractErrorKind>) -> Self {
+            match cause {
+                Some(kind) if matches!(self.kind, ExtractErrorKind::MissingField(_)) => {
+                    self.with_kind(kind)
+                }
+                _ => self,
+            }
+        }
+
+        /// 附加部分提取结果
+        pub fn attach_partial(mut self, partial: PartialSubmission) -> Self {
+            self.partial = Some(Box::new(partial));
+            self
+        }
+
+        pub fn with_partial(kind: ExtractErrorKind, partial: PartialSubmission) -> Self {
             Self {
                 kind,
                 partial: Some(Box::new(partial)),
+                missing_fields: Vec::new(),
+                diagnostics: Vec::new(),
             }
         }
     }
@@ -75,6 +248,8 @@ pub mod error {
         Convert(String),
         #[error("missing field: {0}")]
         MissingField(String),
+        #[error("validation failed: {0}")]
+        Validation(String),
         #[error("regex mismatch: {0}")]
         RegexMismatch(String),
         #[error("selector parse error: {0}")]
@@ -95,7 +270,221 @@ pub mod error {
         NotInSubmissionPage(String),
         #[error("no submission selected: {0}")]
         NoSubmissionSelected(String),
+        #[error("judging in progress, retry after {}s", retry_after.as_secs())]
+        JudgingInProgress { retry_after: Duration },
+        #[error(
+            "layout mismatch: detected {}, expected {}",
+            detected.as_deref().unwrap_or("unknown"),
+            expected.join(" / ")
+        )]
+        LayoutMismatch {
+            /// 识别出的页面布局
+            detected: Option<String>,
+            /// 提取器支持的布局
+            expected: &'static [&'static str],
+        },
+        #[error("not authenticated: {0}")]
+        NotAuthenticated(String),
+        #[error("permission denied: {0}")]
+        PermissionDenied(String),
+        #[error("internal error: {0}")]
+        Internal(String),
+        #[error("unsupported input: {0}")]
+        UnsupportedInput(String),
         #[error("other: {0}")]
         Other(String),
     }
+
+    impl From<scraper::error::SelectorErrorKind<'_>> for ExtractErrorKind {
+        fn from(e: scraper::error::SelectorErrorKind<'_>) -> Self {
+            ExtractErrorKind::SelectorParse(e.to_string())
+        }
+    }
+
+    impl From<regex::Error> for ExtractErrorKind {
+        fn from(e: regex::Error) -> Self {
+            ExtractErrorKind::Parse(format!("regex: {e}"))
+        }
+    }
+
+    impl From<ParseIntError> for ExtractErrorKind {
+        fn from(e: ParseIntError) -> Self {
+            ExtractErrorKind::Convert(e.to_string())
+        }
+    }
+
+    impl From<ParseFloatError> for ExtractErrorKind {
+        fn from(e: ParseFloatError) -> Self {
+            ExtractErrorKind::Convert(e.to_string())
+        }
+    }
+
+    impl From<ExtractErrorKind> for ExtractError {
+        fn from(kind: ExtractErrorKind) -> Self {
+            ExtractError::new(kind)
+        }
+    }
+
+    impl From<ExtractErrorKind> for Error {
+        fn from(kind: ExtractErrorKind) -> Self {
+            Error::Extract(kind.into())
+        }
+    }
+
+    // 内部错误经 ExtractErrorKind 转换为 Error, 以便直接使用 `?`
+    macro_rules! impl_from_internal {
+        ($($ty:ty),* $(,)?) => {
+            $(
+                impl From<$ty> for Error {
+                    fn from(e: $ty) -> Self {
+                        ExtractErrorKind::from(e).into()
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_from_internal!(
+        scraper::error::SelectorErrorKind<'_>,
+        regex::Error,
+        ParseIntError,
+        ParseFloatError,
+    );
+
+    impl ExtractErrorKind {
+        /// 稳定的字符串错误码
+        pub fn code(&self) -> &'static str {
+            match self {
+                ExtractErrorKind::NoExtractor(_) => "no_extractor",
+                ExtractErrorKind::Parse(_) => "parse",
+                ExtractErrorKind::Convert(_) => "convert",
+                ExtractErrorKind::MissingField(_) => "missing_field",
+                ExtractErrorKind::Validation(_) => "validation",
+                ExtractErrorKind::RegexMismatch(_) => "regex_mismatch",
+                ExtractErrorKind::SelectorParse(_) => "selector_parse",
+                ExtractErrorKind::TimeParse(_) => "time_parse",
+                ExtractErrorKind::MemoryParse(_) => "memory_parse",
+                ExtractErrorKind::LanguageParse(_) => "language_parse",
+                ExtractErrorKind::StatusParse(_) => "status_parse",
+                ExtractErrorKind::InvalidUrl(_) => "invalid_url",
+                ExtractErrorKind::EmptyContent => "empty_content",
+                ExtractErrorKind::NotInSubmissionPage(_) => "not_in_submission_page",
+                ExtractErrorKind::NoSubmissionSelected(_) => "no_submission_selected",
+                ExtractErrorKind::JudgingInProgress { .. } => "judging_in_progress",
+                ExtractErrorKind::LayoutMismatch { .. } => "layout_mismatch",
+                ExtractErrorKind::NotAuthenticated(_) => "not_authenticated",
+                ExtractErrorKind::PermissionDenied(_) => "permission_denied",
+                ExtractErrorKind::Internal(_) => "internal",
+                ExtractErrorKind::UnsupportedInput(_) => "unsupported_input",
+                ExtractErrorKind::Other(_) => "other",
+            }
+        }
+
+        /// 稳定的数字错误码, 新增类型只追加, 不复用已有编号
+        pub fn numeric_code(&self) -> u16 {
+            match self {
+                ExtractErrorKind::NoExtractor(_) => 1,
+                ExtractErrorKind::Parse(_) => 2,
+                ExtractErrorKind::Convert(_) => 3,
+                ExtractErrorKind::MissingField(_) => 4,
+                ExtractErrorKind::RegexMismatch(_) => 5,
+                ExtractErrorKind::SelectorParse(_) => 6,
+                ExtractErrorKind::TimeParse(_) => 7,
+                ExtractErrorKind::MemoryParse(_) => 8,
+                ExtractErrorKind::LanguageParse(_) => 9,
+                ExtractErrorKind::StatusParse(_) => 10,
+                ExtractErrorKind::InvalidUrl(_) => 11,
+                ExtractErrorKind::EmptyContent => 12,
+                ExtractErrorKind::NotInSubmissionPage(_) => 13,
+                ExtractErrorKind::NoSubmissionSelected(_) => 14,
+                ExtractErrorKind::Validation(_) => 15,
+                ExtractErrorKind::JudgingInProgress { .. } => 16,
+                ExtractErrorKind::LayoutMismatch { .. } => 17,
+                ExtractErrorKind::NotAuthenticated(_) => 18,
+                ExtractErrorKind::PermissionDenied(_) => 19,
+                ExtractErrorKind::Internal(_) => 20,
+                ExtractErrorKind::UnsupportedInput(_) => 21,
+                ExtractErrorKind::Other(_) => 99,
+            }
+        }
+
+        /// 错误附带的原始信息
+        pub fn detail(&self) -> Option<&str> {
+            match self {
+                ExtractErrorKind::NoExtractor(s)
+                | ExtractErrorKind::Parse(s)
+                | ExtractErrorKind::Convert(s)
+                | ExtractErrorKind::MissingField(s)
+                | ExtractErrorKind::Validation(s)
+                | ExtractErrorKind::RegexMismatch(s)
+                | ExtractErrorKind::SelectorParse(s)
+                | ExtractErrorKind::TimeParse(s)
+                | ExtractErrorKind::MemoryParse(s)
+                | ExtractErrorKind::LanguageParse(s)
+                | ExtractErrorKind::StatusParse(s)
+                | ExtractErrorKind::InvalidUrl(s)
+                | ExtractErrorKind::NotInSubmissionPage(s)
+                | ExtractErrorKind::NoSubmissionSelected(s)
+                | ExtractErrorKind::NotAuthenticated(s)
+                | ExtractErrorKind::PermissionDenied(s)
+                | ExtractErrorKind::Internal(s)
+                | ExtractErrorKind::UnsupportedInput(s)
+                | ExtractErrorKind::Other(s) => Some(s),
+                ExtractErrorKind::LayoutMismatch { detected, .. } => detected.as_deref(),
+                ExtractErrorKind::EmptyContent | ExtractErrorKind::JudgingInProgress { .. } => None,
+            }
+        }
+
+        /// 可重试的错误返回建议的重试间隔
+        pub fn retry_after(&self) -> Option<Duration> {
+            match self {
+                ExtractErrorKind::JudgingInProgress { retry_after } => Some(*retry_after),
+                _ => None,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_serialize() {
+            let err = Error::Extract(ExtractError::new(ExtractErrorKind::MissingField(
+                "pid".to_string(),
+            )));
+            assert_eq!(err.code(), "missing_field");
+            assert_eq!(
+                serde_json::to_value(&err).unwrap(),
+                serde_json::json!({
+                    "code": "missing_field",
+                    "numeric_code": 4,
+                    "message": "missing field: pid",
+                    "detail": "pid",
+                    "retry_after_ms": null,
+                    "missing_fields": [],
+                    "diagnostics": [],
+                    "partial": null,
+                })
+            );
+
+            let err: Error = "x".parse::<u16>().unwrap_err().into();
+            assert_eq!(err.code(), "convert");
+
+            let err = Error::NoExtractor {
+                url: "https://example.com".to_string(),
+                candidates: vec![ExtractorCandidate {
+                    name: "luogu",
+                    score: 0,
+                }],
+            };
+            assert_eq!(
+                err.to_string(),
+                "no extractor found for url: https://example.com (candidates: luogu=0)"
+            );
+            let value = serde_json::to_value(&err).unwrap();
+            assert_eq!(value["numeric_code"], 1);
+            assert_eq!(value["candidates"][0]["name"], "luogu");
+        }
+    }
 }
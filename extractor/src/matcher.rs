@@ -0,0 +1,236 @@
+//! URL 匹配
+//!
+//! 提取器按完整 URL 正则、精确域名、域名通配符与路径正则打分, 子串匹配仅作为弱回退.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use regex::Regex;
+
+/// 完整 URL 正则匹配得分
+pub const PATTERN_SCORE: u32 = 200;
+/// 精确域名匹配得分
+pub const DOMAIN_SCORE: u32 = 120;
+/// 域名通配符匹配得分
+pub const HOST_SCORE: u32 = 100;
+/// 域名匹配且路径匹配时的额外得分
+pub const PATH_SCORE: u32 = 50;
+/// 名称子串匹配得分
+pub const NAME_SCORE: u32 = 20;
+/// 标签子串匹配得分
+pub const TAG_SCORE: u32 = 10;
+
+/// 提取器的 URL 匹配规则
+#[derive(Debug, Clone)]
+pub struct UrlMatcher {
+    name: String,
+    tags: Vec<String>,
+    hosts: Vec<String>,
+    domains: Vec<String>,
+    paths: Vec<Regex>,
+    patterns: Vec<Regex>,
+}
+
+impl UrlMatcher {
+    /// 构建匹配规则
+    ///
+    /// `hosts` 为域名通配符 (如 `*.luogu.com.cn`), `paths` 为匹配路径 (含查询串) 的正则.
+    ///
+    /// # Panics
+    ///
+    /// 路径正则无效时 panic.
+    pub fn new(name: &str, tags: &[&str], hosts: &[&str], paths: &[&str]) -> Self {
+        Self {
+            name: name.to_lowercase(),
+            tags: tags.iter().map(|t| t.to_lowercase()).collect(),
+            hosts: hosts.iter().map(|h| normalize_host(h)).collect(),
+            domains: Vec::new(),
+            paths: paths
+                .iter()
+                .map(|p| Regex::new(p).expect("invalid path pattern"))
+                .collect(),
+            patterns: Vec::new(),
+        }
+    }
+
+    /// 追加完整 URL 正则, 建议以 `^https?://` 锚定
+    ///
+    /// # Panics
+    ///
+    /// 正则无效时 panic.
+    pub fn with_patterns(mut self, patterns: &[&str]) -> Self {
+        self.patterns.extend(
+            patterns
+                .iter()
+                .map(|p| Regex::new(p).expect("invalid url pattern")),
+        );
+        self
+    }
+
+    /// 追加精确域名, 比较时忽略大小写与 `www.` 前缀
+    pub fn with_domains(mut self, domains: &[&str]) -> Self {
+        self.domains
+            .extend(domains.iter().map(|d| normalize_host(d)));
+        self
+    }
+
+    /// 计算 url 的匹配得分
+    ///
+    /// 域名命中时不再参考子串, 避免其他站点链接中的关键字造成误判.
+    pub fn rank(&self, url: &str) -> u32 {
+        if self.patterns.iter().any(|re| re.is_match(url)) {
+            return PATTERN_SCORE;
+        }
+        if let Ok(parsed) = url::Url::parse(url)
+            && let Some(host) = parsed.host_str()
+        {
+            let host = normalize_host(host);
+            let host_score = if self.domains.contains(&host) {
+                Some(DOMAIN_SCORE)
+            } else if self.hosts.iter().any(|pattern| glob_match(pattern, &host)) {
+                Some(HOST_SCORE)
+            } else {
+                None
+            };
+            if let Some(host_score) = host_score {
+                let path = match parsed.query() {
+                    Some(query) => format!("{}?{query}", parsed.path()),
+                    None => parsed.path().to_string(),
+                };
+                return match self.paths.iter().any(|re| re.is_match(&path)) {
+                    true => host_score + PATH_SCORE,
+                    false => host_score,
+                };
+            }
+        }
+
+        self.substring_rank(url)
+    }
+
+    /// 子串回退匹配
+    fn substring_rank(&self, url: &str) -> u32 {
+        let url = url.to_lowercase();
+        let tag_score: u32 = self
+            .tags
+            .iter()
+            .filter(|tag| url.contains(tag.as_str()))
+            .map(|_| TAG_SCORE)
+            .sum();
+        let name_score = match url.contains(&self.name) {
+            true => NAME_SCORE,
+            false => 0,
+        };
+        tag_score + name_score
+    }
+}
+
+/// url 的域名是否命中任一通配符
+pub fn host_matches(hosts: &[&str], url: &str) -> bool {
+    let Some(host) = url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(normalize_host))
+    else {
+        return false;
+    };
+    hosts
+        .iter()
+        .any(|pattern| glob_match(&normalize_host(pattern), &host))
+}
+
+fn normalize_host(host: &str) -> String {
+    let host = host.trim().to_lowercase();
+    match host.strip_prefix("www.") {
+        Some(stripped) => stripped.to_string(),
+        None => host,
+    }
+}
+
+/// 简单通配符匹配, `*` 匹配任意长度字符
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let Some((head, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut text) = text.strip_prefix(head) else {
+        return false;
+    };
+
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let tail = parts.pop().unwrap_or_default();
+    for part in parts {
+        match text.find(part) {
+            Some(pos) => text = &text[pos + part.len()..],
+            None => return false,
+        }
+    }
+    text.len() >= tail.len() && text.ends_with(tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("luogu.com.cn", "luogu.com.cn"));
+        assert!(glob_match("*.luogu.com.cn", "class.luogu.com.cn"));
+        assert!(!glob_match("*.luogu.com.cn", "luogu.com.cn"));
+        assert!(glob_match("luogu.*", "luogu.proxy.edu"));
+        assert!(glob_match("*oj*", "myoj.edu"));
+        assert!(!glob_match("vjudge.net", "vjudge.net.cn"));
+
+        assert!(host_matches(
+            &["luogu.com.cn"],
+            "https://www.luogu.com.cn/record/1"
+        ));
+        assert!(!host_matches(&["luogu.com.cn"], "not a url"));
+    }
+
+    #[test]
+    fn test_rank() {
+        let luogu = UrlMatcher::new("luogu", &["洛谷"], &["luogu.com.cn"], &[r"^/record/\d+"]);
+        let vjudge = UrlMatcher::new("vj", &["vjudge"], &["vjudge.net"], &[r"^/solution/\d+"]);
+
+        let record = "https://www.luogu.com.cn/record/1";
+        assert_eq!(luogu.rank(record), HOST_SCORE + PATH_SCORE);
+
+        // 洛谷讨论中提到 vjudge 时不应被 vjudge 提取器抢走
+        let discuss = "https://www.luogu.com.cn/discuss/1?title=vjudge";
+        assert_eq!(luogu.rank(discuss), HOST_SCORE);
+        assert_eq!(vjudge.rank(discuss), TAG_SCORE + NAME_SCORE);
+
+        assert_eq!(luogu.rank("https://luogu.proxy.edu/record/1"), NAME_SCORE);
+        assert_eq!(vjudge.rank("not a url"), 0);
+    }
+
+    #[test]
+    fn test_domains() {
+        let vjudge = UrlMatcher::new("vj", &["vjudge"], &["*.vjudge.net"], &[])
+            .with_domains(&["vjudge.net", "www.luogu.com.cn"]);
+
+        assert_eq!(vjudge.rank("https://vjudge.net/solution/1"), DOMAIN_SCORE);
+        assert_eq!(vjudge.rank("https://luogu.com.cn/record/1"), DOMAIN_SCORE);
+        assert_eq!(vjudge.rank("https://cn.vjudge.net/solution/1"), HOST_SCORE);
+        // 精确域名不匹配子域名
+        assert_eq!(
+            vjudge.rank("https://vjudge.net.cn/solution/1"),
+            TAG_SCORE + NAME_SCORE
+        );
+    }
+
+    #[test]
+    fn test_patterns() {
+        let luogu = UrlMatcher::new("luogu", &[], &["luogu.com.cn"], &[])
+            .with_patterns(&[r"^https?://(www\.)?luogu\.com\.cn/record/\d+"]);
+
+        assert_eq!(luogu.rank("https://luogu.com.cn/record/1"), PATTERN_SCORE);
+        assert_eq!(luogu.rank("https://luogu.com.cn/discuss/1"), HOST_SCORE);
+        // 锚定后, 出现在查询串中的记录链接不会误判
+        let redirect = "https://example.com/?to=https://luogu.com.cn/record/1";
+        assert_eq!(luogu.rank(redirect), NAME_SCORE);
+    }
+}
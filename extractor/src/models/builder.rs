@@ -0,0 +1,148 @@
+//! 提交记录构建器
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::error::ExtractError;
+use crate::policy::ValidationPolicy;
+
+use super::partial::PartialSubmission;
+use super::submission::*;
+
+/// 提交记录构建器
+///
+/// 依据 [`ValidationPolicy`] 统一完成校验, 校验失败时携带已提取的部分结果.
+#[derive(Default, Debug, Clone)]
+pub struct SubmissionBuilder {
+    partial: PartialSubmission,
+}
+
+impl SubmissionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 从部分提取结果开始构建
+    pub fn from_partial(partial: PartialSubmission) -> Self {
+        Self { partial }
+    }
+
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.partial.code = Some(code.into());
+        self
+    }
+
+    pub fn pid(mut self, pid: impl Into<String>) -> Self {
+        self.partial.pid = Some(pid.into());
+        self
+    }
+
+    pub fn rid(mut self, rid: impl Into<String>) -> Self {
+        self.partial.rid = Some(rid.into());
+        self
+    }
+
+    pub fn oj(mut self, oj: impl Into<String>) -> Self {
+        self.partial.oj = Some(oj.into());
+        self
+    }
+
+    pub fn language(mut self, language: SubmissionLanguage) -> Self {
+        self.partial.language = Some(language);
+        self
+    }
+
+    pub fn status(mut self, status: SubmissionStatus) -> Self {
+        self.partial.status = Some(status);
+        self
+    }
+
+    pub fn total_time(mut self, total_time: u64) -> Self {
+        self.partial.total_time = Some(total_time);
+        self
+    }
+
+    pub fn max_memory(mut self, max_memory: u64) -> Self {
+        self.partial.max_memory = Some(max_memory);
+        self
+    }
+
+    pub fn score(mut self, score: u16) -> Self {
+        self.partial.score = Some(score);
+        self
+    }
+
+    pub fn remote(mut self, remote: RemoteInfo) -> Self {
+        self.partial.remote = Some(remote);
+        self
+    }
+
+    pub fn submitted_at(mut self, submitted_at: u64) -> Self {
+        self.partial.submitted_at = Some(submitted_at);
+        self
+    }
+
+    /// 不经校验直接生成提交记录, 缺失字段取默认值, 规范化题号与提交 ID, 并计算代码摘要
+    pub fn build_unchecked(self) -> Submission {
+        let p = self.partial;
+        let mut sub = Submission {
+            code: p.code.unwrap_or_default(),
+            pid: p.pid.unwrap_or_default(),
+            rid: p.rid.unwrap_or_default(),
+            oj: p.oj.unwrap_or_default(),
+            language: p.language.unwrap_or_default(),
+            status: p.status.unwrap_or_default(),
+            total_time: p.total_time.unwrap_or_default(),
+            max_memory: p.max_memory.unwrap_or_default(),
+            score: p.score,
+            remote: p.remote,
+            submitted_at: p.submitted_at,
+            ..Default::default()
+        };
+        sub.normalize_ids();
+        sub.refresh_code_digest();
+        sub
+    }
+
+    /// 按默认策略校验并生成提交记录
+    pub fn build(self) -> Result<Submission, ExtractError> {
+        self.build_with(&ValidationPolicy::default())
+    }
+
+    /// 按给定策略校验并生成提交记录
+    pub fn build_with(self, policy: &ValidationPolicy) -> Result<Submission, ExtractError> {
+        match policy.validate(&self.partial) {
+            Ok(()) => Ok(self.build_unchecked()),
+            Err(e) => Err(e.attach_partial(self.partial)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ExtractErrorKind;
+
+    #[test]
+    fn test_build() {
+        let sub = SubmissionBuilder::new()
+            .code("int main() {}")
+            .pid("P1001")
+            .rid("1")
+            .oj("luogu")
+            .build()
+            .unwrap();
+        assert_eq!(sub.pid, "P1001");
+        assert_eq!(sub.language, SubmissionLanguage::Cpp17);
+
+        let err = SubmissionBuilder::new().pid("P1001").build().unwrap_err();
+        assert_eq!(err.missing_fields, ["rid", "code"]);
+        assert!(matches!(err.kind, ExtractErrorKind::MissingField(ref f) if f == "rid, code"));
+        assert_eq!(err.partial.unwrap().pid.as_deref(), Some("P1001"));
+    }
+}
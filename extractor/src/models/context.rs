@@ -0,0 +1,104 @@
+//! 提取上下文
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+
+use super::{Document, InputKind};
+use crate::utils::now_ms;
+
+/// 提取上下文, 携带页面之外的已知信息
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtractionContext<'a> {
+    /// 提交记录页面 URL
+    pub url: &'a str,
+    /// 目标站点的 cookie
+    pub cookies: HashMap<String, String>,
+    /// 页面语言, 如 `zh-CN`
+    pub locale: Option<String>,
+    /// 当前时间 (Unix 毫秒), 用于解析相对时间
+    pub now: Option<u64>,
+    /// 页面显示时间所用时区的 UTC 偏移 (分钟), 如浏览器本地时区
+    pub utc_offset_minutes: Option<i32>,
+    /// 调用方额外提供的文档
+    pub extra_docs: Vec<Document<'a>>,
+}
+
+impl<'a> ExtractionContext<'a> {
+    /// 以 url 与当前时间创建上下文
+    pub fn new(url: &'a str) -> Self {
+        Self {
+            url,
+            now: now_ms(),
+            ..Default::default()
+        }
+    }
+
+    /// 设置 cookie
+    pub fn with_cookies(mut self, cookies: HashMap<String, String>) -> Self {
+        self.cookies = cookies;
+        self
+    }
+
+    /// 设置页面语言
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// 设置当前时间
+    pub fn with_now(mut self, now: u64) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// 设置页面时间的 UTC 偏移 (分钟)
+    pub fn with_utc_offset(mut self, minutes: i32) -> Self {
+        self.utc_offset_minutes = Some(minutes);
+        self
+    }
+
+    /// 追加额外文档
+    pub fn with_doc(mut self, doc: Document<'a>) -> Self {
+        self.extra_docs.push(doc);
+        self
+    }
+
+    /// 首个指定类型的额外文档内容
+    pub fn extra_doc(&self, kind: InputKind) -> Option<&'a str> {
+        self.extra_docs
+            .iter()
+            .find(|doc| doc.kind == kind)
+            .map(|doc| doc.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context() {
+        let ctx = ExtractionContext::new("https://vjudge.net/solution/1")
+            .with_now(1_735_790_400_000)
+            .with_locale("zh-CN")
+            .with_utc_offset(-300)
+            .with_doc(Document {
+                url: "https://vjudge.net/solution/data/1",
+                kind: InputKind::Json,
+                content: r#"{"code": ""}"#,
+            });
+
+        assert_eq!(ctx.now, Some(1_735_790_400_000));
+        assert_eq!(ctx.locale.as_deref(), Some("zh-CN"));
+        assert_eq!(ctx.utc_offset_minutes, Some(-300));
+        assert_eq!(ctx.extra_doc(InputKind::Json), Some(r#"{"code": ""}"#));
+        assert_eq!(ctx.extra_doc(InputKind::Html), None);
+    }
+}
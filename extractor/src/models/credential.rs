@@ -0,0 +1,280 @@
+//! 站点登录凭据
+//!
+//! 各站点需要的 cookie 由 [`COOKIE_SCHEMAS`] 声明, 解析时按来源域名选择.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::matcher::host_matches;
+
+use super::deployment::resolve_chost;
+use super::payload::DEFAULT_7FA4_HOST;
+
+/// 单个站点的 cookie 声明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CookieSchema {
+    /// 站点标识, 与 [`Credential`] 的 `oj` 标签一致
+    pub oj: &'static str,
+    /// 域名通配符
+    pub hosts: &'static [&'static str],
+    /// 需要保存的 cookie 名称
+    pub keys: &'static [&'static str],
+}
+
+/// 已知站点的 cookie 声明
+pub const COOKIE_SCHEMAS: &[CookieSchema] = &[
+    CookieSchema {
+        oj: "7fa4",
+        hosts: &["7fa4.cn", "*.7fa4.cn"],
+        keys: &["login", "connect.sid"],
+    },
+    CookieSchema {
+        oj: "luogu",
+        hosts: &["luogu.com.cn", "*.luogu.com.cn"],
+        keys: &["__client_id", "_uid"],
+    },
+    CookieSchema {
+        oj: "vjudge",
+        hosts: &["vjudge.net", "*.vjudge.net"],
+        keys: &["JSESSIONID"],
+    },
+    CookieSchema {
+        oj: "xyd",
+        hosts: &["xinyoudui.com", "*.xinyoudui.com"],
+        keys: &["token"],
+    },
+];
+
+impl CookieSchema {
+    /// 来源对应的声明
+    pub fn for_origin(origin: &str) -> Option<&'static Self> {
+        COOKIE_SCHEMAS
+            .iter()
+            .find(|schema| host_matches(schema.hosts, origin))
+    }
+}
+
+/// 按站点区分的登录凭据
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+#[serde(tag = "oj")]
+pub enum Credential {
+    #[serde(rename = "7fa4")]
+    SevenFa4 {
+        login: Option<String>,
+        #[serde(rename = "connect.sid")]
+        connect_sid: Option<String>,
+        /// 提交接口所在主机, 可能带端口
+        chost: Option<String>,
+    },
+    #[serde(rename = "luogu")]
+    Luogu {
+        #[serde(rename = "__client_id")]
+        client_id: Option<String>,
+        #[serde(rename = "_uid")]
+        uid: Option<String>,
+    },
+    #[serde(rename = "vjudge")]
+    Vjudge {
+        #[serde(rename = "JSESSIONID")]
+        jsessionid: Option<String>,
+    },
+    #[serde(rename = "xyd")]
+    Xyd { token: Option<String> },
+}
+
+impl Credential {
+    /// 解析 `document.cookie` 字符串, 来源不属于已知站点时返回 `None`
+    pub fn parse(cookie_str: &str, origin: &str) -> Option<Self> {
+        let schema = CookieSchema::for_origin(origin)?;
+        let mut cookies = parse_cookie_str(cookie_str);
+        let mut take = |key: &str| cookies.remove(key);
+
+        Some(match schema.oj {
+            "7fa4" => Self::SevenFa4 {
+                login: take("login"),
+                connect_sid: take("connect.sid"),
+                chost: resolve_chost(origin),
+            },
+            "luogu" => Self::Luogu {
+                client_id: take("__client_id"),
+                uid: take("_uid"),
+            },
+            "vjudge" => Self::Vjudge {
+                jsessionid: take("JSESSIONID"),
+            },
+            "xyd" => Self::Xyd {
+                token: take("token"),
+            },
+            oj => unreachable!("cookie schema `{oj}` has no credential variant"),
+        })
+    }
+
+    /// 站点标识
+    pub fn oj(&self) -> &'static str {
+        match self {
+            Self::SevenFa4 { .. } => "7fa4",
+            Self::Luogu { .. } => "luogu",
+            Self::Vjudge { .. } => "vjudge",
+            Self::Xyd { .. } => "xyd",
+        }
+    }
+
+    /// 对应的 cookie 声明
+    pub fn schema(&self) -> &'static CookieSchema {
+        COOKIE_SCHEMAS
+            .iter()
+            .find(|schema| schema.oj == self.oj())
+            .expect("every credential has a cookie schema")
+    }
+
+    /// 按声明顺序排列的 cookie 键值
+    pub fn cookies(&self) -> Vec<(&'static str, &str)> {
+        let values = match self {
+            Self::SevenFa4 {
+                login, connect_sid, ..
+            } => vec![login, connect_sid],
+            Self::Luogu { client_id, uid } => vec![client_id, uid],
+            Self::Vjudge { jsessionid } => vec![jsessionid],
+            Self::Xyd { token } => vec![token],
+        };
+        self.schema()
+            .keys
+            .iter()
+            .zip(values)
+            .filter_map(|(key, value)| Some((*key, value.as_deref()?)))
+            .collect()
+    }
+
+    /// 是否包含全部需要的 cookie
+    pub fn is_complete(&self) -> bool {
+        self.cookies().len() == self.schema().keys.len()
+    }
+
+    /// 生成 `Cookie` 请求头
+    pub fn cookie_header(&self) -> String {
+        self.cookies()
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// 7fa4 登录信息, 由扩展在 7fa4 页面获取
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct CookieInfo {
+    pub login: Option<String>,
+    #[serde(rename = "connect.sid")]
+    pub connect_sid: Option<String>,
+    /// 提交接口所在主机, 可能带端口
+    pub chost: Option<String>,
+}
+
+impl CookieInfo {
+    /// 解析 7fa4 页面的 `document.cookie` 字符串
+    pub fn parse(cookie_str: &str, origin: &str) -> Self {
+        let mut cookies = parse_cookie_str(cookie_str);
+        Self {
+            login: cookies.remove("login"),
+            connect_sid: cookies.remove("connect.sid"),
+            chost: resolve_chost(origin),
+        }
+    }
+
+    /// 提交接口主机, 未指定时为 [`DEFAULT_7FA4_HOST`]
+    pub fn chost(&self) -> &str {
+        match self.chost.as_deref().map(str::trim) {
+            Some(chost) if !chost.is_empty() => chost,
+            _ => DEFAULT_7FA4_HOST,
+        }
+    }
+
+    /// 是否带有任一登录 cookie
+    pub fn has_session(&self) -> bool {
+        self.login.is_some() || self.connect_sid.is_some()
+    }
+
+    /// 生成 7fa4 请求的 `Cookie` 头
+    pub fn cookie_header(&self) -> String {
+        [("login", &self.login), ("connect.sid", &self.connect_sid)]
+            .into_iter()
+            .filter_map(|(key, value)| Some(format!("{key}={}", value.as_deref()?)))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// 拆分 `document.cookie` 字符串, 重复的键取最后一个
+pub fn parse_cookie_str(cookie_str: &str) -> HashMap<String, String> {
+    cookie_str
+        .split(';')
+        .filter_map(|part| part.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_credential() {
+        let luogu = Credential::parse(
+            "__client_id=abc; _uid=42; C3VK=x",
+            "https://www.luogu.com.cn",
+        )
+        .unwrap();
+        assert_eq!(
+            luogu,
+            Credential::Luogu {
+                client_id: Some("abc".to_string()),
+                uid: Some("42".to_string()),
+            }
+        );
+        assert!(luogu.is_complete());
+        assert_eq!(luogu.cookie_header(), "__client_id=abc; _uid=42");
+
+        let vjudge = Credential::parse("other=1", "https://vjudge.net").unwrap();
+        assert_eq!(vjudge, Credential::Vjudge { jsessionid: None });
+        assert!(!vjudge.is_complete());
+
+        let ojs = Credential::parse("login=u; connect.sid=s", "http://jx.7fa4.cn:8888").unwrap();
+        assert_eq!(ojs.oj(), "7fa4");
+        assert!(matches!(
+            &ojs,
+            Credential::SevenFa4 { chost: Some(chost), .. } if chost == "jx.7fa4.cn:8888"
+        ));
+
+        assert_eq!(Credential::parse("token=t", "https://example.com"), None);
+    }
+
+    #[test]
+    fn test_cookie_info() {
+        let info = CookieInfo::parse("login=u; other=1", "http://jx.7fa4.cn:8888/");
+        assert_eq!(info.chost(), "jx.7fa4.cn:8888");
+        assert_eq!(info.cookie_header(), "login=u");
+        assert!(info.has_session());
+        assert_eq!(CookieInfo::default().chost(), DEFAULT_7FA4_HOST);
+    }
+
+    #[test]
+    fn test_credential_serde() {
+        let xyd = Credential::parse("token=t", "https://www.xinyoudui.com").unwrap();
+        let json = serde_json::to_value(&xyd).unwrap();
+        assert_eq!(json, serde_json::json!({ "oj": "xyd", "token": "t" }));
+        assert_eq!(serde_json::from_value::<Credential>(json).unwrap(), xyd);
+    }
+}
@@ -0,0 +1,209 @@
+//! 7fa4 部署
+//!
+//! 7fa4 有多个部署, 页面所在主机与提交接口地址不一定相同 (内网镜像使用 8888 端口).
+//! 内置部署可由 JSON 覆盖, 镜像与测试环境无需重新编译.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::error::*;
+
+/// 单个部署
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct Deployment {
+    /// 页面所在主机 (大小写不敏感)
+    pub host: String,
+    /// 提交接口端口, 缺省时使用默认端口
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// 接口根地址, 缺省为 `http://{chost}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_base: Option<String>,
+}
+
+impl Deployment {
+    fn new(host: &str, port: Option<u16>) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+            api_base: None,
+        }
+    }
+
+    /// 提交接口主机, 形如 `host[:port]`
+    pub fn chost(&self) -> String {
+        match self.port {
+            Some(port) => format!("{}:{port}", self.host),
+            None => self.host.clone(),
+        }
+    }
+
+    /// 接口根地址, 不含末尾的 `/`
+    pub fn api_base(&self) -> String {
+        match &self.api_base {
+            Some(base) => base.trim_end_matches('/').to_string(),
+            None => format!("http://{}", self.chost()),
+        }
+    }
+}
+
+/// 部署表
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployments {
+    deployments: Vec<Deployment>,
+}
+
+impl Default for Deployments {
+    fn default() -> Self {
+        Self {
+            deployments: vec![
+                Deployment::new("oj.7fa4.cn", None),
+                Deployment::new("jx.7fa4.cn", Some(8888)),
+                Deployment::new("in.7fa4.cn", Some(8888)),
+            ],
+        }
+    }
+}
+
+impl Deployments {
+    /// 由 JSON 部署数组构建
+    pub fn from_json(json: &str) -> Result<Self> {
+        let deployments: Vec<Deployment> = serde_json::from_str(json)
+            .map_err(|e| Error::from(ExtractErrorKind::Parse(format!("deployments: {e}"))))?;
+        if let Some(d) = deployments.iter().find(|d| d.host.trim().is_empty()) {
+            return Err(
+                ExtractErrorKind::Validation(format!("deployment without host: {d:?}")).into(),
+            );
+        }
+        Ok(Self { deployments })
+    }
+
+    /// 以另一张表覆盖, 新部署优先匹配
+    pub fn override_with(&mut self, other: Deployments) {
+        let mut deployments = other.deployments;
+        deployments.append(&mut self.deployments);
+        self.deployments = deployments;
+    }
+
+    /// 当前全部部署
+    pub fn iter(&self) -> impl Iterator<Item = &Deployment> {
+        self.deployments.iter()
+    }
+
+    /// 来源 (URL 或主机) 所属的部署
+    pub fn for_origin(&self, origin: &str) -> Option<&Deployment> {
+        let host = url::Url::parse(origin)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_else(|| {
+                origin
+                    .split([':', '/'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            });
+        self.deployments
+            .iter()
+            .find(|d| d.host.trim().eq_ignore_ascii_case(&host))
+    }
+
+    /// 提交接口主机对应的部署
+    pub fn for_chost(&self, chost: &str) -> Option<&Deployment> {
+        self.deployments
+            .iter()
+            .find(|d| d.chost().eq_ignore_ascii_case(chost.trim()))
+    }
+}
+
+static DEPLOYMENTS: Lazy<RwLock<Deployments>> = Lazy::new(|| RwLock::new(Deployments::default()));
+
+/// 以 JSON 部署数组覆盖全局部署表, 新部署优先于内置部署
+pub fn load_deployments(json: &str) -> Result<()> {
+    let overrides = Deployments::from_json(json)?;
+    DEPLOYMENTS.write().unwrap().override_with(overrides);
+    Ok(())
+}
+
+/// 重置全局部署表为内置部署
+pub fn reset_deployments() {
+    *DEPLOYMENTS.write().unwrap() = Deployments::default();
+}
+
+/// 当前全局部署表的副本
+pub fn current_deployments() -> Deployments {
+    DEPLOYMENTS.read().unwrap().clone()
+}
+
+/// 来源所属部署的提交接口主机, 未知部署时取来源的 `host[:port]`
+pub fn resolve_chost(origin: &str) -> Option<String> {
+    if let Some(deployment) = DEPLOYMENTS.read().unwrap().for_origin(origin) {
+        return Some(deployment.chost());
+    }
+    url::Url::parse(origin).ok().and_then(|u| {
+        u.host_str().map(|host| match u.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        })
+    })
+}
+
+/// `chost` 的接口根地址, 未知部署时为 `http://{chost}`
+pub fn api_base(chost: &str) -> String {
+    match DEPLOYMENTS.read().unwrap().for_chost(chost) {
+        Some(deployment) => deployment.api_base(),
+        None => format!("http://{chost}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin() {
+        let deployments = Deployments::default();
+        let jx = deployments
+            .for_origin("http://jx.7fa4.cn:8888/problem/1")
+            .unwrap();
+        assert_eq!(jx.chost(), "jx.7fa4.cn:8888");
+        assert_eq!(jx.api_base(), "http://jx.7fa4.cn:8888");
+        assert_eq!(
+            deployments
+                .for_origin("oj.7fa4.cn")
+                .map(Deployment::chost)
+                .as_deref(),
+            Some("oj.7fa4.cn")
+        );
+        assert!(deployments.for_origin("https://example.com").is_none());
+    }
+
+    #[test]
+    fn test_override() {
+        let mut deployments = Deployments::default();
+        let json = r#"[
+            {"host": "test.7fa4.cn", "port": 3000, "api_base": "https://api.test.7fa4.cn/"},
+            {"host": "jx.7fa4.cn", "port": 9080}
+        ]"#;
+        deployments.override_with(Deployments::from_json(json).unwrap());
+
+        let test = deployments.for_origin("https://test.7fa4.cn").unwrap();
+        assert_eq!(test.chost(), "test.7fa4.cn:3000");
+        assert_eq!(test.api_base(), "https://api.test.7fa4.cn");
+        assert_eq!(
+            deployments.for_origin("http://jx.7fa4.cn").unwrap().chost(),
+            "jx.7fa4.cn:9080"
+        );
+        assert!(deployments.for_chost("test.7fa4.cn:3000").is_some());
+        assert!(Deployments::from_json(r#"[{"host": " "}]"#).is_err());
+    }
+}
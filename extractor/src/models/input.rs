@@ -0,0 +1,114 @@
+//! 提取器输入
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::error::*;
+
+/// 输入文档类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputKind {
+    Html,
+    Json,
+}
+
+/// 单个输入文档
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Document<'a> {
+    /// 文档来源
+    pub url: &'a str,
+    pub kind: InputKind,
+    pub content: &'a str,
+}
+
+/// 提取器输入
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractorInput<'a> {
+    /// 提交记录页面 HTML
+    HtmlPage(&'a str),
+    /// 接口返回的 JSON
+    JsonApi(&'a str),
+    /// 同一提交的多个文档, 首个文档为主文档
+    MultiDocument(Vec<Document<'a>>),
+}
+
+impl<'a> ExtractorInput<'a> {
+    /// 全部文档, 单文档输入的来源为空
+    pub fn documents(&self) -> Vec<Document<'a>> {
+        match self {
+            ExtractorInput::HtmlPage(content) => vec![Document {
+                url: "",
+                kind: InputKind::Html,
+                content,
+            }],
+            ExtractorInput::JsonApi(content) => vec![Document {
+                url: "",
+                kind: InputKind::Json,
+                content,
+            }],
+            ExtractorInput::MultiDocument(docs) => docs.clone(),
+        }
+    }
+
+    /// 首个指定类型的文档内容
+    pub fn first(&self, kind: InputKind) -> Option<&'a str> {
+        self.documents()
+            .into_iter()
+            .find(|doc| doc.kind == kind)
+            .map(|doc| doc.content)
+    }
+
+    /// 首个 HTML 文档, 不存在时报错
+    pub fn html(&self) -> Result<&'a str> {
+        self.first(InputKind::Html).ok_or_else(|| {
+            ExtractErrorKind::UnsupportedInput("html document required".to_string()).into()
+        })
+    }
+
+    /// 全部文档均为空白
+    pub fn is_empty(&self) -> bool {
+        self.documents()
+            .iter()
+            .all(|doc| doc.content.trim().is_empty())
+    }
+}
+
+impl<'a> From<&'a str> for ExtractorInput<'a> {
+    fn from(content: &'a str) -> Self {
+        ExtractorInput::HtmlPage(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documents() {
+        let input = ExtractorInput::MultiDocument(vec![
+            Document {
+                url: "https://www.luogu.com.cn/record/1?_contentOnly=1",
+                kind: InputKind::Json,
+                content: "{}",
+            },
+            Document {
+                url: "https://www.luogu.com.cn/record/1",
+                kind: InputKind::Html,
+                content: "<html></html>",
+            },
+        ]);
+        assert_eq!(input.first(InputKind::Json), Some("{}"));
+        assert_eq!(input.html().unwrap(), "<html></html>");
+        assert!(!input.is_empty());
+
+        let err = ExtractorInput::JsonApi("{}").html().unwrap_err();
+        assert_eq!(err.code(), "unsupported_input");
+        assert!(ExtractorInput::from("  ").is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+//! 语言代码映射
+//!
+//! 不同目标平台对同一语言使用不同的标识, 这里集中维护映射表.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use super::submission::SubmissionLanguage;
+
+/// 语言代码的目标平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LanguageTarget {
+    /// 7fa4 (SYZOJ)
+    SevenFa4,
+    /// 洛谷远程评测
+    LuoguRemote,
+    /// Hydro
+    Hydro,
+    /// Codeforces (`programTypeId`)
+    Codeforces,
+}
+
+/// 语言映射表
+#[derive(Debug, Clone, Copy)]
+pub struct LanguageMap {
+    target: LanguageTarget,
+    table: fn(&SubmissionLanguage) -> &'static str,
+}
+
+impl LanguageMap {
+    /// 目标平台
+    pub fn target(&self) -> LanguageTarget {
+        self.target
+    }
+
+    /// 查询语言代码
+    pub fn get(&self, language: &SubmissionLanguage) -> &'static str {
+        (self.table)(language)
+    }
+
+    /// 由语言代码反查语言, 多个语言共用代码时返回第一个
+    pub fn reverse(&self, code: &str) -> Option<SubmissionLanguage> {
+        SubmissionLanguage::iter().find(|language| self.get(language) == code)
+    }
+}
+
+use SubmissionLanguage::*;
+
+// 各映射表均为完整的 `match`, 新增语言时由编译器提示补全
+
+fn seven_fa4(language: &SubmissionLanguage) -> &'static str {
+    match language {
+        Cpp14 => "cpp14",
+        Cpp17 => "cpp17",
+        Cpp11 => "cpp11",
+        Cpp => "cpp",
+        CppNoiLinux => "cpp-noilinux",
+        Cpp11NoiLinux => "cpp11-noilinux",
+        Cpp11Clang => "cpp11-clang",
+        Cpp17Clang => "cpp17-clang",
+        C => "c",
+        CNoiLinux => "c-noilinux",
+    }
+}
+
+fn luogu_remote(language: &SubmissionLanguage) -> &'static str {
+    match language {
+        Cpp14 => "cxx/14/gcc",
+        Cpp17 => "cxx/17/gcc",
+        Cpp11 => "cxx/11/gcc",
+        Cpp => "cxx/98/gcc",
+        CppNoiLinux | Cpp11NoiLinux => "cxx/noi/202107",
+        Cpp11Clang => "cxx/11/clang",
+        Cpp17Clang => "cxx/17/clang",
+        C | CNoiLinux => "c/99/gcc",
+    }
+}
+
+fn hydro(language: &SubmissionLanguage) -> &'static str {
+    match language {
+        Cpp14 => "cc.cc14",
+        Cpp17 | Cpp17Clang => "cc.cc17",
+        Cpp11 | Cpp11NoiLinux | Cpp11Clang => "cc.cc11",
+        Cpp | CppNoiLinux => "cc",
+        C | CNoiLinux => "c",
+    }
+}
+
+fn codeforces(language: &SubmissionLanguage) -> &'static str {
+    match language {
+        Cpp17 => "54",
+        Cpp14 | Cpp11 | Cpp | CppNoiLinux | Cpp11NoiLinux => "50",
+        Cpp11Clang | Cpp17Clang => "52",
+        C | CNoiLinux => "43",
+    }
+}
+
+/// 获取目标平台的语言映射表
+pub fn map_for(target: LanguageTarget) -> LanguageMap {
+    let table = match target {
+        LanguageTarget::SevenFa4 => seven_fa4,
+        LanguageTarget::LuoguRemote => luogu_remote,
+        LanguageTarget::Hydro => hydro,
+        LanguageTarget::Codeforces => codeforces,
+    };
+    LanguageMap { target, table }
+}
+
+impl SubmissionLanguage {
+    /// 目标平台的语言代码
+    pub fn code_for(&self, target: LanguageTarget) -> &'static str {
+        map_for(target).get(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_for() {
+        assert_eq!(Cpp17.code_for(LanguageTarget::Hydro), "cc.cc17");
+        assert_eq!(Cpp14.code_for(LanguageTarget::LuoguRemote), "cxx/14/gcc");
+        assert_eq!(C.code_for(LanguageTarget::Codeforces), "43");
+
+        // 7fa4 代码与序列化名称一致
+        let map = map_for(LanguageTarget::SevenFa4);
+        for language in SubmissionLanguage::iter() {
+            let code = map.get(&language);
+            assert_eq!(
+                serde_json::to_value(&language).unwrap(),
+                serde_json::Value::from(code)
+            );
+            assert_eq!(map.reverse(code), Some(language));
+        }
+        assert_eq!(
+            map_for(LanguageTarget::Codeforces).reverse("50"),
+            Some(Cpp14)
+        );
+    }
+}
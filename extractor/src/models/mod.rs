@@ -8,6 +8,30 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+pub mod builder;
+pub mod context;
+pub mod credential;
+pub mod deployment;
+pub mod input;
+pub mod language_map;
+pub mod oj_map;
+pub mod page;
+pub mod partial;
+pub mod payload;
+pub mod report;
+#[cfg(feature = "schema")]
+pub mod schema;
 pub mod submission;
 
+pub use builder::*;
+pub use context::*;
+pub use credential::{COOKIE_SCHEMAS, CookieInfo, CookieSchema, Credential};
+pub use deployment::{Deployment, Deployments, load_deployments, resolve_chost};
+pub use input::*;
+pub use language_map::{LanguageMap, LanguageTarget, map_for};
+pub use oj_map::{OjMap, OjRule, current_oj_map, load_oj_overrides, map_remote_oj};
+pub use page::{PageInfo, PageKind};
+pub use partial::*;
+pub use payload::*;
+pub use report::*;
 pub use submission::*;
@@ -0,0 +1,192 @@
+//! 远程 OJ 映射
+//!
+//! VJudge 上的 OJ 标签与 7fa4 的 oj 标识不一致, 题号格式也各不相同, 这里集中维护映射规则.
+//! 内置规则可由 JSON 覆盖, 新增远程 OJ 无需发布新版本.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::error::*;
+
+/// 单条映射规则
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OjRule {
+    /// VJudge 上的 OJ 标签 (大小写不敏感)
+    pub labels: Vec<String>,
+    /// 7fa4 的 oj 标识
+    pub oj: String,
+    /// 题号匹配正则, 不匹配时题号保持不变
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid_pattern: Option<String>,
+    /// 题号改写模板, 支持 `$1` 等捕获组引用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid_replace: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    rule: OjRule,
+    pid_regex: Option<Regex>,
+}
+
+impl CompiledRule {
+    fn new(rule: OjRule) -> Result<Self> {
+        let pid_regex = rule.pid_pattern.as_deref().map(Regex::new).transpose()?;
+        Ok(Self { rule, pid_regex })
+    }
+
+    fn matches(&self, label: &str) -> bool {
+        self.rule
+            .labels
+            .iter()
+            .any(|l| l.trim().eq_ignore_ascii_case(label.trim()))
+    }
+
+    fn rewrite_pid(&self, pid: &str) -> String {
+        match (&self.pid_regex, &self.rule.pid_replace) {
+            (Some(re), Some(replace)) if re.is_match(pid) => re.replace(pid, replace).into_owned(),
+            _ => pid.to_string(),
+        }
+    }
+}
+
+/// 远程 OJ 映射表
+#[derive(Debug, Clone)]
+pub struct OjMap {
+    rules: Vec<CompiledRule>,
+}
+
+impl Default for OjMap {
+    fn default() -> Self {
+        Self::from_rules(builtin_rules()).expect("builtin oj rules are valid")
+    }
+}
+
+impl OjMap {
+    /// 由规则列表构建
+    pub fn from_rules(rules: Vec<OjRule>) -> Result<Self> {
+        let rules = rules
+            .into_iter()
+            .map(CompiledRule::new)
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    /// 由 JSON 规则数组构建
+    pub fn from_json(json: &str) -> Result<Self> {
+        let rules: Vec<OjRule> = serde_json::from_str(json)
+            .map_err(|e| Error::from(ExtractErrorKind::Parse(format!("oj map: {e}"))))?;
+        Self::from_rules(rules)
+    }
+
+    /// 以另一张表覆盖, 新规则优先匹配
+    pub fn override_with(&mut self, other: OjMap) {
+        let mut rules = other.rules;
+        rules.append(&mut self.rules);
+        self.rules = rules;
+    }
+
+    /// 当前全部规则
+    pub fn rules(&self) -> impl Iterator<Item = &OjRule> {
+        self.rules.iter().map(|r| &r.rule)
+    }
+
+    /// 将 VJudge 的 OJ 标签与题号映射为 (oj, pid), 未知标签原样返回
+    pub fn map(&self, label: &str, pid: &str) -> (String, String) {
+        match self.rules.iter().find(|r| r.matches(label)) {
+            Some(rule) => (rule.rule.oj.clone(), rule.rewrite_pid(pid.trim())),
+            None => (label.trim().to_string(), pid.trim().to_string()),
+        }
+    }
+}
+
+fn rule(labels: &[&str], oj: &str, pid: Option<(&str, &str)>) -> OjRule {
+    OjRule {
+        labels: labels.iter().map(|l| l.to_string()).collect(),
+        oj: oj.to_string(),
+        pid_pattern: pid.map(|(p, _)| p.to_string()),
+        pid_replace: pid.map(|(_, r)| r.to_string()),
+    }
+}
+
+/// 内置规则
+fn builtin_rules() -> Vec<OjRule> {
+    vec![
+        rule(&["CodeForces", "CF"], "codeforces", None),
+        rule(
+            &["Gym"],
+            "codeforces",
+            Some((r"^(\d+)([A-Za-z]\d*)$", "gym/$1$2")),
+        ),
+        rule(&["AtCoder"], "atcoder", None),
+        rule(&["HDU", "HDOJ"], "hdu", None),
+        rule(&["POJ"], "poj", None),
+        rule(&["洛谷", "Luogu"], "luogu", Some((r"^(?i)p?(\d+)$", "P$1"))),
+    ]
+}
+
+static OJ_MAP: Lazy<RwLock<OjMap>> = Lazy::new(|| RwLock::new(OjMap::default()));
+
+/// 以 JSON 规则覆盖全局映射表, 新规则优先于内置规则
+pub fn load_oj_overrides(json: &str) -> Result<()> {
+    let overrides = OjMap::from_json(json)?;
+    OJ_MAP.write().unwrap().override_with(overrides);
+    Ok(())
+}
+
+/// 重置全局映射表为内置规则
+pub fn reset_oj_map() {
+    *OJ_MAP.write().unwrap() = OjMap::default();
+}
+
+/// 当前全局映射表的副本, 可在其上叠加临时规则
+pub fn current_oj_map() -> OjMap {
+    OJ_MAP.read().unwrap().clone()
+}
+
+/// 使用全局映射表映射 OJ 标签与题号
+pub fn map_remote_oj(label: &str, pid: &str) -> (String, String) {
+    OJ_MAP.read().unwrap().map(label, pid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin() {
+        let map = OjMap::default();
+        assert_eq!(
+            map.map("CodeForces", "1234A"),
+            ("codeforces".to_string(), "1234A".to_string())
+        );
+        assert_eq!(
+            map.map("Gym", "104012B"),
+            ("codeforces".to_string(), "gym/104012B".to_string())
+        );
+        assert_eq!(map.map("洛谷", "p1001").1, "P1001");
+        assert_eq!(map.map("hdu", "1000").0, "hdu");
+        assert_eq!(map.map("UESTC", "126").0, "UESTC");
+    }
+
+    #[test]
+    fn test_override() {
+        let mut map = OjMap::default();
+        let json = r#"[{"labels": ["UESTC"], "oj": "uestc"}, {"labels": ["HDU"], "oj": "hdoj"}]"#;
+        map.override_with(OjMap::from_json(json).unwrap());
+
+        assert_eq!(map.map("UESTC", "126").0, "uestc");
+        assert_eq!(map.map("HDU", "1000").0, "hdoj");
+        assert!(OjMap::from_json(r#"[{"labels": [], "oj": "x", "pid_pattern": "("}]"#).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+//! 页面类型
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+/// 页面类型, 用于在完整提取前判断是否值得提供同步
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+#[serde(rename_all = "snake_case")]
+pub enum PageKind {
+    /// 单条提交记录详情
+    SubmissionDetail,
+    /// 提交记录列表
+    RecordList,
+    /// 题目页面
+    Problem,
+    /// 登录页或登录墙
+    Login,
+    #[default]
+    Unknown,
+}
+
+/// 页面识别结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct PageInfo {
+    /// 匹配的提取器标识, 无匹配时为 `None`
+    pub oj: Option<String>,
+    pub page_kind: PageKind,
+    /// 页面对应的记录编号
+    pub record_id: Option<String>,
+}
@@ -0,0 +1,336 @@
+//! 部分提取结果
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use super::report::ExtractWarning;
+use super::submission::*;
+
+/// 参与完整度统计与校验的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Field {
+    Code,
+    Pid,
+    Rid,
+    Oj,
+    Language,
+    Status,
+    TotalTime,
+    MaxMemory,
+    Score,
+}
+
+impl Field {
+    /// [`PartialSubmission`] 中对应的字段名
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Field::Code => "code",
+            Field::Pid => "pid",
+            Field::Rid => "rid",
+            Field::Oj => "oj",
+            Field::Language => "language",
+            Field::Status => "status",
+            Field::TotalTime => "total_time",
+            Field::MaxMemory => "max_memory",
+            Field::Score => "score",
+        }
+    }
+}
+
+/// 部分提取结果
+///
+/// 所有字段均为 `Option`, `None` 表示页面中未能提取到该字段, 以区分 "未找到" 与 "值为 0".
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+#[serde(default)]
+pub struct PartialSubmission {
+    pub code: Option<String>,
+    pub pid: Option<String>,
+    pub rid: Option<String>,
+    pub oj: Option<String>,
+    pub language: Option<SubmissionLanguage>,
+    pub status: Option<SubmissionStatus>,
+    pub total_time: Option<u64>,
+    pub max_memory: Option<u64>,
+    pub score: Option<u16>,
+    /// 远程评测信息, 不计入完整度
+    pub remote: Option<RemoteInfo>,
+    /// 提交时间 (Unix 毫秒), 不计入完整度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submitted_at: Option<u64>,
+    /// 提取过程中的警告, 不计入完整度
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<ExtractWarning>,
+    /// 识别出的页面布局, 不计入完整度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+    /// 页面显示仍在评测中
+    #[serde(skip)]
+    pub judging: bool,
+}
+
+impl PartialSubmission {
+    /// 字段总数
+    const FIELD_COUNT: usize = 9;
+
+    /// 各字段名及其是否缺失
+    fn field_states(&self) -> [(&'static str, bool); Self::FIELD_COUNT] {
+        [
+            ("code", self.code.is_none()),
+            ("pid", self.pid.is_none()),
+            ("rid", self.rid.is_none()),
+            ("oj", self.oj.is_none()),
+            ("language", self.language.is_none()),
+            ("status", self.status.is_none()),
+            ("total_time", self.total_time.is_none()),
+            ("max_memory", self.max_memory.is_none()),
+            ("score", self.score.is_none()),
+        ]
+    }
+
+    /// 未提取到的字段名
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        self.field_states()
+            .into_iter()
+            .filter_map(|(name, missing)| missing.then_some(name))
+            .collect()
+    }
+
+    /// 已提取到的字段名
+    pub fn found_fields(&self) -> Vec<&'static str> {
+        self.field_states()
+            .into_iter()
+            .filter_map(|(name, missing)| (!missing).then_some(name))
+            .collect()
+    }
+
+    /// 解析语言文本, 无法识别时回退为默认语言并记录警告
+    pub fn parse_language(&mut self, text: &str) {
+        let language = SubmissionLanguage::recognize(text).unwrap_or_else(|| {
+            self.warn(ExtractWarning::UnrecognizedLanguage(text.to_string()));
+            SubmissionLanguage::default()
+        });
+        self.language = Some(language);
+    }
+
+    /// 解析状态文本, 无法识别时记为 Unknown 并记录警告
+    ///
+    /// 等待 / 评测中的状态不记警告, 而是标记 [`judging`](Self::judging).
+    pub fn parse_status(&mut self, text: &str) {
+        if crate::utils::is_judging_status(text) {
+            self.judging = true;
+            self.status = Some(SubmissionStatus::Unknown);
+            return;
+        }
+
+        let status = text.parse().unwrap_or_else(|_| {
+            self.warn(ExtractWarning::UnrecognizedStatus(text.to_string()));
+            SubmissionStatus::Unknown
+        });
+        self.status = Some(status);
+    }
+
+    /// 解析时间文本, 无法解析时记录警告
+    pub fn parse_time(&mut self, text: &str) {
+        self.total_time = self.parse_number("total_time", text, crate::utils::parse_time_to_ms);
+    }
+
+    /// 解析内存文本, 无法解析时记录警告
+    pub fn parse_memory(&mut self, text: &str) {
+        self.max_memory = self.parse_number("max_memory", text, crate::utils::parse_mem_to_kb);
+    }
+
+    /// 解析分数文本, 无法解析或越界时记录警告
+    pub fn parse_score(&mut self, text: &str) {
+        self.score = self.parse_number("score", text, |t| {
+            crate::utils::parse_num::<u16>(t, "score").ok()
+        });
+    }
+
+    fn parse_number<T>(
+        &mut self,
+        field: &str,
+        text: &str,
+        parse: impl Fn(&str) -> Option<T>,
+    ) -> Option<T> {
+        let value = parse(text);
+        if value.is_none() && !text.trim().is_empty() && !crate::utils::is_no_data(text) {
+            self.warnings.push(ExtractWarning::InvalidNumber {
+                field: field.to_string(),
+                text: text.to_string(),
+            });
+        }
+        value
+    }
+
+    /// 页面未给出语言或语言无法识别时, 由代码内容推测语言并记录警告
+    pub fn detect_language(&mut self) {
+        let fell_back = self
+            .warnings
+            .iter()
+            .any(|w| matches!(w, ExtractWarning::UnrecognizedLanguage(_)));
+        if self.language.is_some() && !fell_back {
+            return;
+        }
+
+        if let Some(language) = self.code.as_deref().and_then(SubmissionLanguage::detect) {
+            self.warnings.push(ExtractWarning::LanguageDetected(
+                language.as_str().to_string(),
+            ));
+            self.language = Some(language);
+        }
+    }
+
+    /// 记录无法识别的文本, 并通知观察回调
+    fn warn(&mut self, warning: ExtractWarning) {
+        crate::factory::notify(&warning);
+        self.warnings.push(warning);
+    }
+
+    /// 字段是否缺失, 空字符串视为缺失
+    pub fn is_field_missing(&self, field: Field) -> bool {
+        let is_blank = |f: &Option<String>| f.as_deref().is_none_or(str::is_empty);
+        match field {
+            Field::Code => is_blank(&self.code),
+            Field::Pid => is_blank(&self.pid),
+            Field::Rid => is_blank(&self.rid),
+            Field::Oj => is_blank(&self.oj),
+            Field::Language => self.language.is_none(),
+            Field::Status => self.status.is_none(),
+            Field::TotalTime => self.total_time.is_none(),
+            Field::MaxMemory => self.max_memory.is_none(),
+            Field::Score => self.score.is_none(),
+        }
+    }
+
+    /// 已提取字段所占比例, 取值 `[0, 1]`
+    pub fn completeness(&self) -> f32 {
+        let found = Self::FIELD_COUNT - self.missing_fields().len();
+        found as f32 / Self::FIELD_COUNT as f32
+    }
+
+    /// 合并另一份结果, 仅填充自身缺失的字段
+    pub fn merge(&mut self, other: PartialSubmission) {
+        self.code = self.code.take().or(other.code);
+        self.pid = self.pid.take().or(other.pid);
+        self.rid = self.rid.take().or(other.rid);
+        self.oj = self.oj.take().or(other.oj);
+        self.language = self.language.take().or(other.language);
+        self.status = self.status.take().or(other.status);
+        self.total_time = self.total_time.or(other.total_time);
+        self.max_memory = self.max_memory.or(other.max_memory);
+        self.score = self.score.or(other.score);
+        self.remote = self.remote.take().or(other.remote);
+        self.submitted_at = self.submitted_at.or(other.submitted_at);
+        self.layout = self.layout.take().or(other.layout);
+        self.warnings.extend(other.warnings);
+        self.judging |= other.judging;
+    }
+}
+
+impl From<Submission> for PartialSubmission {
+    fn from(sub: Submission) -> Self {
+        Self {
+            code: Some(sub.code),
+            pid: Some(sub.pid),
+            rid: Some(sub.rid),
+            oj: Some(sub.oj),
+            language: Some(sub.language),
+            status: Some(sub.status),
+            total_time: Some(sub.total_time),
+            max_memory: Some(sub.max_memory),
+            score: sub.score,
+            remote: sub.remote,
+            submitted_at: sub.submitted_at,
+            warnings: Vec::new(),
+            layout: None,
+            judging: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let mut a = PartialSubmission {
+            pid: Some("P1001".to_string()),
+            score: Some(0),
+            ..Default::default()
+        };
+        let b = PartialSubmission {
+            pid: Some("P1002".to_string()),
+            code: Some("int main() {}".to_string()),
+            score: Some(100),
+            ..Default::default()
+        };
+
+        a.merge(b);
+        assert_eq!(a.pid.as_deref(), Some("P1001"));
+        assert_eq!(a.code.as_deref(), Some("int main() {}"));
+        assert_eq!(a.score, Some(0));
+        assert_eq!(a.missing_fields().len(), 6);
+        assert_eq!(a.found_fields(), ["code", "pid", "score"]);
+        assert!((a.completeness() - 3.0 / 9.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let mut partial = PartialSubmission::default();
+        partial.parse_time("1.5s");
+        partial.parse_memory("lots");
+        assert_eq!(partial.total_time, Some(1500));
+        assert_eq!(partial.max_memory, None);
+        assert_eq!(
+            partial.warnings,
+            [ExtractWarning::InvalidNumber {
+                field: "max_memory".to_string(),
+                text: "lots".to_string(),
+            }]
+        );
+
+        partial.parse_time("—");
+        assert_eq!(partial.warnings.len(), 1);
+
+        partial.parse_score("99999999");
+        assert_eq!(partial.score, None);
+        assert_eq!(
+            partial.warnings[1],
+            ExtractWarning::InvalidNumber {
+                field: "score".to_string(),
+                text: "99999999".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_observer() {
+        use std::sync::{Arc, Mutex};
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        crate::set_observer(move |w| sink.lock().unwrap().push(w.to_string()));
+
+        let mut partial = PartialSubmission::default();
+        partial.parse_status("Observer Test Verdict");
+        crate::clear_observer();
+
+        assert!(
+            seen.lock()
+                .unwrap()
+                .iter()
+                .any(|w| w.contains("Observer Test Verdict"))
+        );
+    }
+}
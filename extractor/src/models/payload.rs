@@ -0,0 +1,302 @@
+//! 7fa4 提交接口数据
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::ExtractError;
+use crate::policy::ValidationPolicy;
+
+use super::deployment::api_base;
+use super::submission::*;
+
+/// 未指定部署时使用的 7fa4 主机
+pub const DEFAULT_7FA4_HOST: &str = "oj.7fa4.cn";
+
+/// `chost` 上的 `/foreign_oj` 接口地址
+pub fn foreign_oj_url(chost: &str) -> String {
+    format!("{}/foreign_oj", api_base(chost))
+}
+
+/// 7fa4 `/foreign_oj` 接口的请求体
+///
+/// 字段名, 语言代码与状态字符串均与 7fa4 接口保持一致, 不随 [`Submission`] 的内部字段变化.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct SubmitPayload {
+    pub code: String,
+    pub pid: String,
+    pub rid: String,
+    pub oj: String,
+    pub language: SubmissionLanguage,
+    pub status: SubmissionStatus,
+    pub total_time: u64,
+    pub max_memory: u64,
+    pub score: u16,
+    pub in_contest: bool,
+}
+
+impl SubmitPayload {
+    pub fn new(sub: &Submission, in_contest: bool) -> Self {
+        Self {
+            code: sub.code.clone(),
+            pid: sub.pid.clone(),
+            rid: sub.rid.clone(),
+            oj: sub.oj.clone(),
+            language: sub.language.clone(),
+            status: sub.status.clone(),
+            total_time: sub.total_time,
+            max_memory: sub.max_memory,
+            score: sub.score.unwrap_or(0),
+            in_contest,
+        }
+    }
+}
+
+impl From<SubmitPayload> for Submission {
+    fn from(p: SubmitPayload) -> Self {
+        Self {
+            code: p.code,
+            pid: p.pid,
+            rid: p.rid,
+            oj: p.oj,
+            language: p.language,
+            status: p.status,
+            total_time: p.total_time,
+            max_memory: p.max_memory,
+            score: Some(p.score),
+            ..Default::default()
+        }
+    }
+}
+
+/// 按 7fa4 规范整理后可直接发送的提交
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct PreparedSubmission {
+    /// 提交接口地址
+    pub url: String,
+    pub payload: SubmitPayload,
+}
+
+impl PreparedSubmission {
+    /// 规范化题号与提交 ID 并按策略校验, 语言与状态按 7fa4 的代码序列化
+    ///
+    /// `chost` 为空时使用 [`DEFAULT_7FA4_HOST`].
+    pub fn new(
+        sub: &Submission,
+        chost: &str,
+        in_contest: bool,
+        policy: &ValidationPolicy,
+    ) -> Result<Self, ExtractError> {
+        let mut sub = sub.clone();
+        sub.normalize_ids();
+        policy.validate(&sub.clone().into())?;
+
+        let chost = match chost.trim() {
+            "" => DEFAULT_7FA4_HOST,
+            chost => chost,
+        };
+        Ok(Self {
+            url: foreign_oj_url(chost),
+            payload: SubmitPayload::new(&sub, in_contest),
+        })
+    }
+}
+
+/// `chost` 上查询当前登录用户的接口地址
+pub fn whoami_url(chost: &str) -> String {
+    format!("{}/api/whoami", api_base(chost))
+}
+
+/// 7fa4 `/foreign_oj` 接口的响应
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct SubmitResponse {
+    /// HTTP 状态码为 2xx 且接口返回 `success: true`
+    pub success: bool,
+    pub status_code: u16,
+    /// 新建记录的编号
+    pub record_id: Option<String>,
+    /// 接口返回的错误信息
+    pub message: Option<String>,
+}
+
+impl SubmitResponse {
+    /// 解析接口响应, 响应体不是 JSON 时视为失败
+    pub fn from_body(status_code: u16, body: &str) -> Self {
+        let json: Value = serde_json::from_str(body).unwrap_or_default();
+        // 记录编号可能位于顶层或 `data` 中, 且可能为数字
+        let lookup = |keys: &[&str]| {
+            [&json, &json["data"]]
+                .into_iter()
+                .find_map(|obj| keys.iter().find_map(|key| json_text(&obj[*key])))
+        };
+        let message = lookup(&["message", "msg", "err", "error"]).or_else(|| {
+            (json.is_null() && !body.trim().is_empty()).then(|| body.trim().to_string())
+        });
+
+        Self {
+            success: (200..300).contains(&status_code) && json["success"] == Value::Bool(true),
+            status_code,
+            record_id: lookup(&["record_id", "rid", "id"]),
+            message,
+        }
+    }
+}
+
+/// 7fa4 会话状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct LoginStatus {
+    /// 会话有效且能识别出用户
+    pub logged_in: bool,
+    pub status_code: u16,
+    pub user_id: Option<String>,
+    pub username: Option<String>,
+}
+
+impl LoginStatus {
+    /// 解析 whoami 接口响应, 用户信息可能位于顶层, `user` 或 `data` 中
+    pub fn from_body(status_code: u16, body: &str) -> Self {
+        let json: Value = serde_json::from_str(body).unwrap_or_default();
+        let lookup = |keys: &[&str]| {
+            [&json, &json["user"], &json["data"]]
+                .into_iter()
+                .find_map(|obj| keys.iter().find_map(|key| json_text(&obj[*key])))
+        };
+        let user_id = lookup(&["user_id", "uid", "id"]);
+        let username = lookup(&["username", "name", "nickname"]);
+
+        Self {
+            logged_in: (200..300).contains(&status_code)
+                && json["success"] != Value::Bool(false)
+                && (user_id.is_some() || username.is_some()),
+            status_code,
+            user_id,
+            username,
+        }
+    }
+}
+
+/// 非空字符串或数字
+fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+impl Submission {
+    /// 生成 7fa4 提交接口所需的 JSON 请求体
+    pub fn to_7fa4_payload(&self, in_contest: bool) -> serde_json::Value {
+        serde_json::to_value(SubmitPayload::new(self, in_contest))
+            .expect("SubmitPayload serialization is infallible")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Submission {
+        Submission {
+            code: "int main() {}".to_string(),
+            pid: "P4198".to_string(),
+            rid: "241494617".to_string(),
+            oj: "luogu".to_string(),
+            language: SubmissionLanguage::Cpp17,
+            status: SubmissionStatus::WrongAnswer,
+            total_time: 2330,
+            max_memory: 1587,
+            score: Some(40),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_payload_format() {
+        assert_eq!(
+            sample().to_7fa4_payload(false),
+            json!({
+                "code": "int main() {}",
+                "pid": "P4198",
+                "rid": "241494617",
+                "oj": "luogu",
+                "language": "cpp17",
+                "status": "Wrong Answer",
+                "total_time": 2330,
+                "max_memory": 1587,
+                "score": 40,
+                "in_contest": false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_submit_response() {
+        let ok = SubmitResponse::from_body(200, r#"{"success":true,"data":{"id":1024}}"#);
+        assert!(ok.success);
+        assert_eq!(ok.record_id.as_deref(), Some("1024"));
+
+        let denied = SubmitResponse::from_body(401, r#"{"success":false,"msg":"请先登录"}"#);
+        assert!(!denied.success);
+        assert_eq!(denied.message.as_deref(), Some("请先登录"));
+
+        let html = SubmitResponse::from_body(502, "Bad Gateway");
+        assert!(!html.success);
+        assert_eq!(html.message.as_deref(), Some("Bad Gateway"));
+    }
+
+    #[test]
+    fn test_login_status() {
+        let ok = LoginStatus::from_body(200, r#"{"user":{"id":7,"username":"fltLi"}}"#);
+        assert!(ok.logged_in);
+        assert_eq!(ok.user_id.as_deref(), Some("7"));
+        assert_eq!(ok.username.as_deref(), Some("fltLi"));
+
+        assert!(!LoginStatus::from_body(200, r#"{"success":false,"user":null}"#).logged_in);
+        assert!(!LoginStatus::from_body(401, r#"{"id":7}"#).logged_in);
+        assert!(!LoginStatus::from_body(200, "<html>login</html>").logged_in);
+    }
+
+    #[test]
+    fn test_prepared() {
+        let sub = Submission {
+            pid: "p4198".to_string(),
+            rid: "#0241494617".to_string(),
+            ..sample()
+        };
+        let prepared =
+            PreparedSubmission::new(&sub, "", false, &ValidationPolicy::default()).unwrap();
+        assert_eq!(prepared.url, "http://oj.7fa4.cn/foreign_oj");
+        assert_eq!(prepared.payload.pid, "P4198");
+        assert_eq!(prepared.payload.rid, "241494617");
+
+        let empty = Submission {
+            code: String::new(),
+            ..sample()
+        };
+        let err = PreparedSubmission::new(&empty, "jx.7fa4.cn:8888", false, &Default::default())
+            .unwrap_err();
+        assert_eq!(err.missing_fields, ["code"]);
+    }
+
+    #[test]
+    fn test_payload_round_trip() {
+        let sub = sample();
+        let value = sub.to_7fa4_payload(true);
+        let payload: SubmitPayload = serde_json::from_value(value).unwrap();
+        assert!(payload.in_contest);
+        assert_eq!(Submission::from(payload), sub);
+    }
+}
@@ -0,0 +1,182 @@
+//! 提取报告
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::constants::JUDGING_RETRY_AFTER;
+use crate::error::{ExtractError, ExtractErrorKind};
+use crate::policy::ValidationPolicy;
+
+use super::builder::SubmissionBuilder;
+use super::partial::PartialSubmission;
+use super::submission::*;
+
+/// 非致命的提取警告
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum ExtractWarning {
+    /// 字段缺失, 已取默认值
+    FieldDefaulted(String),
+    /// 无法识别的语言文本, 已回退为默认语言
+    UnrecognizedLanguage(String),
+    /// 无法识别的状态文本, 已记为 Unknown
+    UnrecognizedStatus(String),
+    /// 页面未给出可识别的语言, 已由代码内容推测
+    LanguageDetected(String),
+    /// 数值文本无法解析, 字段已取默认值
+    InvalidNumber { field: String, text: String },
+    /// 多个提取器得分与优先级相同, 按名称选中了其中之一
+    AmbiguousExtractor { chosen: String, tied: Vec<String> },
+}
+
+impl fmt::Display for ExtractWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractWarning::FieldDefaulted(field) => write!(f, "{field} missing, defaulted"),
+            ExtractWarning::UnrecognizedLanguage(text) => {
+                write!(f, "language text unrecognized: {text}")
+            }
+            ExtractWarning::UnrecognizedStatus(text) => {
+                write!(f, "status text unrecognized: {text}")
+            }
+            ExtractWarning::LanguageDetected(language) => {
+                write!(f, "language detected from code: {language}")
+            }
+            ExtractWarning::InvalidNumber { field, text } => {
+                write!(f, "{field} is not a valid number: {text}")
+            }
+            ExtractWarning::AmbiguousExtractor { chosen, tied } => {
+                write!(
+                    f,
+                    "extractor {chosen} chosen, tied with {}",
+                    tied.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// 提取报告: 提交记录与提取过程中的警告
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExtractionReport {
+    pub submission: Submission,
+    pub warnings: Vec<ExtractWarning>,
+    /// 页面中实际提取到的字段, 其余字段为推测或默认值
+    #[serde(default)]
+    pub fields_found: Vec<String>,
+    /// 识别出的页面布局
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+}
+
+impl ExtractionReport {
+    /// 校验部分提取结果并生成报告, 缺失的非必填字段记为警告
+    pub fn build(
+        mut partial: PartialSubmission,
+        policy: &ValidationPolicy,
+    ) -> Result<Self, ExtractError> {
+        if partial.judging {
+            return Err(ExtractError::with_partial(
+                ExtractErrorKind::JudgingInProgress {
+                    retry_after: JUDGING_RETRY_AFTER,
+                },
+                partial,
+            ));
+        }
+
+        let fields_found = partial
+            .found_fields()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        partial.detect_language();
+
+        let mut warnings = std::mem::take(&mut partial.warnings);
+        warnings.extend(
+            partial
+                .missing_fields()
+                .into_iter()
+                .filter(|&field| !policy.required_fields.iter().any(|f| f.as_str() == field))
+                .map(|field| ExtractWarning::FieldDefaulted(field.to_string())),
+        );
+
+        let layout = partial.layout.clone();
+        let submission = SubmissionBuilder::from_partial(partial).build_with(policy)?;
+        Ok(Self {
+            submission,
+            warnings,
+            fields_found,
+            layout,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let mut partial = PartialSubmission {
+            code: Some("print(1)".to_string()),
+            pid: Some("P1001".to_string()),
+            rid: Some("1".to_string()),
+            oj: Some("luogu".to_string()),
+            status: Some(SubmissionStatus::Accepted),
+            total_time: Some(0),
+            max_memory: Some(0),
+            ..Default::default()
+        };
+        partial.parse_language("Python 3");
+
+        let report = ExtractionReport::build(partial, &ValidationPolicy::default()).unwrap();
+        assert_eq!(report.submission.language, SubmissionLanguage::Cpp17);
+        assert_eq!(
+            report.warnings,
+            [
+                ExtractWarning::UnrecognizedLanguage("Python 3".to_string()),
+                ExtractWarning::FieldDefaulted("score".to_string()),
+            ]
+        );
+        assert_eq!(report.warnings[1].to_string(), "score missing, defaulted");
+        assert!(!report.fields_found.contains(&"score".to_string()));
+        assert_eq!(report.fields_found.len(), 8);
+    }
+
+    #[test]
+    fn test_language_detected() {
+        let partial = PartialSubmission {
+            code: Some("#include <iostream>\nint main() { std::cout << 1; }".to_string()),
+            pid: Some("P1001".to_string()),
+            rid: Some("1".to_string()),
+            ..Default::default()
+        };
+
+        let report = ExtractionReport::build(partial, &ValidationPolicy::default()).unwrap();
+        assert_eq!(report.submission.language, SubmissionLanguage::Cpp17);
+        assert_eq!(
+            report.warnings[0],
+            ExtractWarning::LanguageDetected("C++17".to_string())
+        );
+    }
+
+    #[test]
+    fn test_judging() {
+        let mut partial = PartialSubmission::default();
+        partial.parse_status("Judging");
+        assert!(partial.warnings.is_empty());
+
+        let err = ExtractionReport::build(partial, &ValidationPolicy::default()).unwrap_err();
+        assert_eq!(err.kind.retry_after(), Some(JUDGING_RETRY_AFTER));
+    }
+}
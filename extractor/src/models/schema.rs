@@ -0,0 +1,37 @@
+//! 数据模型的 JSON Schema
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use super::*;
+
+/// [`Submission`] 的 JSON Schema
+pub fn submission_schema() -> String {
+    let schema = schemars::schema_for!(Submission);
+    serde_json::to_string_pretty(&schema).expect("schema serialization is infallible")
+}
+
+/// [`PartialSubmission`] 的 JSON Schema
+pub fn partial_submission_schema() -> String {
+    let schema = schemars::schema_for!(PartialSubmission);
+    serde_json::to_string_pretty(&schema).expect("schema serialization is infallible")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submission_schema() {
+        let schema: serde_json::Value = serde_json::from_str(&submission_schema()).unwrap();
+        let status = serde_json::to_string(&schema).unwrap();
+        assert!(status.contains("Wrong Answer"));
+        assert!(status.contains("cpp11-noilinux"));
+        assert!(schema["properties"]["code"].is_object());
+    }
+}
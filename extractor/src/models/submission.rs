@@ -9,10 +9,13 @@
  */
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::str::FromStr;
 
 /// 7fa4 提交记录
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
 pub struct Submission {
     pub code: String,
     pub pid: String,
@@ -21,31 +24,156 @@ pub struct Submission {
     pub language: SubmissionLanguage,
     pub status: SubmissionStatus,
     #[serde(default)]
-    pub total_time: i32, // ms
+    pub total_time: u64, // ms
     #[serde(default)]
-    pub max_memory: i32, // K
-    pub score: i32,
+    pub max_memory: u64, // K
+    #[serde(default)]
+    pub score: Option<u16>, // 未知时为 None
+    /// 规范化后的代码长度 (字符数)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub code_length: usize,
+    /// 规范化后代码的 blake3 哈希
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub code_hash: String,
+    /// 远程评测信息 (经 VJudge 等代理提交时)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<RemoteInfo>,
+    /// 提交时间 (Unix 毫秒)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submitted_at: Option<u64>,
+    /// 提交记录页面的 URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    /// 产生该记录的提取器名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extractor_name: Option<String>,
+    /// 提取时间 (Unix 毫秒)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extracted_at: Option<u64>,
+}
+
+/// 远程评测信息
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
+pub struct RemoteInfo {
+    /// 原始 oj
+    pub oj: String,
+    /// 原始题目 ID
+    pub pid: String,
+    /// 原始 oj 上的提交 ID
+    pub rid: String,
+    /// 原始提交记录链接
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+fn is_zero(v: &usize) -> bool {
+    *v == 0
+}
+
+impl Submission {
+    /// 依据当前代码重新计算长度与哈希
+    pub fn refresh_code_digest(&mut self) {
+        let (length, hash) = crate::utils::code_digest(&self.code);
+        self.code_length = length;
+        self.code_hash = hash;
+    }
+
+    /// 按 oj 规范化题号与提交 ID, 远程评测信息一并处理
+    pub fn normalize_ids(&mut self) {
+        use crate::utils::{normalize_pid, normalize_rid};
+
+        self.pid = normalize_pid(&self.oj, &self.pid);
+        self.rid = normalize_rid(&self.rid);
+        if let Some(remote) = &mut self.remote {
+            remote.pid = normalize_pid(&remote.oj, &remote.pid);
+            remote.rid = normalize_rid(&remote.rid);
+        }
+    }
+
+    /// 记录来源信息, 已有的提取时间不会被覆盖
+    pub fn with_provenance(mut self, url: &str, extractor_name: &str) -> Self {
+        self.source_url = Some(url.to_string());
+        self.extractor_name = Some(extractor_name.to_string());
+        self.extracted_at = self.extracted_at.or_else(crate::utils::now_ms);
+        self
+    }
 }
 
+/// 评测状态
+///
+/// 序列化名称与 7fa4 接口一致, 同时兼容旧版本的变体名称与常见缩写.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
 pub enum SubmissionStatus {
     #[default]
+    #[serde(rename = "Unknown")]
     Unknown,
+    #[serde(rename = "Accepted", alias = "AC")]
     Accepted,
-    #[serde(rename = "Wrong Answer")]
+    #[serde(rename = "Wrong Answer", alias = "WrongAnswer", alias = "WA")]
     WrongAnswer,
-    #[serde(rename = "Partially Correct")]
+    #[serde(rename = "Partially Correct", alias = "PartiallyCorrect", alias = "PC")]
     PartiallyCorrect,
-    #[serde(rename = "Runtime Error")]
+    #[serde(rename = "Runtime Error", alias = "RuntimeError", alias = "RE")]
     RuntimeError,
-    #[serde(rename = "Compile Error")]
+    #[serde(rename = "Compile Error", alias = "CompileError", alias = "CE")]
     CompileError,
-    #[serde(rename = "Time Limit Exceeded")]
+    #[serde(
+        rename = "Time Limit Exceeded",
+        alias = "TimeLimitExceeded",
+        alias = "TLE"
+    )]
     TimeLimitExceeded,
-    #[serde(rename = "Memory Limit Exceeded")]
+    #[serde(
+        rename = "Memory Limit Exceeded",
+        alias = "MemoryLimitExceeded",
+        alias = "MLE"
+    )]
     MemoryLimitExceeded,
 }
 
+impl SubmissionStatus {
+    /// 全部评测状态
+    pub const ALL: [SubmissionStatus; 8] = [
+        SubmissionStatus::Unknown,
+        SubmissionStatus::Accepted,
+        SubmissionStatus::WrongAnswer,
+        SubmissionStatus::PartiallyCorrect,
+        SubmissionStatus::RuntimeError,
+        SubmissionStatus::CompileError,
+        SubmissionStatus::TimeLimitExceeded,
+        SubmissionStatus::MemoryLimitExceeded,
+    ];
+
+    /// 遍历全部评测状态
+    pub fn iter() -> impl Iterator<Item = SubmissionStatus> {
+        Self::ALL.into_iter()
+    }
+
+    /// 显示名称, 与 7fa4 接口的状态字符串一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Unknown => "Unknown",
+            SubmissionStatus::Accepted => "Accepted",
+            SubmissionStatus::WrongAnswer => "Wrong Answer",
+            SubmissionStatus::PartiallyCorrect => "Partially Correct",
+            SubmissionStatus::RuntimeError => "Runtime Error",
+            SubmissionStatus::CompileError => "Compile Error",
+            SubmissionStatus::TimeLimitExceeded => "Time Limit Exceeded",
+            SubmissionStatus::MemoryLimitExceeded => "Memory Limit Exceeded",
+        }
+    }
+}
+
+impl fmt::Display for SubmissionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl FromStr for SubmissionStatus {
     type Err = String;
 
@@ -66,38 +194,84 @@ impl FromStr for SubmissionStatus {
 }
 
 /// 提交语言
+///
+/// 序列化名称为 7fa4 接口的语言代码, 同时兼容旧版本的变体名称.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "tsify", derive(tsify::Tsify))]
 pub enum SubmissionLanguage {
-    #[serde(rename = "cpp14")]
+    #[serde(rename = "cpp14", alias = "Cpp14")]
     Cpp14,
     #[default]
-    #[serde(rename = "cpp17")]
+    #[serde(rename = "cpp17", alias = "Cpp17")]
     Cpp17,
-    #[serde(rename = "cpp11")]
+    #[serde(rename = "cpp11", alias = "Cpp11")]
     Cpp11,
-    #[serde(rename = "cpp")]
+    #[serde(rename = "cpp", alias = "Cpp")]
     Cpp,
-    #[serde(rename = "cpp-noilinux")]
+    #[serde(rename = "cpp-noilinux", alias = "CppNoiLinux")]
     CppNoiLinux,
-    #[serde(rename = "cpp11-noilinux")]
+    #[serde(rename = "cpp11-noilinux", alias = "Cpp11NoiLinux")]
     Cpp11NoiLinux,
-    #[serde(rename = "cpp11-clang")]
+    #[serde(rename = "cpp11-clang", alias = "Cpp11Clang")]
     Cpp11Clang,
-    #[serde(rename = "cpp17-clang")]
+    #[serde(rename = "cpp17-clang", alias = "Cpp17Clang")]
     Cpp17Clang,
-    #[serde(rename = "c")]
+    #[serde(rename = "c", alias = "C")]
     C,
-    #[serde(rename = "c-noilinux")]
+    #[serde(rename = "c-noilinux", alias = "CNoiLinux")]
     CNoiLinux,
 }
 
-impl FromStr for SubmissionLanguage {
-    type Err = String;
+impl SubmissionLanguage {
+    /// 全部提交语言
+    pub const ALL: [SubmissionLanguage; 10] = [
+        SubmissionLanguage::Cpp14,
+        SubmissionLanguage::Cpp17,
+        SubmissionLanguage::Cpp11,
+        SubmissionLanguage::Cpp,
+        SubmissionLanguage::CppNoiLinux,
+        SubmissionLanguage::Cpp11NoiLinux,
+        SubmissionLanguage::Cpp11Clang,
+        SubmissionLanguage::Cpp17Clang,
+        SubmissionLanguage::C,
+        SubmissionLanguage::CNoiLinux,
+    ];
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// 遍历全部提交语言
+    pub fn iter() -> impl Iterator<Item = SubmissionLanguage> {
+        Self::ALL.into_iter()
+    }
+
+    /// 显示名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionLanguage::Cpp14 => "C++14",
+            SubmissionLanguage::Cpp17 => "C++17",
+            SubmissionLanguage::Cpp11 => "C++11",
+            SubmissionLanguage::Cpp => "C++",
+            SubmissionLanguage::CppNoiLinux => "C++ NOI Linux",
+            SubmissionLanguage::Cpp11NoiLinux => "C++11 NOI Linux",
+            SubmissionLanguage::Cpp11Clang => "C++11 Clang",
+            SubmissionLanguage::Cpp17Clang => "C++17 Clang",
+            SubmissionLanguage::C => "C",
+            SubmissionLanguage::CNoiLinux => "C NOI Linux",
+        }
+    }
+}
+
+impl fmt::Display for SubmissionLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl SubmissionLanguage {
+    /// 识别语言文本, 无法识别时返回 `None`
+    pub fn recognize(s: &str) -> Option<Self> {
         let txt = s.trim().to_lowercase();
         if txt.is_empty() {
-            return Err("empty language".to_string());
+            return None;
         }
 
         // 检测环境特征
@@ -109,46 +283,105 @@ impl FromStr for SubmissionLanguage {
             match (has_clang, has_noilinux) {
                 (true, _) => {
                     if txt.contains("17") {
-                        Ok(SubmissionLanguage::Cpp17Clang)
+                        Some(SubmissionLanguage::Cpp17Clang)
                     } else {
-                        Ok(SubmissionLanguage::Cpp11Clang)
+                        Some(SubmissionLanguage::Cpp11Clang)
                     }
                 }
                 (false, true) => {
                     if txt.contains("11") {
-                        Ok(SubmissionLanguage::Cpp11NoiLinux)
+                        Some(SubmissionLanguage::Cpp11NoiLinux)
                     } else {
-                        Ok(SubmissionLanguage::CppNoiLinux)
+                        Some(SubmissionLanguage::CppNoiLinux)
                     }
                 }
                 (false, false) => {
                     if txt.contains("17") {
-                        Ok(SubmissionLanguage::Cpp17)
+                        Some(SubmissionLanguage::Cpp17)
                     } else if txt.contains("14") {
-                        Ok(SubmissionLanguage::Cpp14)
+                        Some(SubmissionLanguage::Cpp14)
                     } else if txt.contains("11") {
-                        Ok(SubmissionLanguage::Cpp11)
+                        Some(SubmissionLanguage::Cpp11)
                     } else {
-                        Ok(SubmissionLanguage::Cpp)
+                        Some(SubmissionLanguage::Cpp)
                     }
                 }
             }
         } else if txt.contains('c') && !txt.contains("c#") && !txt.contains("cs") {
             if has_noilinux {
-                Ok(SubmissionLanguage::CNoiLinux)
+                Some(SubmissionLanguage::CNoiLinux)
             } else {
-                Ok(SubmissionLanguage::C)
+                Some(SubmissionLanguage::C)
             }
         } else {
-            Ok(SubmissionLanguage::Cpp17)
+            None
         }
     }
 }
 
+impl SubmissionLanguage {
+    /// 由代码内容推测语言, 仅能区分 C 与 C++, 其他语言返回 `None`
+    pub fn detect(code: &str) -> Option<Self> {
+        const CPP_MARKERS: &[&str] = &[
+            "#include <bits/stdc++.h>",
+            "#include<bits/stdc++.h>",
+            "<iostream>",
+            "using namespace",
+            "std::",
+            "cin >>",
+            "cout <<",
+            "template <",
+            "template<",
+            "if constexpr",
+            "auto [",
+        ];
+        const C_MARKERS: &[&str] = &["<stdio.h>", "<stdlib.h>", "<string.h>", "scanf(", "printf("];
+        const OTHER_MARKERS: &[&str] = &["import java", "public class", "def main():", "fn main()"];
+
+        let has = |markers: &[&str]| markers.iter().any(|m| code.contains(m));
+
+        if has(OTHER_MARKERS) {
+            None
+        } else if has(CPP_MARKERS) {
+            // 无明显版本特征时取默认的 C++17
+            Some(SubmissionLanguage::Cpp17)
+        } else if has(C_MARKERS) {
+            Some(SubmissionLanguage::C)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for SubmissionLanguage {
+    type Err = String;
+
+    /// 无法识别的语言回退为 C++17
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err("empty language".to_string());
+        }
+        Ok(Self::recognize(s).unwrap_or(SubmissionLanguage::Cpp17))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_language_detect() {
+        let cpp = "#include <bits/stdc++.h>\nusing namespace std;\nint main() {}";
+        assert_eq!(
+            SubmissionLanguage::detect(cpp),
+            Some(SubmissionLanguage::Cpp17)
+        );
+        let c = "#include <stdio.h>\nint main() { printf(\"1\"); }";
+        assert_eq!(SubmissionLanguage::detect(c), Some(SubmissionLanguage::C));
+        assert_eq!(SubmissionLanguage::detect("import java.util.*;"), None);
+        assert_eq!(SubmissionLanguage::detect("def main():\n    pass"), None);
+    }
+
     #[test]
     fn test_language_parsing() {
         assert_eq!("C++".parse(), Ok(SubmissionLanguage::Cpp));
@@ -180,4 +413,95 @@ mod tests {
         assert_eq!("C#".parse(), Ok(SubmissionLanguage::Cpp17));
         assert_eq!("CSharp".parse(), Ok(SubmissionLanguage::Cpp17));
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        for status in SubmissionStatus::iter() {
+            assert_eq!(status.to_string().parse(), Ok(status.clone()));
+        }
+        for language in SubmissionLanguage::iter() {
+            assert_eq!(language.to_string().parse(), Ok(language.clone()));
+        }
+    }
+
+    #[test]
+    fn test_provenance() {
+        let sub = Submission::default().with_provenance("https://vjudge.net/solution/1", "vj");
+        assert_eq!(sub.extractor_name.as_deref(), Some("vj"));
+        assert!(sub.extracted_at.is_some());
+
+        // 未设置的来源字段不参与序列化
+        let value = serde_json::to_value(Submission::default()).unwrap();
+        assert!(value.get("source_url").is_none());
+    }
+
+    #[test]
+    fn test_code_digest() {
+        let mut a = Submission {
+            code: "int main() {\r\n    return 0;\r\n}\r\n".to_string(),
+            ..Default::default()
+        };
+        let mut b = Submission {
+            code: "int main() {\n  return 0;\n}".to_string(),
+            ..Default::default()
+        };
+        a.refresh_code_digest();
+        b.refresh_code_digest();
+        assert_eq!(a.code_hash, b.code_hash);
+        assert_eq!(a.code_length, "int main() { return 0; }".len());
+    }
+
+    #[test]
+    fn test_serde_names() {
+        let status = |s: &str| serde_json::from_str::<SubmissionStatus>(s).unwrap();
+        assert_eq!(status(r#""Wrong Answer""#), SubmissionStatus::WrongAnswer);
+        assert_eq!(status(r#""WrongAnswer""#), SubmissionStatus::WrongAnswer);
+        assert_eq!(status(r#""TLE""#), SubmissionStatus::TimeLimitExceeded);
+        assert_eq!(
+            serde_json::to_string(&SubmissionStatus::MemoryLimitExceeded).unwrap(),
+            r#""Memory Limit Exceeded""#
+        );
+
+        let language = |s: &str| serde_json::from_str::<SubmissionLanguage>(s).unwrap();
+        assert_eq!(
+            language(r#""cpp11-noilinux""#),
+            SubmissionLanguage::Cpp11NoiLinux
+        );
+        assert_eq!(
+            language(r#""Cpp11NoiLinux""#),
+            SubmissionLanguage::Cpp11NoiLinux
+        );
+        assert_eq!(
+            serde_json::to_string(&SubmissionLanguage::CNoiLinux).unwrap(),
+            r#""c-noilinux""#
+        );
+    }
+
+    #[test]
+    fn test_7fa4_round_trip() {
+        // 7fa4 接口返回的提交记录
+        let captured = r##"{
+            "code": "#include <cstdio>\nint main() { return 0; }\n",
+            "pid": "UESTC-126",
+            "rid": "65377961",
+            "oj": "UESTC",
+            "language": "cpp17-clang",
+            "status": "Partially Correct",
+            "total_time": 1886,
+            "max_memory": 10752,
+            "score": 50
+        }"##;
+
+        let sub: Submission = serde_json::from_str(captured).unwrap();
+        assert_eq!(sub.language, SubmissionLanguage::Cpp17Clang);
+        assert_eq!(sub.status, SubmissionStatus::PartiallyCorrect);
+
+        let reparsed: Submission =
+            serde_json::from_str(&serde_json::to_string(&sub).unwrap()).unwrap();
+        assert_eq!(reparsed, sub);
+        assert_eq!(
+            serde_json::to_value(&sub).unwrap(),
+            serde_json::from_str::<serde_json::Value>(captured).unwrap()
+        );
+    }
 }
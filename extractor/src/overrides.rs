@@ -0,0 +1,125 @@
+//! 选择器与正则覆盖
+//!
+//! OJ 调整页面结构时, 扩展可下发新的选择器与正则, 无需发布新的 wasm.
+//! 提取器以 (提取器名称, 键) 查询, 未覆盖的键使用内置值.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::error::*;
+use crate::utils::parse_selector;
+
+/// 单个提取器的覆盖配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtractorOverrides {
+    /// 键 → CSS 选择器
+    pub selectors: HashMap<String, String>,
+    /// 键 → 正则
+    pub regexes: HashMap<String, String>,
+}
+
+/// 校验并编译后的覆盖配置
+#[derive(Debug, Clone)]
+struct Compiled {
+    selectors: HashMap<String, String>,
+    regexes: HashMap<String, Regex>,
+}
+
+impl Compiled {
+    fn new(overrides: ExtractorOverrides) -> Result<Self> {
+        for css in overrides.selectors.values() {
+            parse_selector(css)?;
+        }
+        let regexes = overrides
+            .regexes
+            .into_iter()
+            .map(|(key, pattern)| Ok((key, Regex::new(&pattern)?)))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            selectors: overrides.selectors,
+            regexes,
+        })
+    }
+}
+
+static OVERRIDES: Lazy<RwLock<HashMap<String, Compiled>>> = Lazy::new(Default::default);
+
+/// 安装覆盖配置, JSON 形如 `{ "xyd": { "selectors": { "code": ".cm-line" }, "regexes": {} } }`
+///
+/// 配置整体校验通过后才生效, 同名提取器的旧配置被替换.
+pub fn load_overrides(json: &str) -> Result<()> {
+    let table: HashMap<String, ExtractorOverrides> = serde_json::from_str(json)
+        .map_err(|e| Error::from(ExtractErrorKind::Parse(format!("overrides: {e}"))))?;
+    let compiled = table
+        .into_iter()
+        .map(|(name, overrides)| Ok((name, Compiled::new(overrides)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    tracing::debug!(extractors = compiled.len(), "loaded selector overrides");
+    OVERRIDES.write().unwrap().extend(compiled);
+    Ok(())
+}
+
+/// 清除全部覆盖配置
+pub fn clear_overrides() {
+    OVERRIDES.write().unwrap().clear();
+}
+
+/// 查询 CSS 选择器, 未覆盖时返回 `default`
+pub fn selector(extractor: &str, key: &str, default: &'static str) -> Cow<'static, str> {
+    OVERRIDES
+        .read()
+        .unwrap()
+        .get(extractor)
+        .and_then(|o| o.selectors.get(key))
+        .map_or(Cow::Borrowed(default), |css| Cow::Owned(css.clone()))
+}
+
+/// 查询正则, 未覆盖时返回 `default`
+pub fn regex(extractor: &str, key: &str, default: &Regex) -> Regex {
+    OVERRIDES
+        .read()
+        .unwrap()
+        .get(extractor)
+        .and_then(|o| o.regexes.get(key))
+        .unwrap_or(default)
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides() {
+        let default = Regex::new(r"(\d+)").unwrap();
+        assert_eq!(selector("overrides-test", "code", "pre"), "pre");
+
+        load_overrides(
+            r#"{"overrides-test": {"selectors": {"code": "pre code"}, "regexes": {"pid": "P(\\d+)"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(selector("overrides-test", "code", "pre"), "pre code");
+        assert_eq!(selector("overrides-test", "status", ".status"), ".status");
+        assert_eq!(regex("overrides-test", "pid", &default).as_str(), r"P(\d+)");
+        assert_eq!(regex("overrides-test", "rid", &default).as_str(), r"(\d+)");
+
+        // 无效配置整体不生效
+        assert!(load_overrides(r#"{"overrides-test": {"selectors": {"code": "<<"}}}"#).is_err());
+        assert!(load_overrides(r#"{"overrides-test": {"regexes": {"pid": "("}}}"#).is_err());
+        assert_eq!(selector("overrides-test", "code", "pre"), "pre code");
+    }
+}
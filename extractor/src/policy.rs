@@ -0,0 +1,120 @@
+//! 校验策略
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::error::*;
+use crate::models::*;
+
+/// 提取结果的校验策略
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    /// 必填字段
+    pub required_fields: Vec<Field>,
+    /// 编译错误的提交允许没有代码 (部分 oj 不展示编译失败的代码)
+    pub allow_empty_code_for_ce: bool,
+    /// 要求分数位于 `[0, 100]`
+    pub check_score_range: bool,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            required_fields: vec![Field::Pid, Field::Rid, Field::Code],
+            allow_empty_code_for_ce: true,
+            check_score_range: true,
+        }
+    }
+}
+
+impl ValidationPolicy {
+    /// 不做任何校验
+    pub fn permissive() -> Self {
+        Self {
+            required_fields: Vec::new(),
+            allow_empty_code_for_ce: true,
+            check_score_range: false,
+        }
+    }
+
+    /// 缺失的必填字段
+    pub fn missing_fields(&self, partial: &PartialSubmission) -> Vec<&'static str> {
+        let is_ce = partial.status == Some(SubmissionStatus::CompileError);
+
+        self.required_fields
+            .iter()
+            .copied()
+            .filter(|&field| !(field == Field::Code && is_ce && self.allow_empty_code_for_ce))
+            .filter(|&field| partial.is_field_missing(field))
+            .map(Field::as_str)
+            .collect()
+    }
+
+    /// 校验部分提取结果, 缺失字段会一次性全部报告
+    pub fn validate(&self, partial: &PartialSubmission) -> std::result::Result<(), ExtractError> {
+        let missing = self.missing_fields(partial);
+        if !missing.is_empty() {
+            return Err(ExtractError::missing(missing));
+        }
+
+        if self.check_score_range
+            && let Some(score) = partial.score
+            && score > 100
+        {
+            return Err(ExtractError::new(ExtractErrorKind::Validation(format!(
+                "score out of range: {score}"
+            ))));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let mut partial = PartialSubmission {
+            pid: Some("P1001".to_string()),
+            rid: Some("1".to_string()),
+            status: Some(SubmissionStatus::CompileError),
+            ..Default::default()
+        };
+
+        let policy = ValidationPolicy::default();
+        assert!(policy.validate(&partial).is_ok());
+
+        let strict = ValidationPolicy {
+            allow_empty_code_for_ce: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            strict.validate(&partial).unwrap_err().missing_fields,
+            ["code"]
+        );
+
+        partial.score = Some(120);
+        assert!(matches!(
+            policy.validate(&partial).unwrap_err().kind,
+            ExtractErrorKind::Validation(_)
+        ));
+
+        let empty = PartialSubmission::default();
+        let err = policy.validate(&empty).unwrap_err();
+        assert_eq!(err.missing_fields, ["pid", "rid", "code"]);
+        assert!(matches!(err.kind, ExtractErrorKind::MissingField(ref f) if f == "pid, rid, code"));
+        assert!(ValidationPolicy::permissive().validate(&partial).is_ok());
+
+        // 字段名拼写错误在解析时即报错, 而不是被当作已提取
+        let fields: Vec<Field> = serde_json::from_str(r#"["total_time", "language"]"#).unwrap();
+        assert_eq!(fields, [Field::TotalTime, Field::Language]);
+        assert!(serde_json::from_str::<Field>(r#""langauge""#).is_err());
+    }
+}
@@ -8,13 +8,181 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::future::{Future, ready};
+use std::pin::Pin;
+
 use crate::error::*;
 use crate::models::*;
+use crate::policy::ValidationPolicy;
+
+/// 装箱的 future, 不要求 `Send` 以兼容 wasm
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// 补充资源获取器, 由调用方提供 (浏览器 fetch / 原生 http 客户端)
+pub trait Fetcher {
+    /// 获取 url 对应的文本内容
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String>>;
+}
+
+/// 自检用样例: 内置的小型页面与期望结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub url: &'static str,
+    pub content: &'static str,
+    pub pid: &'static str,
+    pub rid: &'static str,
+    pub status: SubmissionStatus,
+}
+
+impl Fixture {
+    /// 运行样例, 返回首个不符之处
+    pub fn check(&self, extractor: &(impl Extractor + ?Sized)) -> std::result::Result<(), String> {
+        let submission = extractor
+            .extract(self.url, self.content)
+            .map_err(|e| e.to_string())?;
+        let expect = |field: &str, got: &str, want: &str| match got == want {
+            true => Ok(()),
+            false => Err(format!("{field}: expected {want}, got {got}")),
+        };
+        expect("pid", &submission.pid, self.pid)?;
+        expect("rid", &submission.rid, self.rid)?;
+        match submission.status == self.status {
+            true => Ok(()),
+            false => Err(format!(
+                "status: expected {:?}, got {:?}",
+                self.status, submission.status
+            )),
+        }
+    }
+}
 
 /// 提取器
 pub trait Extractor {
     /// 解析提交记录, 返回 7fa4 格式
-    fn extract(&self, url: &str, content: &str) -> Result<Submission>;
+    fn extract(&self, url: &str, content: &str) -> Result<Submission> {
+        self.extract_with_policy(url, content, &ValidationPolicy::default())
+    }
+
+    /// 解析提交记录, 并按给定策略校验
+    fn extract_with_policy(
+        &self,
+        url: &str,
+        content: &str,
+        policy: &ValidationPolicy,
+    ) -> Result<Submission> {
+        self.extract_with_report(url, content, policy)
+            .map(|report| report.submission)
+    }
+
+    /// 解析提交记录页面, 同时返回非致命的警告
+    fn extract_with_report(
+        &self,
+        url: &str,
+        content: &str,
+        policy: &ValidationPolicy,
+    ) -> Result<ExtractionReport> {
+        let ctx = ExtractionContext::new(url);
+        self.extract_input(&ctx, &ExtractorInput::HtmlPage(content), policy)
+    }
+
+    /// 判断页面类型, 只做轻量检查
+    fn classify(&self, url: &str, content: &str) -> PageKind {
+        let _ = (url, content);
+        PageKind::Unknown
+    }
+
+    /// 识别记录编号, 不做完整提取
+    fn record_id(&self, url: &str, content: &str) -> Option<String> {
+        let _ = (url, content);
+        None
+    }
+
+    /// 自检样例, 用于发现已失效的提取器
+    fn fixture(&self) -> Option<Fixture> {
+        None
+    }
+
+    /// 支持的页面布局
+    fn layouts(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// 识别页面布局版本, 无法区分时返回 `None`
+    fn detect_layout(&self, content: &str) -> Option<&'static str> {
+        let _ = content;
+        None
+    }
+
+    /// 支持的输入类型
+    fn inputs(&self) -> &'static [InputKind] {
+        &[InputKind::Html]
+    }
+
+    /// 尽力提取, 不做校验, 校验失败时调用方仍可展示已找到的字段
+    fn extract_partial(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+    ) -> Result<PartialSubmission>;
+
+    /// 按上下文解析任意输入 (页面 / 接口 JSON / 多文档), 同时返回非致命的警告
+    ///
+    /// 默认对 [`Extractor::extract_partial`] 的结果按策略校验.
+    fn extract_input(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+        policy: &ValidationPolicy,
+    ) -> Result<ExtractionReport> {
+        let partial = self.extract_merged(ctx, input)?;
+        ExtractionReport::build(partial, policy).map_err(Error::Extract)
+    }
+
+    /// 逐个提取多文档输入中的文档并合并, 靠前文档的字段优先
+    ///
+    /// 单文档输入直接调用 [`Extractor::extract_partial`]; 全部文档均失败时返回首个错误.
+    fn extract_merged(
+        &self,
+        ctx: &ExtractionContext<'_>,
+        input: &ExtractorInput<'_>,
+    ) -> Result<PartialSubmission> {
+        let ExtractorInput::MultiDocument(docs) = input else {
+            return self.extract_partial(ctx, input);
+        };
+        let mut merged: Option<PartialSubmission> = None;
+        let mut first_err = None;
+        for doc in docs {
+            let single = match doc.kind {
+                InputKind::Html => ExtractorInput::HtmlPage(doc.content),
+                InputKind::Json => ExtractorInput::JsonApi(doc.content),
+            };
+            match self.extract_partial(ctx, &single) {
+                Ok(partial) => match &mut merged {
+                    Some(merged) => merged.merge(partial),
+                    None => merged = Some(partial),
+                },
+                Err(e) => {
+                    tracing::debug!(url = doc.url, code = e.code(), "skipped document: {e}");
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+        merged.ok_or_else(|| first_err.unwrap_or_else(|| ExtractErrorKind::EmptyContent.into()))
+    }
+
+    /// 异步解析提交记录, 可通过 `fetcher` 获取页面外的补充资源
+    ///
+    /// 默认不获取任何资源, 直接调用 [`Extractor::extract_with_report`].
+    fn extract_async<'a>(
+        &'a self,
+        url: &'a str,
+        content: &'a str,
+        policy: &'a ValidationPolicy,
+        fetcher: &'a dyn Fetcher,
+    ) -> BoxFuture<'a, Result<ExtractionReport>> {
+        let _ = fetcher;
+        Box::pin(ready(self.extract_with_report(url, content, policy)))
+    }
 }
 
 /// 工厂注册用提取器
@@ -8,99 +8,757 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-/// 将时间字符串解析为毫秒
-pub fn parse_time_to_ms(s: &str) -> Option<i32> {
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Selector};
+use std::collections::HashMap;
+
+use crate::error::{ExtractErrorKind, Result};
+
+use crate::models::{OjMap, RemoteInfo, Submission, map_remote_oj};
+
+/// 当前时间 (Unix 毫秒)
+///
+/// wasm32 下 `SystemTime` 不可用, 返回 `None`, 由运行时自行填充.
+pub fn now_ms() -> Option<u64> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_millis() as u64)
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
+/// 将浮点数值饱和转换为 u64 (负数与 NaN 取 0, 超出上限取 `u64::MAX`)
+fn saturate_u64(v: f64) -> u64 {
+    if v.is_nan() || v <= 0.0 {
+        0
+    } else if v >= u64::MAX as f64 {
+        u64::MAX
+    } else {
+        v as u64
+    }
+}
+
+/// 跟踪参数, 规范化 URL 时去除
+const TRACKING_PARAMS: &[&str] = &["spm", "from", "ref", "fbclid", "gclid", "_t"];
+
+/// 规范化 URL: 升级为 https, 去除 `www.` / `m.` 前缀、跟踪参数、片段与末尾斜杠
+///
+/// 无法解析时原样返回去除首尾空白的输入.
+pub fn canonicalize_url(url: &str) -> String {
+    let trimmed = url.trim();
+    let Ok(mut parsed) = url::Url::parse(trimmed) else {
+        return trimmed.to_string();
+    };
+
+    if parsed.scheme() == "http" {
+        let _ = parsed.set_scheme("https");
+    }
+    if let Some(host) = parsed.host_str() {
+        let host = host
+            .strip_prefix("www.")
+            .or_else(|| host.strip_prefix("m."))
+            .unwrap_or(host)
+            .to_string();
+        let _ = parsed.set_host(Some(&host));
+    }
+    parsed.set_fragment(None);
+
+    let query: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(k, _)| !k.starts_with("utm_") && !TRACKING_PARAMS.contains(&k.as_ref()))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    if query.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(query);
+    }
+
+    let path = parsed.path().trim_end_matches('/').to_string();
+    parsed.set_path(if path.is_empty() { "/" } else { &path });
+
+    let out = parsed.to_string();
+    // 根路径的 "/" 由 url 库补全, 一并去除
+    match out.strip_suffix('/') {
+        Some(stripped) if parsed.path() == "/" && parsed.query().is_none() => stripped.to_string(),
+        _ => out,
+    }
+}
+
+const MINUTE_MS: i64 = 60_000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+// 相对时间: "3 分钟前", "2 days ago", "an hour ago"
+static RELATIVE_TIME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(\d+|an?)\s*(秒|分钟|小时|天|周|个月|月|年|seconds?|secs?|minutes?|mins?|hours?|hrs?|days?|weeks?|months?|years?)\s*(?:前|ago)$").unwrap()
+});
+
+// 相对日期加钟点: "昨天 14:20", "yesterday at 2:20"
+static RELATIVE_DAY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(今天|昨天|前天|today|yesterday)\s*(?:at\s*)?(\d{1,2}):(\d{2})(?::(\d{2}))?$")
+        .unwrap()
+});
+
+// 绝对时间: "2025-01-02 14:20:00", "2025/1/2 14:20"
+static ABSOLUTE_TIME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(\d{4})[-/.](\d{1,2})[-/.](\d{1,2})(?:[ T](\d{1,2}):(\d{2})(?::(\d{2}))?)?$")
+        .unwrap()
+});
+
+/// 公历日期距 1970-01-01 的天数
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// 将相对或绝对的时间文本转换为 Unix 毫秒
+///
+/// `now_ms` 为当前时间, 缺失时仅能解析绝对时间; `utc_offset_minutes` 为页面时区 (如北京时间为 480),
+/// 用于解析 "昨天 14:20" 与不带时区的绝对时间.
+pub fn parse_relative_time(
+    text: &str,
+    now_ms: Option<u64>,
+    utc_offset_minutes: i32,
+) -> Option<u64> {
+    let txt = text.trim();
+    let offset = i64::from(utc_offset_minutes) * MINUTE_MS;
+    let clock = |h: &str, m: &str, s: Option<&str>| -> Option<i64> {
+        let (h, m, s): (i64, i64, i64) = (
+            h.parse().ok()?,
+            m.parse().ok()?,
+            s.map_or(Some(0), |s| s.parse().ok())?,
+        );
+        (h < 24 && m < 60 && s < 60).then_some(h * HOUR_MS + m * MINUTE_MS + s * 1000)
+    };
+    let to_u64 = |ms: i64| u64::try_from(ms).ok();
+
+    if let Some(caps) = ABSOLUTE_TIME_REGEX.captures(txt) {
+        let (year, month, day): (i64, i64, i64) = (
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        );
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+        let time = match (caps.get(4), caps.get(5)) {
+            (Some(h), Some(m)) => clock(h.as_str(), m.as_str(), caps.get(6).map(|s| s.as_str()))?,
+            _ => 0,
+        };
+        return to_u64(days_from_civil(year, month, day) * DAY_MS + time - offset);
+    }
+
+    let now = i64::try_from(now_ms?).ok()?;
+    let lower = txt.to_lowercase();
+    if matches!(lower.as_str(), "刚刚" | "just now" | "now") {
+        return to_u64(now);
+    }
+
+    if let Some(caps) = RELATIVE_TIME_REGEX.captures(txt) {
+        let n: i64 = match caps[1].to_lowercase().as_str() {
+            "a" | "an" => 1,
+            digits => digits.parse().ok()?,
+        };
+        let unit = caps[2].to_lowercase();
+        let scale = match unit.trim_end_matches('s') {
+            "秒" | "second" | "sec" => 1000,
+            "分钟" | "minute" | "min" => MINUTE_MS,
+            "小时" | "hour" | "hr" => HOUR_MS,
+            "天" | "day" => DAY_MS,
+            "周" | "week" => 7 * DAY_MS,
+            "个月" | "月" | "month" => 30 * DAY_MS,
+            "年" | "year" => 365 * DAY_MS,
+            _ => return None,
+        };
+        return to_u64(now - n.checked_mul(scale)?);
+    }
+
+    if let Some(caps) = RELATIVE_DAY_REGEX.captures(txt) {
+        let days_ago = match caps[1].to_lowercase().as_str() {
+            "今天" | "today" => 0,
+            "昨天" | "yesterday" => 1,
+            _ => 2,
+        };
+        let local_now = now + offset;
+        let day_start = local_now - local_now.rem_euclid(DAY_MS);
+        let time = clock(&caps[2], &caps[3], caps.get(4).map(|s| s.as_str()))?;
+        return to_u64(day_start - days_ago * DAY_MS + time - offset);
+    }
+
+    None
+}
+
+/// 状态文本是否表示评测尚未完成
+pub fn is_judging_status(s: &str) -> bool {
+    let txt = s.trim().to_lowercase();
+    [
+        "waiting",
+        "pending",
+        "judging",
+        "running",
+        "compiling",
+        "queuing",
+        "等待",
+        "评测中",
+        "编译中",
+    ]
+    .iter()
+    .any(|k| txt.contains(k))
+}
+
+/// 将 "数值 单位" 序列拆分为 (数值, 小写单位) 对, 单位可为空
+///
+/// 逗号视为小数点, 出现无法识别的字符时返回 `None`.
+fn split_quantities(s: &str) -> Option<Vec<(f64, String)>> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut pairs = Vec::new();
+    let mut i = 0;
+
+    let skip_ws = |i: &mut usize| {
+        while *i < chars.len() && chars[*i].is_whitespace() {
+            *i += 1;
+        }
+    };
+
+    skip_ws(&mut i);
+    while i < chars.len() {
+        // 数值: 可选符号, 数字与小数点, 可选指数
+        let mut num = String::new();
+        if matches!(chars[i], '-' | '+') {
+            num.push(chars[i]);
+            i += 1;
+        }
+        while i < chars.len() {
+            match chars[i] {
+                c @ ('0'..='9' | '.') => num.push(c),
+                ',' => num.push('.'),
+                'e' | 'E'
+                    if chars
+                        .get(i + 1)
+                        .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+')) =>
+                {
+                    num.push('e');
+                    i += 1;
+                    num.push(chars[i]);
+                }
+                _ => break,
+            }
+            i += 1;
+        }
+        let value = num.parse::<f64>().ok()?;
+
+        skip_ws(&mut i);
+        let mut unit = String::new();
+        while i < chars.len() && chars[i].is_alphabetic() {
+            unit.extend(chars[i].to_lowercase());
+            i += 1;
+        }
+        skip_ws(&mut i);
+
+        pairs.push((value, unit));
+    }
+
+    (!pairs.is_empty()).then_some(pairs)
+}
+
+/// 文本是否表示 "无数据" (如 "-", "—", "N/A")
+pub(crate) fn is_no_data(s: &str) -> bool {
     let txt = s.trim();
-    if txt.is_empty() {
+    txt.eq_ignore_ascii_case("n/a") || (!txt.is_empty() && txt.chars().all(|c| "-–—".contains(c)))
+}
+
+/// 解析数值, 失败时错误信息附带字段名与原始文本
+pub fn parse_num<T>(s: &str, field: &str) -> std::result::Result<T, ExtractErrorKind>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    s.trim()
+        .parse()
+        .map_err(|e| ExtractErrorKind::Convert(format!("{field}: `{s}`: {e}")))
+}
+
+/// 将时间字符串解析为毫秒
+///
+/// 支持 "1min 20s", "1.2 s", "1,23s" 等写法; "12ms / 1000ms" 取测得部分; 破折号视为无数据.
+pub fn parse_time_to_ms(s: &str) -> Option<u64> {
+    if is_no_data(s) {
         return None;
     }
-    let lower = txt.to_lowercase();
-    if lower.contains("ms") {
-        let num = lower.replace("ms", "").trim().to_string();
-        num.parse::<f64>().ok().map(|v| v as i32)
-    } else if lower.contains('s') {
-        let num = lower.replace('s', "").trim().to_string();
-        num.parse::<f64>().ok().map(|v| (v * 1000.0) as i32)
-    } else {
-        txt.parse::<f64>().ok().map(|v| v as i32)
+    let measured = s.split('/').next()?.trim();
+    if measured.is_empty() {
+        return None;
     }
+
+    let pairs = split_quantities(measured)?;
+    let mut total = 0.0;
+    for (value, unit) in &pairs {
+        let scale = match unit.as_str() {
+            // 无单位按毫秒处理 (兼容历史行为)
+            "" if pairs.len() == 1 => 1.0,
+            "us" | "μs" | "µs" => 0.001,
+            "ms" => 1.0,
+            "s" | "sec" | "secs" => 1000.0,
+            "m" | "min" | "mins" => 60_000.0,
+            "h" | "hr" => 3_600_000.0,
+            _ => return None,
+        };
+        total += value * scale;
+    }
+    Some(saturate_u64(total))
 }
 
 /// 将内存字符串解析为 KB
-pub fn parse_mem_to_kb(s: &str) -> Option<i32> {
-    let txt = s.trim();
-    if txt.is_empty() {
+///
+/// 支持 B / K / KB / KiB / M / MB / MiB / G / GB / GiB 后缀 (大小写不敏感, 可带空格, 按 1024 进制);
+/// 无单位按 KB 处理, 含多余内容时返回 `None`.
+pub fn parse_mem_to_kb(s: &str) -> Option<u64> {
+    if is_no_data(s) {
         return None;
     }
 
-    // 统一用小写做后缀判断, 但保留原始数字子串解析
-    let lower = txt.to_lowercase();
+    let pairs = split_quantities(s)?;
+    let [(value, unit)] = pairs.as_slice() else {
+        return None;
+    };
+    let value = *value;
 
-    // 处理常见后缀: mb, m (兆 / MB), kb, k (千 / KB), b (字节)
-    if lower.ends_with("mb") || lower.ends_with('m') {
-        let num = lower
-            .trim_end_matches("mb")
-            .trim_end_matches('m')
-            .trim()
-            .to_string();
-        return num.parse::<f64>().ok().map(|v| (v * 1024.0) as i32);
+    // 以 KB 为基准的倍数, 字节以负数表示除数
+    let scale: i64 = match unit.as_str() {
+        "b" | "byte" | "bytes" => -1024,
+        "" | "k" | "kb" | "kib" => 1,
+        "m" | "mb" | "mib" => 1024,
+        "g" | "gb" | "gib" => 1024 * 1024,
+        _ => return None,
+    };
+
+    // 整数值用 i64 计算, 避免浮点误差与溢出
+    if value.fract() == 0.0 && value.abs() < (1u64 << 53) as f64 {
+        let v = value as i64;
+        let kb = if scale < 0 {
+            v / -scale
+        } else {
+            v.saturating_mul(scale)
+        };
+        return Some(u64::try_from(kb).unwrap_or(0));
     }
 
-    if lower.ends_with("kb") || lower.ends_with('k') {
-        let num = lower
-            .trim_end_matches("kb")
-            .trim_end_matches('k')
-            .trim()
-            .to_string();
-        return num.parse::<f64>().ok().map(|v| v as i32);
+    let kb = if scale < 0 {
+        value / -scale as f64
+    } else {
+        value * scale as f64
+    };
+    Some(saturate_u64(kb))
+}
+
+// HTML 实体
+static ENTITY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(#[xX][0-9a-fA-F]+|#[0-9]+|[a-zA-Z]+);").unwrap());
+
+/// 解码单个 HTML 实体, 无法识别时返回 `None`
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(num) = entity.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+
+    Some(match entity {
+        "nbsp" => ' ',
+        "lt" => '<',
+        "gt" => '>',
+        "amp" => '&',
+        "quot" => '"',
+        "apos" => '\'',
+        "tab" | "Tab" => '\t',
+        "NewLine" => '\n',
+        _ => return None,
+    })
+}
+
+/// 解码 HTML 实体, 无法识别的实体原样保留
+///
+/// 页面文本已由 HTML 解析器解码, 仅用于已知经过二次转义的内容 (如属性或原始 JSON 中的 HTML),
+/// 否则会改写代码中本来就有的 `&lt;` / `&amp;` 等文本.
+pub fn decode_entities(text: &str) -> String {
+    ENTITY_REGEX
+        .replace_all(text, |caps: &regex::Captures| {
+            decode_entity(&caps[1]).map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into_owned()
+}
+
+/// 规范化提取到的代码: NBSP 转为空格, 去除 BOM / 零宽字符, 统一换行为 `\n`
+///
+/// 不解码 HTML 实体, 代码中的 `&amp;` 等文本保持原样.
+pub fn normalize_code(code: &str) -> String {
+    code.replace("\r\n", "\n")
+        .chars()
+        .filter_map(|c| match c {
+            '\r' => Some('\n'),
+            '\u{a0}' | '\u{202f}' => Some(' '),
+            '\u{feff}' | '\u{200b}' | '\u{200c}' | '\u{200d}' | '\u{2060}' => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// 去除代码块中混入的行号栏
+///
+/// 仅当每一行都以连续递增的行号开头时才视为行号栏, 去除行号及其后的一个分隔符 (`|` / `:` / 空格 / 制表符).
+pub fn strip_line_numbers(code: &str) -> String {
+    let lines: Vec<&str> = code.trim_end_matches('\n').split('\n').collect();
+    if lines.len() < 2 {
+        return code.to_string();
     }
 
-    // 单位为字节 (e.g. "1024b" 或 "1024B"), 转换为 KB
-    if lower.ends_with('b') {
-        let num = lower.trim_end_matches('b').trim().to_string();
-        return num.parse::<f64>().ok().map(|v| (v / 1024.0) as i32);
+    let mut stripped = Vec::with_capacity(lines.len());
+    let mut expected = None;
+    for line in &lines {
+        let rest = line.trim_start();
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let Ok(number) = rest[..digits].parse::<u64>() else {
+            return code.to_string();
+        };
+        if expected.is_some_and(|n| n != number) {
+            return code.to_string();
+        }
+        expected = Some(number + 1);
+
+        let body = &rest[digits..];
+        let body = body.strip_prefix(['|', ':']).unwrap_or(body);
+        stripped.push(body.strip_prefix([' ', '\t']).unwrap_or(body));
     }
 
-    // 没有单位, 按 KB 处理 (兼容历史行为)
-    txt.parse::<f64>().ok().map(|v| v as i32)
+    let mut out = stripped.join("\n");
+    if code.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+// 分数: "8/10"
+static FRACTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d+(?:\.\d+)?)\s*/\s*(\d+(?:\.\d+)?)").unwrap());
+
+// 数值: "37.5", "80%"
+static NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+(?:\.\d+)?").unwrap());
+
+/// 将得分文本解析为 0–100 的整数
+///
+/// 支持 "80%", "AC 8/10", "100 分", "Score: 37.5" 等写法, 小数四舍五入, 超出范围时返回 `None`.
+pub fn parse_score(s: &str) -> Option<u16> {
+    let value = if let Some(caps) = FRACTION_REGEX.captures(s) {
+        let (got, total): (f64, f64) = (caps[1].parse().ok()?, caps[2].parse().ok()?);
+        if total <= 0.0 {
+            return None;
+        }
+        got / total * 100.0
+    } else {
+        NUMBER_REGEX.find(s)?.as_str().parse().ok()?
+    };
+
+    let rounded = value.round();
+    (0.0..=100.0).contains(&rounded).then_some(rounded as u16)
+}
+
+/// 解析 CSS 选择器, 错误信息附带选择器原文
+pub fn parse_selector(css: &str) -> Result<Selector> {
+    Selector::parse(css)
+        .map_err(|e| ExtractErrorKind::SelectorParse(format!("`{css}`: {e}")).into())
+}
+
+/// 首个匹配元素的文本 (去除首尾空白), 无匹配时返回 `None`
+pub fn select_text(document: &Html, css: &str) -> Result<Option<String>> {
+    let selector = parse_selector(css)?;
+    Ok(document
+        .select(&selector)
+        .next()
+        .map(|el| el.text().collect::<String>().trim().to_string()))
+}
+
+/// 首个匹配元素的属性值 (去除首尾空白), 无匹配元素或属性时返回 `None`
+pub fn select_attr(document: &Html, css: &str, attr: &str) -> Result<Option<String>> {
+    let selector = parse_selector(css)?;
+    Ok(document
+        .select(&selector)
+        .next()
+        .and_then(|el| el.value().attr(attr))
+        .map(|v| v.trim().to_string()))
+}
+
+/// 表格标签别名: (规范键, 中英文标签)
+const LABEL_ALIASES: &[(&str, &[&str])] = &[
+    ("language", &["语言", "编程语言", "language", "lang"]),
+    (
+        "status",
+        &[
+            "评测结果",
+            "评测状态",
+            "状态",
+            "status",
+            "result",
+            "verdict",
+        ],
+    ),
+    ("time", &["耗时", "用时", "时间", "time", "runtime"]),
+    ("memory", &["内存消耗", "内存", "memory"]),
+    ("score", &["得分", "分数", "评测分数", "score", "points"]),
+    (
+        "submitted_at",
+        &["提交时间", "submit time", "submitted", "submission time"],
+    ),
+    ("problem", &["题目", "problem"]),
+    ("user", &["用户", "提交者", "user", "author"]),
+];
+
+/// 将表格标签规范化为键: 识别的别名映射为规范键, 否则取去除冒号后的小写标签
+pub fn normalize_label(label: &str) -> String {
+    let label = label
+        .trim()
+        .trim_end_matches([':', '：'])
+        .trim()
+        .to_lowercase();
+    LABEL_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&label.as_str()))
+        .map_or(label, |(key, _)| key.to_string())
+}
+
+/// 提取键值对: 对每个匹配 `row_selector` 的元素, 取其中首个 `key_selector` 与 `value_selector` 的文本
+///
+/// 键经 [`normalize_label`] 规范化, 重复的键保留首次出现的值.
+pub fn kv_pairs(
+    document: &Html,
+    row_selector: &str,
+    key_selector: &str,
+    value_selector: &str,
+) -> Result<HashMap<String, String>> {
+    let row_sel = parse_selector(row_selector)?;
+    let key_sel = parse_selector(key_selector)?;
+    let value_sel = parse_selector(value_selector)?;
+    let text = |el: scraper::ElementRef| el.text().collect::<String>().trim().to_string();
+
+    let mut map = HashMap::new();
+    for row in document.select(&row_sel) {
+        if let (Some(key), Some(value)) =
+            (row.select(&key_sel).next(), row.select(&value_sel).next())
+        {
+            map.entry(normalize_label(&text(key)))
+                .or_insert_with(|| text(value));
+        }
+    }
+    Ok(map)
+}
+
+/// 按标签查找字段值: 在匹配 `container_selector` 的元素中, 找到文本等于任一别名的子元素, 返回其后首个兄弟元素的文本
+///
+/// 别名比较忽略大小写与末尾冒号, 可同时传入中英文标签以兼容不同的页面语言.
+pub fn find_field_by_label(
+    document: &Html,
+    container_selector: &str,
+    labels: &[&str],
+) -> Result<Option<String>> {
+    let container_sel = parse_selector(container_selector)?;
+    let normalize = |s: &str| s.trim().trim_end_matches([':', '：']).trim().to_lowercase();
+    let labels: Vec<String> = labels.iter().map(|l| normalize(l)).collect();
+
+    for container in document.select(&container_sel) {
+        let mut children = container.children().filter_map(scraper::ElementRef::wrap);
+        while let Some(child) = children.next() {
+            if labels.contains(&normalize(&child.text().collect::<String>()))
+                && let Some(value) = children.next()
+            {
+                return Ok(Some(value.text().collect::<String>().trim().to_string()));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// 提取 th / td 形式的表格为 标签 → 值 映射
+pub fn kv_table(document: &Html, table_selector: &str) -> Result<HashMap<String, String>> {
+    kv_pairs(document, &format!("{table_selector} tr"), "th", "td")
+}
+
+/// 规范化代码空白: 所有连续空白 (含换行) 折叠为单个空格, 并去除首尾空白
+pub fn normalize_whitespace(code: &str) -> String {
+    code.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 计算代码摘要, 返回规范化后的 (字符数, blake3 十六进制哈希)
+pub fn code_digest(code: &str) -> (usize, String) {
+    let normalized = normalize_whitespace(code);
+    let hash = blake3::hash(normalized.as_bytes()).to_hex().to_string();
+    (normalized.chars().count(), hash)
+}
+
+/// 去除 C / C++ 风格的注释, 字符串与字符字面量中的内容保持不变
+fn strip_comments(code: &str) -> String {
+    let mut out = String::with_capacity(code.len());
+    let mut chars = code.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            '"' | '\'' => {
+                out.push(c);
+                while let Some(n) = chars.next() {
+                    out.push(n);
+                    if n == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            out.push(escaped);
+                        }
+                    } else if n == c || n == '\n' {
+                        break;
+                    }
+                }
+            }
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// 计算代码指纹: 去除注释并规范化空白后的 blake3 十六进制哈希
+///
+/// 仅注释或排版不同的代码得到相同指纹, 可用于判断提交是否已同步.
+pub fn code_fingerprint(code: &str) -> String {
+    let normalized = normalize_whitespace(&strip_comments(&normalize_code(code)));
+    blake3::hash(normalized.as_bytes()).to_hex().to_string()
+}
+
+// Codeforces 题号: "1234A", "1234/a", "CF1234 A"
+static CODEFORCES_PID_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^(?:cf)?(\d+)\s*[/ ]?\s*([a-z]\d?)$").unwrap());
+
+/// 按 oj 规范化题号, 使其符合 7fa4 的格式
+///
+/// - 洛谷: 题库前缀 P / B / U / T 统一大写, 纯数字补 `P`
+/// - Codeforces: `1234/A`, `CF1234a` 统一为 `1234A`, 保留 `gym/` 前缀
+/// - AtCoder: 统一小写
+pub fn normalize_pid(oj: &str, pid: &str) -> String {
+    let pid = pid.trim();
+    match oj.trim().to_lowercase().as_str() {
+        "luogu" => {
+            let mut chars = pid.chars();
+            match chars.next() {
+                Some(c) if c.is_ascii_digit() => format!("P{pid}"),
+                Some(c) if "pbut".contains(c.to_ascii_lowercase()) => {
+                    format!("{}{}", c.to_ascii_uppercase(), chars.as_str())
+                }
+                _ => pid.to_string(),
+            }
+        }
+        "codeforces" => {
+            let (prefix, rest) = match pid.get(..4) {
+                Some(p) if p.eq_ignore_ascii_case("gym/") => ("gym/", &pid[4..]),
+                _ => ("", pid),
+            };
+            match CODEFORCES_PID_REGEX.captures(rest) {
+                Some(caps) => format!("{prefix}{}{}", &caps[1], caps[2].to_uppercase()),
+                None => pid.to_string(),
+            }
+        }
+        "atcoder" => pid.to_lowercase(),
+        _ => pid.to_string(),
+    }
+}
+
+/// 规范化提交 ID: 去除空白与 `#` 前缀, 纯数字时去除前导零
+pub fn normalize_rid(rid: &str) -> String {
+    let rid = rid.trim().trim_start_matches('#');
+    if !rid.is_empty() && rid.chars().all(|c| c.is_ascii_digit()) {
+        let trimmed = rid.trim_start_matches('0');
+        return if trimmed.is_empty() { "0" } else { trimmed }.to_string();
+    }
+    rid.to_string()
 }
 
 /// 如果 submission 来源于 VJudge, 尝试将其映射为真实的源 OJ (参考 extension/popup.js 中的逻辑)
-/// 输入: submission 的部分结果
-/// 输出: (mapped_oj, mapped_pid, mapped_rid) 三元组, 未映射时返回 None
-pub fn map_vjudge_to_origin(sub: &crate::models::Submission) -> Option<(String, String, String)> {
+///
+/// 优先使用提取器记录的 [`RemoteInfo`], 否则从 pid 中拆分原始 oj 与题号, 并按 [`map_remote_oj`] 映射.
+pub fn map_vjudge_to_origin(sub: &Submission) -> Option<RemoteInfo> {
+    map_vjudge_by(sub, map_remote_oj)
+}
+
+/// 同 [`map_vjudge_to_origin`], 但使用调用方提供的映射表
+pub fn map_vjudge_to_origin_with(sub: &Submission, map: &OjMap) -> Option<RemoteInfo> {
+    map_vjudge_by(sub, |label, pid| map.map(label, pid))
+}
+
+fn map_vjudge_by(
+    sub: &Submission,
+    map: impl FnOnce(&str, &str) -> (String, String),
+) -> Option<RemoteInfo> {
+    if let Some(remote) = &sub.remote {
+        return Some(remote.clone());
+    }
+
     // 仅在 oj 字段看起来像 vjudge 或包含 vjudge 标识时尝试映射
     let oj_lower = sub.oj.to_lowercase();
     if !oj_lower.contains("vjudge") && !oj_lower.contains("virtual") {
         return None;
     }
 
-    // pid 可能像 "UESTC-126" 或包含原始链接信息
-    let pid = sub.pid.trim();
-    // 常见情况: PID 形如 "OJNAME-123" 或 "ojname/problem/123" 等
+    split_remote_pid(&sub.pid).map(|(label, pid)| {
+        let (oj, pid) = map(&label, &pid);
+        RemoteInfo {
+            oj,
+            pid,
+            rid: sub.rid.clone(),
+            url: None,
+        }
+    })
+}
+
+/// 拆分 VJudge 形如 "UESTC-126" 或 "ojname/123" 的题目 ID 为 (oj, pid)
+pub fn split_remote_pid(pid: &str) -> Option<(String, String)> {
+    let pid = pid.trim();
     // 先尝试分解 PID 中的 "-" 分割 (如 UESTC-126)
     if let Some(idx) = pid.find('-') {
-        let oj = pid[..idx].to_string();
-        let pid_only = pid[idx + 1..].to_string();
-        // rid 有时包含在 sub.rid, 或者 remote run id
-        let rid = if !sub.rid.is_empty() {
-            sub.rid.clone()
-        } else {
-            String::new()
-        };
-        return Some((oj, pid_only, rid));
-    }
-
-    // 备选: pid 本身可能就是原题目的 id (例如 UESTC-126 中的完整形式)
-    if !pid.is_empty() {
-        // 试图从 pid 中提取 OJ 前缀 (以非数字分隔)
-        let parts: Vec<&str> = pid.split(&['/', '_', ':'][..]).collect();
-        if parts.len() >= 2 {
-            let oj = parts[0].to_string();
-            let pid_only = parts[1].to_string();
-            let rid = sub.rid.clone();
-            return Some((oj, pid_only, rid));
-        }
+        return Some((pid[..idx].to_string(), pid[idx + 1..].to_string()));
+    }
+
+    // 备选: 以其他分隔符拆分
+    let parts: Vec<&str> = pid.split(&['/', '_', ':'][..]).collect();
+    if parts.len() >= 2 {
+        return Some((parts[0].to_string(), parts[1].to_string()));
     }
 
     None
@@ -115,6 +773,21 @@ mod tests {
         assert_eq!(parse_time_to_ms("100ms"), Some(100));
         assert_eq!(parse_time_to_ms("0.2s"), Some(200));
         assert_eq!(parse_time_to_ms("  50  "), Some(50));
+        assert_eq!(parse_time_to_ms("1min 20s"), Some(80_000));
+        assert_eq!(parse_time_to_ms("1.2 s"), Some(1200));
+        assert_eq!(parse_time_to_ms("1,23s"), Some(1230));
+        assert_eq!(parse_time_to_ms("12ms / 1000ms"), Some(12));
+        assert_eq!(parse_time_to_ms("—"), None);
+        assert_eq!(parse_time_to_ms("-"), None);
+        assert_eq!(parse_time_to_ms("12 parsecs"), None);
+    }
+
+    #[test]
+    fn test_parse_num() {
+        assert_eq!(parse_num::<u16>(" 100 ", "score").ok(), Some(100));
+        let err = parse_num::<u16>("1e9", "score").unwrap_err();
+        assert_eq!(err.code(), "convert");
+        assert!(err.to_string().contains("score: `1e9`"));
     }
 
     #[test]
@@ -122,5 +795,209 @@ mod tests {
         assert_eq!(parse_mem_to_kb("1MB"), Some(1024));
         assert_eq!(parse_mem_to_kb("512K"), Some(512));
         assert_eq!(parse_mem_to_kb("256"), Some(256));
+        assert_eq!(parse_mem_to_kb("2 GiB"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_mem_to_kb("1.5gb"), Some(1536 * 1024));
+        assert_eq!(parse_mem_to_kb("64 MiB"), Some(65536));
+        assert_eq!(parse_mem_to_kb("2048 B"), Some(2));
+        assert_eq!(parse_mem_to_kb("10752kB"), Some(10752));
+        assert_eq!(parse_mem_to_kb("12MB foo"), None);
+        assert_eq!(parse_mem_to_kb("12 parsecs"), None);
+    }
+
+    #[test]
+    fn test_normalize_code() {
+        let code = "\u{feff}#include <cstdio>\r\nint\u{a0}main()\u{200b} {}\r";
+        assert_eq!(normalize_code(code), "#include <cstdio>\nint main() {}\n");
+
+        // 代码中本来就有的实体文本不被改写
+        let code = r#"puts("&amp; &lt;br&gt; &#39;");"#;
+        assert_eq!(normalize_code(code), code);
+
+        assert_eq!(
+            decode_entities("&lt;cstdio&gt;&#x41;&#66;&unknown;"),
+            "<cstdio>AB&unknown;"
+        );
+    }
+
+    #[test]
+    fn test_strip_line_numbers() {
+        let code = "1 #include <cstdio>\n2 int main() {\n3     return 0;\n4 }\n";
+        assert_eq!(
+            strip_line_numbers(code),
+            "#include <cstdio>\nint main() {\n    return 0;\n}\n"
+        );
+        assert_eq!(strip_line_numbers("1|a\n2|b"), "a\nb");
+
+        // 行号不连续时不处理
+        let code = "1 2 3\n5 6 7\n";
+        assert_eq!(strip_line_numbers(code), code);
+        let code = "int a;\nint b;";
+        assert_eq!(strip_line_numbers(code), code);
+    }
+
+    #[test]
+    fn test_code_fingerprint() {
+        let a = "int main() { // entry\n    puts(\"// not a comment\"); /* done */ }\n";
+        let b = "int main() {\r\n  puts(\"// not a comment\");\r\n}";
+        assert_eq!(code_fingerprint(a), code_fingerprint(b));
+
+        let c = "int main() { puts(\"\"); }";
+        assert_ne!(code_fingerprint(a), code_fingerprint(c));
+    }
+
+    #[test]
+    fn test_canonicalize_url() {
+        assert_eq!(
+            canonicalize_url("http://www.luogu.com.cn/record/123/?utm_source=x&spm=1#top"),
+            "https://luogu.com.cn/record/123"
+        );
+        assert_eq!(
+            canonicalize_url("https://m.vjudge.net/solution/65377961?lang=en"),
+            "https://vjudge.net/solution/65377961?lang=en"
+        );
+        assert_eq!(
+            canonicalize_url("https://vjudge.net/"),
+            "https://vjudge.net"
+        );
+        assert_eq!(canonicalize_url(" not a url "), "not a url");
+    }
+
+    #[test]
+    fn test_parse_relative_time() {
+        // 2025-01-02 12:00:00 +08:00
+        let now = 1_735_790_400_000;
+        assert_eq!(
+            parse_relative_time("3 分钟前", Some(now), 480),
+            Some(now - 180_000)
+        );
+        assert_eq!(
+            parse_relative_time("2 days ago", Some(now), 480),
+            Some(now - 2 * 86_400_000)
+        );
+        assert_eq!(
+            parse_relative_time("an hour ago", Some(now), 480),
+            Some(now - 3_600_000)
+        );
+        assert_eq!(parse_relative_time("刚刚", Some(now), 480), Some(now));
+        assert_eq!(
+            parse_relative_time("昨天 14:20", Some(now), 480),
+            parse_relative_time("2025-01-01 14:20", None, 480)
+        );
+        assert_eq!(
+            parse_relative_time("2025-01-02 12:00:00", None, 480),
+            Some(now)
+        );
+        assert_eq!(parse_relative_time("3 分钟前", None, 480), None);
+        assert_eq!(parse_relative_time("sometime", Some(now), 480), None);
+    }
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(parse_score("80%"), Some(80));
+        assert_eq!(parse_score("AC 8/10"), Some(80));
+        assert_eq!(parse_score("100 分"), Some(100));
+        assert_eq!(parse_score("Score: 37.5"), Some(38));
+        assert_eq!(parse_score("150"), None);
+        assert_eq!(parse_score("1/0"), None);
+        assert_eq!(parse_score("Accepted"), None);
+    }
+
+    #[test]
+    fn test_kv_table() -> Result<()> {
+        let document = Html::parse_fragment(
+            r#"<table id="info"><tr><th>语言:</th><td> C++17 </td></tr><tr><th>Memory</th><td>1MB</td></tr><tr><th>Judge</th><td>x</td></tr></table>"#,
+        );
+        let table = kv_table(&document, "#info")?;
+        assert_eq!(table["language"], "C++17");
+        assert_eq!(table["memory"], "1MB");
+        assert_eq!(table["judge"], "x");
+        Ok(())
+    }
+
+    #[test]
+    fn test_select() -> Result<()> {
+        let document = Html::parse_fragment(r#"<p class="a" title=" t "> text </p>"#);
+        assert_eq!(select_text(&document, "p.a")?.as_deref(), Some("text"));
+        assert_eq!(select_attr(&document, "p", "title")?.as_deref(), Some("t"));
+        assert_eq!(select_text(&document, "div")?, None);
+
+        let err = select_text(&document, "p[").unwrap_err();
+        assert_eq!(err.code(), "selector_parse");
+        assert!(err.to_string().contains("`p[`"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_ids() {
+        assert_eq!(normalize_pid("luogu", "p1001"), "P1001");
+        assert_eq!(normalize_pid("luogu", "1001"), "P1001");
+        assert_eq!(normalize_pid("luogu", "b2001"), "B2001");
+        assert_eq!(normalize_pid("codeforces", "1234/a"), "1234A");
+        assert_eq!(normalize_pid("codeforces", "CF1234 B1"), "1234B1");
+        assert_eq!(normalize_pid("codeforces", "gym/104012b"), "gym/104012B");
+        assert_eq!(normalize_pid("atcoder", "ABC300_A"), "abc300_a");
+        assert_eq!(normalize_pid("xyd", " 23051 "), "23051");
+
+        assert_eq!(normalize_rid(" #000123"), "123");
+        assert_eq!(normalize_rid("000"), "0");
+        assert_eq!(normalize_rid("5f3a0b"), "5f3a0b");
+    }
+
+    #[test]
+    fn test_find_field_by_label() -> Result<()> {
+        let document = Html::parse_fragment(
+            r#"<div class="rows"><div><span>Status:</span><span>Wrong Answer</span></div></div>"#,
+        );
+        assert_eq!(
+            find_field_by_label(&document, ".rows div", &["评测状态", "Status"])?.as_deref(),
+            Some("Wrong Answer")
+        );
+        assert_eq!(
+            find_field_by_label(&document, ".rows div", &["评测分数"])?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_vjudge() {
+        let sub = Submission {
+            oj: "VJudge".to_string(),
+            pid: "HDU-1000".to_string(),
+            rid: "42".to_string(),
+            ..Default::default()
+        };
+        let remote = map_vjudge_to_origin(&sub).unwrap();
+        assert_eq!((remote.oj.as_str(), remote.pid.as_str()), ("hdu", "1000"));
+        assert_eq!(remote.rid, "42");
+
+        let luogu = Submission {
+            oj: "luogu".to_string(),
+            pid: "P1001".to_string(),
+            ..Default::default()
+        };
+        assert!(map_vjudge_to_origin(&luogu).is_none());
+
+        let sub = Submission {
+            pid: "UESTC-126".to_string(),
+            ..sub
+        };
+        let mut map = OjMap::default();
+        map.override_with(
+            OjMap::from_json(
+                r#"[{"labels": ["UESTC"], "oj": "uestc", "pid_pattern": "^(\\d+)$", "pid_replace": "U$1"}]"#,
+            )
+            .unwrap(),
+        );
+        let remote = map_vjudge_to_origin_with(&sub, &map).unwrap();
+        assert_eq!((remote.oj.as_str(), remote.pid.as_str()), ("uestc", "U126"));
+    }
+
+    #[test]
+    fn test_parse_saturate() {
+        assert_eq!(parse_time_to_ms("-5ms"), Some(0));
+        assert_eq!(parse_mem_to_kb("-1MB"), Some(0));
+        assert_eq!(parse_mem_to_kb("1e30MB"), Some(u64::MAX));
+        assert_eq!(parse_mem_to_kb("3000000000"), Some(3_000_000_000));
     }
 }
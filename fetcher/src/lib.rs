@@ -0,0 +1,321 @@
+//! 原生页面获取
+//!
+//! 以保存的 cookie 下载提交记录页面, 处理重定向, 压缩与字符集后交给提取器,
+//! 供 CLI 等非浏览器环境使用.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+mod rate_limit;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use extractor::error::ExtractErrorKind;
+use extractor::models::credential::parse_cookie_str;
+use extractor::models::{Credential, ExtractionReport};
+use extractor::{BoxFuture, ValidationPolicy, decode};
+use reqwest::cookie::Jar;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::redirect::Policy;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+pub use rate_limit::RateLimiter;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// 请求使用的 User-Agent
+const USER_AGENT: &str = concat!("rsubmitter/", env!("CARGO_PKG_VERSION"));
+
+/// 默认请求超时 (秒)
+pub const DEFAULT_TIMEOUT_SECS: u64 = 15;
+
+/// 最多跟随的重定向次数
+const MAX_REDIRECTS: usize = 10;
+
+/// 获取错误
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("invalid url: {0}")]
+    InvalidUrl(String),
+    #[error("request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("unexpected status {status} from {url}")]
+    Status { url: String, status: u16 },
+    #[error(transparent)]
+    Extract(#[from] extractor::error::Error),
+}
+
+impl Error {
+    /// 稳定的字符串错误码, 提取错误沿用提取器的错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::InvalidUrl(_) => "invalid_url",
+            Error::Request(e) if e.is_timeout() => "timeout",
+            Error::Request(_) => "request",
+            Error::Status { .. } => "status",
+            Error::Extract(e) => e.code(),
+        }
+    }
+}
+
+/// 客户端配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FetcherConfig {
+    /// 请求超时 (秒)
+    pub timeout_secs: u64,
+    /// 同一主机相邻请求的最小间隔 (毫秒), 0 为不限制
+    pub min_interval_ms: u64,
+    /// 替换默认的 User-Agent
+    pub user_agent: Option<String>,
+}
+
+impl Default for FetcherConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            min_interval_ms: 0,
+            user_agent: None,
+        }
+    }
+}
+
+/// 下载得到的页面
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedPage {
+    /// 跟随重定向后的最终地址
+    pub url: String,
+    pub status: u16,
+    /// 解压并按字符集解码后的文本
+    pub content: String,
+}
+
+/// 带 cookie jar 的 http 客户端
+///
+/// 克隆的客户端共享连接池与 cookie jar.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    jar: Arc<Jar>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl Client {
+    /// 使用默认配置创建
+    pub fn new() -> Result<Self> {
+        Self::from_config(&FetcherConfig::default())
+    }
+
+    /// 按配置创建
+    pub fn from_config(config: &FetcherConfig) -> Result<Self> {
+        let jar = Arc::new(Jar::default());
+        let http = reqwest::Client::builder()
+            .cookie_provider(jar.clone())
+            .redirect(Policy::limited(MAX_REDIRECTS))
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(config.user_agent.as_deref().unwrap_or(USER_AGENT))
+            .build()?;
+        Ok(Self {
+            http,
+            jar,
+            limiter: Arc::new(RateLimiter::new(Duration::from_millis(
+                config.min_interval_ms,
+            ))),
+        })
+    }
+
+    /// 为 `origin` 添加 `document.cookie` 形式的 cookie
+    pub fn add_cookies(&self, origin: &str, cookie_str: &str) -> Result<()> {
+        let url = parse_url(origin)?;
+        for (key, value) in parse_cookie_str(cookie_str) {
+            self.jar
+                .add_cookie_str(&format!("{key}={value}; Path=/"), &url);
+        }
+        Ok(())
+    }
+
+    /// 添加站点登录凭据, cookie 对站点的全部子域名生效
+    pub fn add_credential(&self, credential: &Credential) {
+        let Some(domain) = credential
+            .schema()
+            .hosts
+            .iter()
+            .find(|host| !host.starts_with('*'))
+        else {
+            return;
+        };
+        let url = Url::parse(&format!("http://{domain}/")).expect("cookie schema hosts are valid");
+        for (key, value) in credential.cookies() {
+            self.jar
+                .add_cookie_str(&format!("{key}={value}; Domain={domain}; Path=/"), &url);
+        }
+    }
+
+    /// 下载页面, 非 2xx 状态报错, 同一主机的请求按配置限速
+    pub async fn fetch_page(&self, url: &str) -> Result<FetchedPage> {
+        let parsed = parse_url(url)?;
+        self.limiter
+            .wait(parsed.host_str().unwrap_or_default())
+            .await;
+        let response = self.http.get(parsed).send().await?;
+        let status = response.status().as_u16();
+        let final_url = response.url().to_string();
+        if !response.status().is_success() {
+            return Err(Error::Status {
+                url: final_url,
+                status,
+            });
+        }
+
+        // 压缩已由 reqwest 处理, 此处只需确定字符集
+        let charset = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(charset_of)
+            .map(str::to_string);
+        let bytes = response.bytes().await?;
+        tracing::debug!(url, final_url, len = bytes.len(), ?charset, "fetched page");
+
+        Ok(FetchedPage {
+            url: final_url,
+            status,
+            content: decode::decode_text(&bytes, charset.as_deref()),
+        })
+    }
+
+    /// 下载并提取提交记录, 提取器可经本客户端获取补充资源
+    ///
+    /// 以请求的 URL 选择提取器, 重定向到登录页时由提取器报告具体原因.
+    pub async fn extract(&self, url: &str, policy: &ValidationPolicy) -> Result<ExtractionReport> {
+        let page = self.fetch_page(url).await?;
+        Ok(extractor::extract_async(url, &page.content, policy, self).await?)
+    }
+}
+
+impl extractor::Fetcher for Client {
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, extractor::error::Result<String>> {
+        Box::pin(async move {
+            self.fetch_page(url)
+                .await
+                .map(|page| page.content)
+                .map_err(|e| {
+                    let kind = match e {
+                        Error::Extract(e) => return e,
+                        Error::InvalidUrl(url) => ExtractErrorKind::InvalidUrl(url),
+                        Error::Status { status: 401, url } => {
+                            ExtractErrorKind::NotAuthenticated(url)
+                        }
+                        Error::Status { status: 403, url } => {
+                            ExtractErrorKind::PermissionDenied(url)
+                        }
+                        e => ExtractErrorKind::Other(format!("fetch {url}: {e}")),
+                    };
+                    kind.into()
+                })
+        })
+    }
+}
+
+fn parse_url(url: &str) -> Result<Url> {
+    Url::parse(url.trim()).map_err(|e| Error::InvalidUrl(format!("{url}: {e}")))
+}
+
+/// `Content-Type` 中声明的字符集
+fn charset_of(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+            .filter(|charset| !charset.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 依次以给定响应应答请求, 返回服务地址与收到的请求头
+    async fn serve(responses: Vec<Vec<u8>>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut head = Vec::new();
+                let mut buf = [0; 1024];
+                while !head.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    head.extend_from_slice(&buf[..n]);
+                }
+                requests.push(String::from_utf8(head).unwrap());
+                stream.write_all(&response).await.unwrap();
+            }
+            requests
+        });
+        (addr, handle)
+    }
+
+    fn response(status: &str, headers: &str, body: &[u8]) -> Vec<u8> {
+        let mut out = format!(
+            "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        out.extend_from_slice(body);
+        out
+    }
+
+    #[test]
+    fn test_charset_of() {
+        assert_eq!(charset_of("text/html; charset=GBK"), Some("GBK"));
+        assert_eq!(charset_of(r#"text/html;Charset="utf-8""#), Some("utf-8"));
+        assert_eq!(charset_of("text/html"), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page() {
+        // "通过" 的 GBK 编码
+        let body = [b"<p>".as_slice(), &[0xcd, 0xa8, 0xb9, 0xfd], b"</p>"].concat();
+        let (addr, server) = serve(vec![
+            response("302 Found", "Location: /record/1\r\n", b""),
+            response("200 OK", "Content-Type: text/html; charset=gbk\r\n", &body),
+        ])
+        .await;
+
+        let client = Client::new().unwrap();
+        client
+            .add_cookies(&addr, "_uid=42; __client_id=abc")
+            .unwrap();
+        let page = client.fetch_page(&format!("{addr}/r/1")).await.unwrap();
+        assert_eq!(page.url, format!("{addr}/record/1"));
+        assert_eq!(page.content, "<p>通过</p>");
+
+        let requests = server.await.unwrap();
+        assert!(requests[1].starts_with("GET /record/1 "));
+        assert!(requests.iter().all(|r| r.contains("_uid=42")));
+    }
+
+    #[tokio::test]
+    async fn test_status_error() {
+        let (addr, _server) = serve(vec![response("401 Unauthorized", "", b"")]).await;
+        let client = Client::new().unwrap();
+        let err = client.fetch_page(&addr).await.unwrap_err();
+        assert!(matches!(err, Error::Status { status: 401, .. }));
+        assert_eq!(
+            client.fetch_page("not a url").await.unwrap_err().code(),
+            "invalid_url"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! 按主机限制请求频率
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 同一主机的相邻请求至少间隔 `min_interval`
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    min_interval: Duration,
+    /// 主机 -> 下一个可用的发送时刻
+    next: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            next: Mutex::default(),
+        }
+    }
+
+    /// 预约发送时刻, 返回需要等待的时长
+    ///
+    /// 并发请求依次预约, 不会在同一时刻一起放行.
+    fn reserve(&self, host: &str, now: Instant) -> Duration {
+        if self.min_interval.is_zero() {
+            return Duration::ZERO;
+        }
+        let mut next = self.next.lock().unwrap();
+        let slot = next.get(host).map_or(now, |&at| at.max(now));
+        next.insert(host.to_string(), slot + self.min_interval);
+        slot - now
+    }
+
+    /// 等待到可以向 `host` 发送请求
+    pub async fn wait(&self, host: &str) {
+        let delay = self.reserve(host, Instant::now());
+        if !delay.is_zero() {
+            tracing::debug!(host, ?delay, "rate limited");
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve() {
+        let limiter = RateLimiter::new(Duration::from_millis(500));
+        let now = Instant::now();
+        assert_eq!(limiter.reserve("a", now), Duration::ZERO);
+        assert_eq!(limiter.reserve("a", now), Duration::from_millis(500));
+        assert_eq!(limiter.reserve("a", now), Duration::from_millis(1000));
+        assert_eq!(limiter.reserve("b", now), Duration::ZERO);
+        let later = now + Duration::from_secs(5);
+        assert_eq!(limiter.reserve("a", later), Duration::ZERO);
+
+        let unlimited = RateLimiter::default();
+        assert_eq!(unlimited.reserve("a", now), Duration::ZERO);
+        assert_eq!(unlimited.reserve("a", now), Duration::ZERO);
+    }
+}
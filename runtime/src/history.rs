@@ -0,0 +1,141 @@
+//! 同步历史 - IndexedDB 存储
+//!
+//! 以 `(oj, rid)` 为键记录已同步的提交, 重复同步时覆盖旧记录.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use extractor::models::Submission;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use tsify::Tsify;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbFactory, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+use crate::i18n::Msg;
+
+/// 数据库名称
+const DB_NAME: &str = "rsubmitter";
+
+/// 数据库版本, 修改存储结构时递增
+const DB_VERSION: u32 = 1;
+
+/// 历史记录的存储名称
+const STORE_NAME: &str = "history";
+
+thread_local! {
+    static DB: RefCell<Option<IdbDatabase>> = const { RefCell::new(None) };
+}
+
+/// 单条同步记录
+#[derive(Debug, Clone, Serialize, Deserialize, Tsify)]
+pub struct HistoryEntry {
+    pub oj: String,
+    pub rid: String,
+    pub pid: String,
+    pub code_hash: String,
+    /// 7fa4 新建记录的编号
+    pub record_id: Option<String>,
+    /// 同步时间 (Unix 毫秒)
+    pub synced_at: u64,
+}
+
+impl HistoryEntry {
+    pub fn new(sub: &Submission, record_id: Option<String>) -> Self {
+        Self {
+            oj: sub.oj.clone(),
+            rid: sub.rid.clone(),
+            pid: sub.pid.clone(),
+            code_hash: sub.code_hash.clone(),
+            record_id,
+            synced_at: js_sys::Date::now() as u64,
+        }
+    }
+}
+
+/// 等待 IndexedDB 请求完成, 返回请求结果
+async fn wait(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    JsFuture::from(promise).await?;
+    request.result()
+}
+
+/// 打开 (必要时创建) 数据库, 连接在模块内复用
+async fn open() -> Result<IdbDatabase, JsValue> {
+    if let Some(db) = DB.with(|db| db.borrow().clone()) {
+        return Ok(db);
+    }
+
+    // 页面与 service worker 的全局对象均提供 `indexedDB`
+    let factory: IdbFactory = js_sys::Reflect::get(&js_sys::global(), &"indexedDB".into())?
+        .dyn_into()
+        .map_err(|_| JsValue::from_str(Msg::IndexedDbUnsupported.text()))?;
+    let request = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+
+    let upgrade_request = request.clone();
+    let on_upgrade = Closure::once_into_js(move |_: web_sys::Event| {
+        let Ok(db) = upgrade_request.result() else {
+            return;
+        };
+        let db: IdbDatabase = db.unchecked_into();
+        if !db.object_store_names().contains(STORE_NAME) {
+            let params = web_sys::IdbObjectStoreParameters::new();
+            params.set_key_path(&js_sys::Array::of2(&"oj".into(), &"rid".into()));
+            let _ = db.create_object_store_with_optional_parameters(STORE_NAME, &params);
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+    let db: IdbDatabase = wait(&request).await?.unchecked_into();
+    DB.with(|cached| *cached.borrow_mut() = Some(db.clone()));
+    Ok(db)
+}
+
+/// 打开历史记录的存储
+async fn store(mode: IdbTransactionMode) -> Result<IdbObjectStore, JsValue> {
+    open()
+        .await?
+        .transaction_with_str_and_mode(STORE_NAME, mode)?
+        .object_store(STORE_NAME)
+}
+
+/// 写入一条记录, 相同 `(oj, rid)` 的旧记录被覆盖
+pub async fn record(entry: &HistoryEntry) -> Result<(), JsValue> {
+    let value = serde_wasm_bindgen::to_value(entry)?;
+    let store = store(IdbTransactionMode::Readwrite).await?;
+    wait(&store.put(&value)?).await.map(|_| ())
+}
+
+/// 全部记录
+pub async fn list() -> Result<Vec<HistoryEntry>, JsValue> {
+    let store = store(IdbTransactionMode::Readonly).await?;
+    let entries = wait(&store.get_all()?).await?;
+    Ok(serde_wasm_bindgen::from_value(entries)?)
+}
+
+/// 查找 `(oj, rid)` 对应的记录
+pub async fn get(oj: &str, rid: &str) -> Result<Option<HistoryEntry>, JsValue> {
+    let store = store(IdbTransactionMode::Readonly).await?;
+    let key = js_sys::Array::of2(&oj.into(), &rid.into());
+    let entry = wait(&store.get(&key)?).await?;
+    if entry.is_undefined() {
+        return Ok(None);
+    }
+    Ok(Some(serde_wasm_bindgen::from_value(entry)?))
+}
+
+/// 清空全部记录
+pub async fn clear() -> Result<(), JsValue> {
+    let store = store(IdbTransactionMode::Readwrite).await?;
+    wait(&store.clear()?).await.map(|_| ())
+}
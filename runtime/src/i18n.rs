@@ -0,0 +1,241 @@
+//! 运行时消息的本地化
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::cell::Cell;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use extractor::error::{Error, ExtractErrorKind};
+
+thread_local! {
+    static LOCALE: Cell<Locale> = const { Cell::new(Locale::ZhCn) };
+}
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    ZhCn,
+    En,
+}
+
+impl FromStr for Locale {
+    type Err = ();
+
+    /// 接受 BCP 47 标签, 仅比较主语言子标签
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.trim().split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "zh" => Ok(Locale::ZhCn),
+            "en" => Ok(Locale::En),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::En => "en",
+        }
+    }
+}
+
+/// 当前语言
+pub fn locale() -> Locale {
+    LOCALE.with(Cell::get)
+}
+
+/// 设置当前语言
+pub fn set_locale(locale: Locale) {
+    LOCALE.with(|l| l.set(locale));
+}
+
+/// 运行时消息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    Serialize,
+    Deserialize,
+    CreateExtractor,
+    NoExtractor,
+    InvalidSubmission,
+    InvalidCookieInfo,
+    UnknownLogLevel,
+    UnknownLocale,
+    LoggingNotInitialized,
+    SetLogLevel,
+    Overrides,
+    OjMap,
+    Deployments,
+    #[cfg(feature = "history")]
+    History,
+    #[cfg(feature = "history")]
+    IndexedDbUnsupported,
+    BuildRequest,
+    RequestTimeout,
+    RequestFailed,
+}
+
+impl Msg {
+    /// 当前语言下的文本
+    pub fn text(self) -> &'static str {
+        match locale() {
+            Locale::ZhCn => self.zh_cn(),
+            Locale::En => self.en(),
+        }
+    }
+
+    fn zh_cn(self) -> &'static str {
+        match self {
+            Msg::Serialize => "序列化错误",
+            Msg::Deserialize => "反序列化错误",
+            Msg::CreateExtractor => "创建提取器失败",
+            Msg::NoExtractor => "没有找到适用于 URL 的提取器",
+            Msg::InvalidSubmission => "无效的 Submission",
+            Msg::InvalidCookieInfo => "无效的 CookieInfo",
+            Msg::UnknownLogLevel => "未知的日志级别",
+            Msg::UnknownLocale => "未知的语言",
+            Msg::LoggingNotInitialized => "日志尚未初始化",
+            Msg::SetLogLevel => "设置日志级别失败",
+            Msg::Overrides => "覆盖配置错误",
+            Msg::OjMap => "映射表错误",
+            Msg::Deployments => "部署配置错误",
+            #[cfg(feature = "history")]
+            Msg::History => "历史记录错误",
+            #[cfg(feature = "history")]
+            Msg::IndexedDbUnsupported => "当前环境不支持 IndexedDB",
+            Msg::BuildRequest => "构建请求失败",
+            Msg::RequestTimeout => "请求超时",
+            Msg::RequestFailed => "请求失败",
+        }
+    }
+
+    fn en(self) -> &'static str {
+        match self {
+            Msg::Serialize => "serialization error",
+            Msg::Deserialize => "deserialization error",
+            Msg::CreateExtractor => "failed to create extractor",
+            Msg::NoExtractor => "no extractor found for url",
+            Msg::InvalidSubmission => "invalid Submission",
+            Msg::InvalidCookieInfo => "invalid CookieInfo",
+            Msg::UnknownLogLevel => "unknown log level",
+            Msg::UnknownLocale => "unknown locale",
+            Msg::LoggingNotInitialized => "logging is not initialized",
+            Msg::SetLogLevel => "failed to set log level",
+            Msg::Overrides => "invalid overrides",
+            Msg::OjMap => "invalid oj map",
+            Msg::Deployments => "invalid deployments",
+            #[cfg(feature = "history")]
+            Msg::History => "history error",
+            #[cfg(feature = "history")]
+            Msg::IndexedDbUnsupported => "IndexedDB is not available",
+            Msg::BuildRequest => "failed to build request",
+            Msg::RequestTimeout => "request timed out",
+            Msg::RequestFailed => "request failed",
+        }
+    }
+
+    /// 附带详细信息的文本, 形如 `消息: 详情`
+    pub fn with(self, detail: impl Display) -> String {
+        format!("{}: {detail}", self.text())
+    }
+}
+
+/// 提取错误的本地化文本, 形如 `错误类型: 详情`
+///
+/// 按 [`ExtractErrorKind::code`] 查表, 英文沿用错误类型自身的描述.
+pub fn extract_error(kind: &ExtractErrorKind) -> String {
+    if locale() == Locale::En {
+        return kind.to_string();
+    }
+    match kind {
+        ExtractErrorKind::JudgingInProgress { retry_after } => {
+            format!("正在评测, 请 {} 秒后重试", retry_after.as_secs())
+        }
+        ExtractErrorKind::LayoutMismatch { detected, expected } => format!(
+            "页面布局不符: 识别为 {}, 应为 {}",
+            detected.as_deref().unwrap_or("未知"),
+            expected.join(" / ")
+        ),
+        _ => {
+            let title = match kind.code() {
+                "no_extractor" => Msg::NoExtractor.text(),
+                "parse" => "解析错误",
+                "convert" => "转换错误",
+                "missing_field" => "缺少字段",
+                "validation" => "校验失败",
+                "regex_mismatch" => "正则不匹配",
+                "selector_parse" => "选择器解析错误",
+                "time_parse" => "时间解析错误",
+                "memory_parse" => "内存解析错误",
+                "language_parse" => "语言解析错误",
+                "status_parse" => "状态解析错误",
+                "invalid_url" => "无效的 URL",
+                "empty_content" => "页面内容为空",
+                "not_in_submission_page" => "不在提交记录页面",
+                "no_submission_selected" => "未选择提交记录",
+                "not_authenticated" => "未登录",
+                "permission_denied" => "没有权限",
+                "internal" => "内部错误",
+                "unsupported_input" => "不支持的输入",
+                _ => "其他错误",
+            };
+            match kind.detail() {
+                Some(detail) => format!("{title}: {detail}"),
+                None => title.to_string(),
+            }
+        }
+    }
+}
+
+/// 通用错误的本地化文本
+pub fn error(e: &Error) -> String {
+    match e {
+        Error::NoExtractor { url, .. } => Msg::NoExtractor.with(url),
+        Error::Extract(ee) => extract_error(&ee.kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale() {
+        assert_eq!("en-US".parse(), Ok(Locale::En));
+        assert_eq!("zh_Hans".parse(), Ok(Locale::ZhCn));
+        assert!("fr".parse::<Locale>().is_err());
+
+        assert_eq!(Msg::Deserialize.with("x"), "反序列化错误: x");
+        set_locale(Locale::En);
+        assert_eq!(Msg::Deserialize.with("x"), "deserialization error: x");
+        set_locale(Locale::ZhCn);
+    }
+
+    #[test]
+    fn test_extract_error() {
+        let kind = ExtractErrorKind::MissingField("code".to_string());
+        assert_eq!(extract_error(&kind), "缺少字段: code");
+        assert_eq!(
+            extract_error(&ExtractErrorKind::EmptyContent),
+            "页面内容为空"
+        );
+        let e = Error::from(kind);
+        assert_eq!(
+            Msg::CreateExtractor.with(error(&e)),
+            "创建提取器失败: 缺少字段: code"
+        );
+
+        set_locale(Locale::En);
+        assert_eq!(error(&e), "missing field: code");
+        set_locale(Locale::ZhCn);
+    }
+}
@@ -8,145 +8,824 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+#[cfg(feature = "history")]
+mod history;
+mod i18n;
+
 use extractor::error;
-use extractor::models::Submission;
+use extractor::models::{
+    CookieInfo, Credential, Document, ExtractWarning, ExtractionContext, ExtractorInput, InputKind,
+    LoginStatus, OjMap, PartialSubmission, PreparedSubmission, RemoteInfo, Submission,
+    SubmissionLanguage, SubmissionStatus, SubmitResponse, current_oj_map, whoami_url,
+};
 use extractor::utils;
+use extractor::{ValidationPolicy, decode};
+use i18n::{Locale, Msg};
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{Registry, reload};
+use tsify::Tsify;
+use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+/// 默认日志级别, 仅输出警告与错误
+const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::WARN;
+
+/// 可在运行时调整的日志级别
+static LOG_FILTER: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+/// 运行时 ABI 标识, 由对外类型的声明计算
+static ABI: OnceLock<String> = OnceLock::new();
+
+/// 请求 7fa4 的超时时间
+const REQUEST_TIMEOUT_MS: u32 = 8000;
+
+thread_local! {
+    static OBSERVER: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+}
+
+/// 由 JS 直接 `fetch` 的 7fa4 提交请求
+#[derive(Debug, Serialize, Tsify)]
+pub struct SubmitRequest {
+    pub url: String,
+    pub method: String,
+    pub headers: BTreeMap<String, String>,
+    /// JSON 请求体
+    pub body: String,
+}
 
-/// 解析后的 Cookie 信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CookieInfo {
-    pub login: Option<String>,
-    #[serde(rename = "connect.sid")]
-    pub connect_sid: Option<String>,
-    pub chost: Option<String>,
+#[wasm_bindgen]
+extern "C" {
+    /// 全局 `fetch`, 同时适用于页面与 service worker
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_request(request: &web_sys::Request) -> js_sys::Promise;
+
+    /// 全局 `setTimeout`
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(callback: &js_sys::Function, delay: i32) -> JsValue;
 }
 
 /// 提取操作的输出结果
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ExtractOutput {
     pub success: bool,
     pub error: Option<String>,
-    pub partial: Option<Submission>,
+    pub error_code: Option<String>,
+    pub partial: Option<PartialSubmission>,
+    pub submission: Option<Submission>,
     pub extractor_name: Option<String>,
+    /// 页面中实际提取到的字段
+    pub fields_found: Vec<String>,
+    /// 非致命警告, 如取默认值的字段与无法识别的文本
+    pub warnings: Vec<ExtractWarning>,
 }
 
-/// 从 URL 和 HTML 内容中提取提交信息
-#[wasm_bindgen]
-pub fn extract_submission(url: &str, html: &str) -> JsValue {
-    // 先创建合适的提取器以获取其名称 (用于区分 vjudge) 
+/// 记录提交来源, wasm 下提取时间取自 JS 时钟
+fn with_provenance(sub: Submission, url: &str, name: &str) -> Submission {
+    let mut sub = sub.with_provenance(url, name);
+    if sub.extracted_at.is_none() {
+        sub.extracted_at = Some(js_sys::Date::now() as u64);
+    }
+    sub
+}
+
+/// 批量提取的单个输入
+#[derive(Debug, Deserialize, Tsify)]
+pub struct ExtractItem {
+    pub url: String,
+    pub html: String,
+}
+
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    serde_wasm_bindgen::to_value(value)
+        .unwrap_or_else(|e| JsValue::from_str(&Msg::Serialize.with(e)))
+}
+
+/// 序列化为 JSON 字符串, 失败时返回带错误信息的 `ExtractOutput` 形式
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|e| {
+        serde_json::json!({ "success": false, "error": Msg::Serialize.with(e) }).to_string()
+    })
+}
+
+/// 提取单个页面或接口响应
+fn extract_output(url: &str, input: ExtractorInput<'_>) -> ExtractOutput {
+    let url = &utils::canonicalize_url(url);
+    // 先创建合适的提取器以获取其名称 (用于区分 vjudge)
     match extractor::create_extractor(url) {
-        Ok((ext, name)) => match ext.extract(url, html) {
-            Ok(sub) => {
-                let out = ExtractOutput {
-                    success: true,
-                    error: None,
-                    partial: Some(sub),
-                    extractor_name: Some(name),
-                };
-                serde_wasm_bindgen::to_value(&out)
-                    .unwrap_or_else(|e| JsValue::from_str(&format!("序列化错误: {e}")))
+        Ok((ext, name)) => run_extractor(ext.as_ref(), name, url, &input),
+        Err(e) => no_extractor_output(e),
+    }
+}
+
+/// 提取上下文, wasm 下当前时间与时区取自 JS 时钟
+fn context(url: &str) -> ExtractionContext<'_> {
+    let ctx = ExtractionContext::new(url);
+    #[cfg(target_arch = "wasm32")]
+    let ctx = {
+        let date = js_sys::Date::new_0();
+        // `getTimezoneOffset` 为 UTC 减本地时间
+        ctx.with_now(date.get_time() as u64)
+            .with_utc_offset(-(date.get_timezone_offset() as i32))
+    };
+    ctx
+}
+
+/// 使用选定的提取器提取
+fn run_extractor(
+    ext: &dyn extractor::Extractor,
+    name: String,
+    url: &str,
+    input: &ExtractorInput<'_>,
+) -> ExtractOutput {
+    let ctx = context(url);
+    let result =
+        extractor::contain_panic(|| ext.extract_input(&ctx, input, &ValidationPolicy::default()));
+    match result {
+        Ok(report) => {
+            tracing::debug!(url, extractor = %name, "extracted submission");
+            let sub = with_provenance(report.submission, url, &name);
+            ExtractOutput {
+                success: true,
+                error: None,
+                error_code: None,
+                partial: Some(sub.clone().into()),
+                submission: Some(sub),
+                extractor_name: Some(name),
+                fields_found: report.fields_found,
+                warnings: report.warnings,
             }
-            Err(e) => match e {
-                error::Error::Extract(ee) => {
-                    let out = ExtractOutput {
-                        success: false,
-                        error: Some(format!("{ee}")),
-                        partial: ee.partial.map(|b| *b),
-                        extractor_name: Some(name),
-                    };
-                    serde_wasm_bindgen::to_value(&out)
-                        .unwrap_or_else(|e| JsValue::from_str(&format!("序列化错误: {e}")))
-                }
-                error::Error::NoExtractor(u) => {
-                    let out = ExtractOutput {
-                        success: false,
-                        error: Some(format!("没有找到适用于 URL 的提取器: {u}")),
-                        partial: None,
-                        extractor_name: None,
-                    };
-                    serde_wasm_bindgen::to_value(&out)
-                        .unwrap_or_else(|e| JsValue::from_str(&format!("序列化错误: {e}")))
-                }
-            },
-        },
-        Err(e) => {
-            // 不能创建提取器
-            let out = ExtractOutput {
+        }
+        Err(e) => failed_output(e, Some(name)),
+    }
+}
+
+/// 不能创建提取器时的输出
+fn no_extractor_output(e: error::Error) -> ExtractOutput {
+    ExtractOutput {
+        success: false,
+        error: Some(Msg::CreateExtractor.with(i18n::error(&e))),
+        error_code: Some(e.code().to_string()),
+        partial: None,
+        submission: None,
+        extractor_name: None,
+        fields_found: Vec::new(),
+        warnings: Vec::new(),
+    }
+}
+
+/// 让出事件循环, 使页面在长时间提取的间隙保持响应
+///
+/// 使用 `setTimeout` 而非微任务, 以便浏览器处理渲染与输入事件.
+async fn yield_now() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        set_timeout(&resolve, 0);
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+/// 提取失败时的输出
+fn failed_output(e: error::Error, name: Option<String>) -> ExtractOutput {
+    tracing::info!(extractor = ?name, code = e.code(), "extraction failed: {e}");
+    match e {
+        error::Error::Extract(ee) => {
+            let error = Some(i18n::extract_error(&ee.kind));
+            let error_code = Some(ee.kind.code().to_string());
+            let mut partial = ee.partial.map(|b| *b);
+            let fields_found = partial
+                .as_ref()
+                .map(|p| p.found_fields().into_iter().map(str::to_string).collect())
+                .unwrap_or_default();
+            let warnings = partial
+                .as_mut()
+                .map(|p| std::mem::take(&mut p.warnings))
+                .unwrap_or_default();
+            ExtractOutput {
                 success: false,
-                error: Some(format!("创建提取器失败: {e}")),
-                partial: None,
-                extractor_name: None,
-            };
-            serde_wasm_bindgen::to_value(&out)
-                .unwrap_or_else(|e| JsValue::from_str(&format!("序列化错误: {e}")))
+                error,
+                error_code,
+                partial,
+                submission: None,
+                extractor_name: name,
+                fields_found,
+                warnings,
+            }
         }
+        error::Error::NoExtractor { url, .. } => ExtractOutput {
+            success: false,
+            error: Some(Msg::NoExtractor.with(url)),
+            error_code: Some("no_extractor".to_string()),
+            partial: None,
+            submission: None,
+            extractor_name: None,
+            fields_found: Vec::new(),
+            warnings: Vec::new(),
+        },
     }
 }
 
-/// 将 VJudge 的提取结果映射为可能的原始 OJ (如果适用)
+/// 从 URL 和 HTML 内容中提取提交信息
+#[wasm_bindgen(unchecked_return_type = "ExtractOutput")]
+pub fn extract_submission(url: &str, html: &str) -> JsValue {
+    to_js(&extract_output(url, ExtractorInput::HtmlPage(html)))
+}
+
+/// 从 URL 和 HTML 内容中提取提交信息, 以 JSON 字符串返回 `ExtractOutput`
+///
+/// 代码较长时比构建 JS 对象更快, 也便于经 `postMessage` 传递.
+#[wasm_bindgen]
+pub fn extract_submission_json_out(url: &str, html: &str) -> String {
+    to_json(&extract_output(url, ExtractorInput::HtmlPage(html)))
+}
+
+/// 从未解码的响应体中提取提交信息
+///
+/// `content_encoding` 为 `Content-Encoding` 响应头, 缺省时自动识别 gzip;
+/// `charset` 为 `Content-Type` 中的字符集, 缺省时依据 BOM 与 `<meta>` 判断, 可处理 GBK 页面.
+#[wasm_bindgen(unchecked_return_type = "ExtractOutput")]
+pub fn extract_submission_bytes(
+    url: &str,
+    bytes: &[u8],
+    content_encoding: Option<String>,
+    charset: Option<String>,
+) -> JsValue {
+    let output = match decode::decode_body(bytes, content_encoding.as_deref(), charset.as_deref()) {
+        Ok(html) => extract_output(url, ExtractorInput::HtmlPage(&html)),
+        Err(e) => failed_output(e, None),
+    };
+    to_js(&output)
+}
+
+/// 从拦截到的接口响应中提取提交信息
+///
+/// 支持洛谷 `_contentOnly` 与 VJudge `/solution/data` 返回的 JSON, 比解析渲染后的页面更可靠.
+#[wasm_bindgen(unchecked_return_type = "ExtractOutput")]
+pub fn extract_submission_json(url: &str, json: &str) -> JsValue {
+    to_js(&extract_output(url, ExtractorInput::JsonApi(json)))
+}
+
+/// 从同一提交的多个页面中提取并合并为一条提交信息
+///
+/// 如洛谷记录页与单独加载的代码页; 靠前页面的字段优先, 无法解析的页面被跳过.
+#[wasm_bindgen(unchecked_return_type = "ExtractOutput")]
+pub fn extract_submission_multi(url: &str, htmls: Vec<String>) -> JsValue {
+    let docs = htmls
+        .iter()
+        .map(|html| Document {
+            url,
+            kind: InputKind::Html,
+            content: html,
+        })
+        .collect();
+    to_js(&extract_output(url, ExtractorInput::MultiDocument(docs)))
+}
+
+/// 批量提取, 传入 `{ url, html }` 数组, 按顺序返回各项的 `ExtractOutput`
+///
+/// 单项失败不影响其他项, 仅在输入无法解析时返回错误字符串.
+#[wasm_bindgen(unchecked_return_type = "ExtractOutput[]")]
+pub fn extract_submissions(
+    #[wasm_bindgen(unchecked_param_type = "ExtractItem[]")] items: JsValue,
+) -> JsValue {
+    let items: Vec<ExtractItem> = match serde_wasm_bindgen::from_value(items) {
+        Ok(items) => items,
+        Err(e) => return JsValue::from_str(&Msg::Deserialize.with(e)),
+    };
+    let outputs: Vec<ExtractOutput> = items
+        .iter()
+        .map(|item| extract_output(&item.url, ExtractorInput::HtmlPage(&item.html)))
+        .collect();
+    to_js(&outputs)
+}
+
+/// 异步批量提取, 每完成一项让出事件循环, 并以 `(done, total)` 调用可选的进度回调
+#[wasm_bindgen(unchecked_return_type = "ExtractOutput[]")]
+pub async fn extract_submissions_async(
+    #[wasm_bindgen(unchecked_param_type = "ExtractItem[]")] items: JsValue,
+    on_progress: Option<js_sys::Function>,
+) -> Result<JsValue, JsError> {
+    let items: Vec<ExtractItem> = serde_wasm_bindgen::from_value(items)
+        .map_err(|e| JsError::new(&Msg::Deserialize.with(e)))?;
+    let total = items.len();
+    let mut outputs = Vec::with_capacity(total);
+    for (done, item) in items.iter().enumerate() {
+        outputs.push(extract_output(
+            &item.url,
+            ExtractorInput::HtmlPage(&item.html),
+        ));
+        if let Some(callback) = &on_progress {
+            let _ = callback.call2(
+                &JsValue::NULL,
+                &JsValue::from(done + 1),
+                &JsValue::from(total),
+            );
+        }
+        yield_now().await;
+    }
+    Ok(to_js(&outputs))
+}
+
+/// 运行时版本 (semver)
+#[wasm_bindgen]
+pub fn get_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+/// 运行时 ABI 标识, 为 `ExtractOutput` / `Submission` 等对外类型声明的哈希
+///
+/// 扩展可与其 JS 胶水代码构建时记录的值比对, 不一致时提示更新而非在调用时出错.
+#[wasm_bindgen]
+pub fn get_abi() -> String {
+    ABI.get_or_init(|| {
+        let mut hasher = blake3::Hasher::new();
+        for decl in [
+            ExtractOutput::DECL,
+            Submission::DECL,
+            PartialSubmission::DECL,
+            RemoteInfo::DECL,
+            SubmissionStatus::DECL,
+            SubmissionLanguage::DECL,
+            ExtractWarning::DECL,
+        ] {
+            hasher.update(decl.as_bytes());
+        }
+        hasher.finalize().to_hex()[..16].to_string()
+    })
+    .clone()
+}
+
+/// 模块加载时安装 panic hook, 将 panic 信息输出到控制台
+#[wasm_bindgen(start)]
+pub fn start() {
+    console_error_panic_hook::set_once();
+    init_logging();
+}
+
+/// 将 `tracing` 日志输出到浏览器控制台
+fn init_logging() {
+    let (filter, handle) = reload::Layer::new(DEFAULT_LOG_LEVEL);
+    let console = tracing_wasm::WASMLayer::new(
+        tracing_wasm::WASMLayerConfigBuilder::new()
+            .set_report_logs_in_timings(false)
+            .build(),
+    );
+    let subscriber = Registry::default().with(filter).with(console);
+    if tracing::subscriber::set_global_default(subscriber).is_ok() {
+        let _ = LOG_FILTER.set(handle);
+    }
+}
+
+/// 设置控制台日志级别, 可选 `off` / `error` / `warn` / `info` / `debug` / `trace`
+///
+/// 提交问题反馈前可调至 `debug` 以输出提取器选择与提取过程.
+#[wasm_bindgen]
+pub fn set_log_level(level: &str) -> Result<(), JsError> {
+    let filter: LevelFilter = level
+        .trim()
+        .parse()
+        .map_err(|_| JsError::new(&Msg::UnknownLogLevel.with(level)))?;
+    let handle = LOG_FILTER
+        .get()
+        .ok_or_else(|| JsError::new(Msg::LoggingNotInitialized.text()))?;
+    handle
+        .reload(filter)
+        .map_err(|e| JsError::new(&Msg::SetLogLevel.with(e)))
+}
+
+/// 安装提取器的选择器与正则覆盖, 形如 `{ "xyd": { "selectors": { "code": ".cm-line" }, "regexes": {} } }`
+///
+/// OJ 调整页面结构时, 扩展可下发配置热修复, 无需更新 wasm. 传入无效配置时保持原有覆盖不变.
+#[wasm_bindgen]
+pub fn load_overrides(json: &str) -> Result<(), JsError> {
+    extractor::load_overrides(json).map_err(|e| JsError::new(&Msg::Overrides.with(e)))
+}
+
+/// 清除全部选择器与正则覆盖
+#[wasm_bindgen]
+pub fn clear_overrides() {
+    extractor::clear_overrides();
+}
+
+/// 设置运行时消息的语言, 可选 `zh-CN` (默认) / `en`
+#[wasm_bindgen]
+pub fn set_locale(locale: &str) -> Result<(), JsError> {
+    let locale: Locale = locale
+        .parse()
+        .map_err(|_| JsError::new(&Msg::UnknownLocale.with(locale)))?;
+    i18n::set_locale(locale);
+    Ok(())
+}
+
+/// 当前运行时消息的语言
+#[wasm_bindgen]
+pub fn get_locale() -> String {
+    i18n::locale().as_str().to_string()
+}
+
+/// 计算代码指纹, 用于与已同步的提交比对
 #[wasm_bindgen]
-pub fn map_vjudge_submission(submission: &JsValue) -> JsValue {
+pub fn code_fingerprint(code: &str) -> String {
+    utils::code_fingerprint(code)
+}
+
+/// 设置状态 / 语言无法识别时的回调, 传入 null 清除
+///
+/// 回调参数形如 `{ kind: "unrecognized_status", detail: "..." }`.
+#[wasm_bindgen]
+pub fn set_observer(callback: Option<js_sys::Function>) {
+    let enabled = callback.is_some();
+    OBSERVER.with(|o| *o.borrow_mut() = callback);
+
+    if !enabled {
+        extractor::clear_observer();
+        return;
+    }
+
+    extractor::set_observer(|warning| {
+        let Some(callback) = OBSERVER.with(|o| o.borrow().clone()) else {
+            return;
+        };
+        if let Ok(value) = serde_wasm_bindgen::to_value(warning) {
+            let _ = callback.call1(&JsValue::NULL, &value);
+        }
+    });
+}
+
+/// 返回 `ExtractOutput` 的 JSON Schema
+#[cfg(feature = "schema")]
+#[wasm_bindgen]
+pub fn output_schema() -> String {
+    let schema = schemars::schema_for!(ExtractOutput);
+    serde_json::to_string_pretty(&schema).unwrap_or_else(|e| Msg::Serialize.with(e))
+}
+
+/// 返回 `Submission` 的 JSON Schema
+#[cfg(feature = "schema")]
+#[wasm_bindgen]
+pub fn submission_schema() -> String {
+    extractor::models::schema::submission_schema()
+}
+
+/// 列出已注册的提取器, 含名称、标签与 URL 匹配规则
+///
+/// 扩展可据此展示支持的 OJ, 并判断当前页面是否需要激活.
+#[wasm_bindgen(unchecked_return_type = "ExtractorInfo[]")]
+pub fn list_extractors() -> JsValue {
+    to_js(&extractor::list_extractors())
+}
+
+/// 运行全部提取器的自检样例, 可在启动时调用以提示页面结构已变化的提取器
+#[wasm_bindgen(unchecked_return_type = "VerifyResult[]")]
+pub fn verify_extractors() -> JsValue {
+    to_js(&extractor::verify_all())
+}
+
+/// 提取器对 URL 的匹配得分
+#[derive(Debug, Serialize, Tsify)]
+pub struct RankEntry {
+    pub name: String,
+    pub score: u32,
+}
+
+/// 全部提取器对 url 的得分, 按选择顺序排列, 用于排查页面未被识别的原因
+#[wasm_bindgen(unchecked_return_type = "RankEntry[]")]
+pub fn rank_url(url: &str) -> JsValue {
+    let ranks: Vec<RankEntry> = extractor::rank_all(url)
+        .into_iter()
+        .map(|(name, score)| RankEntry { name, score })
+        .collect();
+    to_js(&ranks)
+}
+
+/// 轻量识别页面所属 OJ、页面类型与记录编号, 供内容脚本决定是否显示同步按钮
+#[wasm_bindgen(unchecked_return_type = "PageInfo")]
+pub fn detect_page(url: &str, html: &str) -> JsValue {
+    to_js(&extractor::detect_page(url, html))
+}
+
+/// 返回编译期生成的提取器清单 (JSON), 供扩展生成匹配规则与界面
+#[wasm_bindgen]
+pub fn extractor_manifest() -> String {
+    extractor::extractors::MANIFEST.to_string()
+}
+
+/// 将 VJudge 的提取结果映射为可能的原始 OJ (如果适用)
+///
+/// `overrides` 为 `OjRule[]` 的 JSON, 仅在本次调用中优先于全局映射表, 供扩展热修复新出现的远程 OJ.
+#[wasm_bindgen(unchecked_return_type = "RemoteInfo | null")]
+pub fn map_vjudge_submission(
+    #[wasm_bindgen(unchecked_param_type = "Submission")] submission: &JsValue,
+    overrides: Option<String>,
+) -> JsValue {
     // 先将 JsValue 反序列化为 Submission
     let sub: Submission = match serde_wasm_bindgen::from_value(submission.clone()) {
         Ok(s) => s,
-        Err(e) => return JsValue::from_str(&format!("反序列化错误: {e}")),
+        Err(e) => return JsValue::from_str(&Msg::Deserialize.with(e)),
     };
 
-    match utils::map_vjudge_to_origin(&sub) {
-        Some((oj, pid, rid)) => serde_wasm_bindgen::to_value(&(oj, pid, rid))
-            .unwrap_or_else(|e| JsValue::from_str(&format!("序列化错误: {e}"))),
+    let remote = match overrides.as_deref() {
+        Some(json) => match OjMap::from_json(json) {
+            Ok(overrides) => {
+                let mut map = current_oj_map();
+                map.override_with(overrides);
+                utils::map_vjudge_to_origin_with(&sub, &map)
+            }
+            Err(e) => return JsValue::from_str(&Msg::OjMap.with(e)),
+        },
+        None => utils::map_vjudge_to_origin(&sub),
+    };
+
+    match remote {
+        Some(remote) => to_js(&remote),
         None => JsValue::NULL,
     }
 }
 
-/// 解析原始的 document.cookie 字符串和 origin 为结构化 Cookie 信息
-#[wasm_bindgen]
-pub fn parse_cookie(cookie_str: &str, origin: &str) -> JsValue {
-    let mut login = None;
-    let mut connect_sid = None;
+/// 将提取结果转换为 7fa4 提交接口的请求体
+#[wasm_bindgen(unchecked_return_type = "SubmitPayload")]
+pub fn to_7fa4_payload(
+    #[wasm_bindgen(unchecked_param_type = "Submission")] submission: &JsValue,
+    in_contest: bool,
+) -> JsValue {
+    let sub: Submission = match serde_wasm_bindgen::from_value(submission.clone()) {
+        Ok(s) => s,
+        Err(e) => return JsValue::from_str(&Msg::Deserialize.with(e)),
+    };
 
-    for part in cookie_str.split(';') {
-        let p = part.trim();
-        if p.is_empty() {
-            continue;
-        }
-        if let Some(idx) = p.find('=') {
-            let key = p[..idx].trim();
-            let value = p[idx + 1..].trim();
-            match key {
-                "login" => login = Some(value.to_string()),
-                "connect.sid" => connect_sid = Some(value.to_string()),
-                _ => (),
-            }
+    let payload = extractor::models::SubmitPayload::new(&sub, in_contest);
+    serde_wasm_bindgen::to_value(&payload)
+        .unwrap_or_else(|e| JsValue::from_str(&Msg::Serialize.with(e)))
+}
+
+/// 按 7fa4 规范整理提交: 规范化题号与提交 ID, 校验必填字段, 生成请求体与接口地址
+///
+/// `chost` 缺省时使用默认部署, 校验失败时抛出异常.
+#[wasm_bindgen(unchecked_return_type = "PreparedSubmission")]
+pub fn normalize_submission(
+    #[wasm_bindgen(unchecked_param_type = "Submission")] submission: JsValue,
+    chost: Option<String>,
+    in_contest: bool,
+) -> Result<JsValue, JsError> {
+    let sub: Submission = serde_wasm_bindgen::from_value(submission)
+        .map_err(|e| JsError::new(&Msg::InvalidSubmission.with(e)))?;
+    let prepared = PreparedSubmission::new(
+        &sub,
+        chost.as_deref().unwrap_or_default(),
+        in_contest,
+        &ValidationPolicy::default(),
+    )
+    .map_err(|e| JsError::new(&i18n::extract_error(&e.kind)))?;
+    Ok(to_js(&prepared))
+}
+
+/// 生成 7fa4 提交请求 `{ url, method, headers, body }`, 供需在内容脚本中发送请求的扩展直接 `fetch`
+///
+/// 提交内容经 [`normalize_submission`] 相同的规范化与校验, 校验失败时抛出异常.
+#[wasm_bindgen(unchecked_return_type = "SubmitRequest")]
+pub fn build_submit_request(
+    #[wasm_bindgen(unchecked_param_type = "Submission")] submission: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "CookieInfo")] cookies: JsValue,
+    in_contest: Option<bool>,
+) -> Result<JsValue, JsError> {
+    let sub: Submission = serde_wasm_bindgen::from_value(submission)
+        .map_err(|e| JsError::new(&Msg::InvalidSubmission.with(e)))?;
+    let cookies: CookieInfo = serde_wasm_bindgen::from_value(cookies)
+        .map_err(|e| JsError::new(&Msg::InvalidCookieInfo.with(e)))?;
+    let prepared = PreparedSubmission::new(
+        &sub,
+        cookies.chost.as_deref().unwrap_or_default(),
+        in_contest.unwrap_or(false),
+        &ValidationPolicy::default(),
+    )
+    .map_err(|e| JsError::new(&i18n::extract_error(&e.kind)))?;
+    let body = serde_json::to_string(&prepared.payload)
+        .map_err(|e| JsError::new(&Msg::Serialize.with(e)))?;
+
+    let headers = BTreeMap::from([
+        ("Content-Type".to_string(), "application/json".to_string()),
+        ("Cookie".to_string(), cookies.cookie_header()),
+    ]);
+    let request = SubmitRequest {
+        url: prepared.url,
+        method: "POST".to_string(),
+        headers,
+        body,
+    };
+    // 以普通对象而非 `Map` 表示请求头, 便于直接传给 `fetch`
+    request
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&Msg::Serialize.with(e)))
+}
+
+/// 将提交推送到 7fa4, 返回接口响应与新建记录的编号
+///
+/// 网络错误与超时时 Promise 被拒绝, 接口返回的业务错误记录在 `SubmitResponse` 中.
+#[wasm_bindgen(unchecked_return_type = "SubmitResponse")]
+pub async fn submit_to_7fa4(
+    #[wasm_bindgen(unchecked_param_type = "Submission")] submission: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "CookieInfo")] cookies: JsValue,
+    in_contest: bool,
+) -> Result<JsValue, JsError> {
+    let sub: Submission = serde_wasm_bindgen::from_value(submission)
+        .map_err(|e| JsError::new(&Msg::InvalidSubmission.with(e)))?;
+    let cookies: CookieInfo = serde_wasm_bindgen::from_value(cookies)
+        .map_err(|e| JsError::new(&Msg::InvalidCookieInfo.with(e)))?;
+    let chost = cookies.chost();
+    // 与 `normalize_submission` 相同的规范化与校验
+    let prepared = PreparedSubmission::new(&sub, chost, in_contest, &ValidationPolicy::default())
+        .map_err(|e| JsError::new(&i18n::extract_error(&e.kind)))?;
+    let body = serde_json::to_string(&prepared.payload)
+        .map_err(|e| JsError::new(&Msg::Serialize.with(e)))?;
+
+    let (status, text) = send(&prepared.url, &cookies, Some(&body)).await?;
+    tracing::debug!(chost, status, rid = %prepared.payload.rid, "submitted to 7fa4");
+    let response = SubmitResponse::from_body(status, &text);
+
+    #[cfg(feature = "history")]
+    if response.success {
+        let entry = history::HistoryEntry::new(&sub, response.record_id.clone());
+        if let Err(e) = history::record(&entry).await {
+            tracing::warn!("failed to record history: {e:?}");
         }
     }
 
-    let chost = if origin.contains("oj.7fa4.cn") {
-        Some("oj.7fa4.cn".to_string())
-    } else if origin.contains("jx.7fa4.cn") {
-        Some("jx.7fa4.cn:8888".to_string())
-    } else if origin.contains("in.7fa4.cn") {
-        Some("in.7fa4.cn:8888".to_string())
-    } else {
-        url::Url::parse(origin).ok().and_then(|u| {
-            u.host_str().map(|host| {
-                if let Some(port) = u.port() {
-                    format!("{host}:{port}")
-                } else {
-                    host.to_string()
-                }
-            })
-        })
-    };
+    Ok(to_js(&response))
+}
+
+/// 查询 7fa4 会话是否有效及所属用户, 用于在提交前提示重新登录
+#[wasm_bindgen(unchecked_return_type = "LoginStatus")]
+pub async fn check_login(
+    #[wasm_bindgen(unchecked_param_type = "CookieInfo")] cookies: JsValue,
+) -> Result<JsValue, JsError> {
+    let cookies: CookieInfo = serde_wasm_bindgen::from_value(cookies)
+        .map_err(|e| JsError::new(&Msg::InvalidCookieInfo.with(e)))?;
+    let chost = cookies.chost();
+
+    let (status, text) = send(&whoami_url(chost), &cookies, None).await?;
+    Ok(to_js(&LoginStatus::from_body(status, &text)))
+}
 
-    let ci = CookieInfo {
-        login,
-        connect_sid,
-        chost,
+/// IndexedDB 错误转换为 JS 异常
+#[cfg(feature = "history")]
+fn history_error(e: JsValue) -> JsError {
+    JsError::new(&Msg::History.with(format!("{e:?}")))
+}
+
+/// 记录一次同步, 相同 `(oj, rid)` 的旧记录被覆盖
+///
+/// `submit_to_7fa4` 成功时会自动记录, 扩展自行发送请求时可调用此函数.
+#[cfg(feature = "history")]
+#[wasm_bindgen]
+pub async fn history_record(
+    #[wasm_bindgen(unchecked_param_type = "Submission")] submission: JsValue,
+    record_id: Option<String>,
+) -> Result<(), JsError> {
+    let sub: Submission = serde_wasm_bindgen::from_value(submission)
+        .map_err(|e| JsError::new(&Msg::InvalidSubmission.with(e)))?;
+    history::record(&history::HistoryEntry::new(&sub, record_id))
+        .await
+        .map_err(history_error)
+}
+
+/// 查询提交是否已同步, 用于显示 "已同步" 标记
+#[cfg(feature = "history")]
+#[wasm_bindgen(unchecked_return_type = "HistoryEntry | null")]
+pub async fn history_get(oj: String, rid: String) -> Result<JsValue, JsError> {
+    let entry = history::get(&oj, &rid).await.map_err(history_error)?;
+    Ok(entry.map_or(JsValue::NULL, |entry| to_js(&entry)))
+}
+
+/// 全部同步记录
+#[cfg(feature = "history")]
+#[wasm_bindgen(unchecked_return_type = "HistoryEntry[]")]
+pub async fn history_list() -> Result<JsValue, JsError> {
+    let entries = history::list().await.map_err(history_error)?;
+    Ok(to_js(&entries))
+}
+
+/// 清空同步记录
+#[cfg(feature = "history")]
+#[wasm_bindgen]
+pub async fn history_clear() -> Result<(), JsError> {
+    history::clear().await.map_err(history_error)
+}
+
+/// 发送携带凭据的请求, 返回状态码与响应文本
+///
+/// 有请求体时以 JSON POST 发送, 否则为 GET.
+async fn send(
+    url: &str,
+    cookies: &CookieInfo,
+    body: Option<&str>,
+) -> Result<(u16, String), JsError> {
+    let request = build_request(url, cookies, body)
+        .map_err(|e| JsError::new(&Msg::BuildRequest.with(format!("{e:?}"))))?;
+    let response = JsFuture::from(fetch_with_request(&request))
+        .await
+        .map_err(|e| match e.dyn_ref::<js_sys::Error>() {
+            Some(err) if err.name() == "TimeoutError" => JsError::new(&format!(
+                "{} ({}s)",
+                Msg::RequestTimeout.text(),
+                REQUEST_TIMEOUT_MS / 1000
+            )),
+            _ => JsError::new(&Msg::RequestFailed.with(format!("{e:?}"))),
+        })?
+        .unchecked_into::<web_sys::Response>();
+    let text = match response.text() {
+        Ok(promise) => JsFuture::from(promise)
+            .await
+            .ok()
+            .and_then(|t| t.as_string()),
+        Err(_) => None,
     };
+    Ok((response.status(), text.unwrap_or_default()))
+}
+
+/// 构建携带凭据的请求
+fn build_request(
+    url: &str,
+    cookies: &CookieInfo,
+    body: Option<&str>,
+) -> Result<web_sys::Request, JsValue> {
+    let headers = web_sys::Headers::new()?;
+    // 浏览器会忽略页面设置的 Cookie 头, 此时依赖 credentials 携带站点 cookie
+    headers.set("Cookie", &cookies.cookie_header())?;
+
+    let init = web_sys::RequestInit::new();
+    if let Some(body) = body {
+        headers.set("Content-Type", "application/json")?;
+        init.set_method("POST");
+        init.set_body(&JsValue::from_str(body));
+    } else {
+        init.set_method("GET");
+    }
+    init.set_headers(&headers);
+    init.set_credentials(web_sys::RequestCredentials::Include);
+    init.set_signal(Some(&web_sys::AbortSignal::timeout_with_u32(
+        REQUEST_TIMEOUT_MS,
+    )));
+    web_sys::Request::new_with_str_and_init(url, &init)
+}
+
+/// 载入 7fa4 部署表, 形如 `[{ host, port?, api_base? }]`, 新部署优先于内置部署
+///
+/// 镜像与测试部署可由此配置 `chost` 与接口地址, 无需重新编译.
+#[wasm_bindgen]
+pub fn load_deployments(json: &str) -> Result<(), JsError> {
+    extractor::models::load_deployments(json).map_err(|e| JsError::new(&Msg::Deployments.with(e)))
+}
+
+/// 当前生效的 7fa4 部署表
+#[wasm_bindgen(unchecked_return_type = "Deployment[]")]
+pub fn list_deployments() -> JsValue {
+    let deployments: Vec<_> = extractor::models::deployment::current_deployments()
+        .iter()
+        .cloned()
+        .collect();
+    to_js(&deployments)
+}
+
+/// 解析原始的 document.cookie 字符串和 origin 为结构化 Cookie 信息
+#[wasm_bindgen(unchecked_return_type = "CookieInfo")]
+pub fn parse_cookie(cookie_str: &str, origin: &str) -> JsValue {
+    to_js(&CookieInfo::parse(cookie_str, origin))
+}
 
-    serde_wasm_bindgen::to_value(&ci)
-        .unwrap_or_else(|e| JsValue::from_str(&format!("序列化错误: {e}")))
+/// 按来源站点的 cookie 声明解析登录凭据, 未知站点返回 `null`
+#[wasm_bindgen(unchecked_return_type = "Credential | null")]
+pub fn parse_credential(cookie_str: &str, origin: &str) -> JsValue {
+    match Credential::parse(cookie_str, origin) {
+        Some(credential) => to_js(&credential),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[cfg(feature = "vjudge")]
+    #[wasm_bindgen_test]
+    fn test_relative_time() {
+        let html = r#"
+            <div id="info-panel"><table><tbody>
+                <tr><th>提交时间</th><td>3 分钟前</td></tr>
+            </tbody></table></div>
+        "#;
+        let output = extract_output(
+            "https://vjudge.net/solution/1",
+            ExtractorInput::HtmlPage(html),
+        );
+        let partial = output.partial.expect("partial submission");
+        let submitted_at = partial.submitted_at.expect("resolved relative time");
+        let expected = js_sys::Date::now() as u64 - 3 * 60_000;
+        assert!(submitted_at.abs_diff(expected) < 60_000);
+    }
 }
@@ -0,0 +1,331 @@
+//! 7fa4 提交客户端
+//!
+//! 以 [`CookieInfo`] 登录, 将 [`Submission`] 按 7fa4 规范整理后推送到 `/foreign_oj` 接口.
+//! 同一客户端内已推送的 `(oj, rid)` 不会重复提交.
+
+/*
+ * Copyright (c) 2025 fltLi
+ *
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use extractor::ValidationPolicy;
+use extractor::error::ExtractError;
+use extractor::models::{
+    CookieInfo, LoginStatus, PreparedSubmission, Submission, SubmitResponse, whoami_url,
+};
+use fetcher::RateLimiter;
+use reqwest::header::{CONTENT_TYPE, COOKIE};
+use serde::{Deserialize, Serialize};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// 请求 7fa4 的默认超时 (秒)
+pub const DEFAULT_TIMEOUT_SECS: u64 = 8;
+
+/// 提交客户端配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubmitterConfig {
+    /// 请求超时 (秒)
+    pub timeout_secs: u64,
+    /// 相邻两次提交的最小间隔 (毫秒), 0 为不限制
+    pub min_interval_ms: u64,
+}
+
+impl Default for SubmitterConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            min_interval_ms: 0,
+        }
+    }
+}
+
+/// 提交错误, 接口返回的业务错误见 [`SubmitOutcome::Rejected`]
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("no 7fa4 session cookie, fetch login info on a 7fa4 page first")]
+    MissingCredentials,
+    #[error("invalid submission: {0}")]
+    Invalid(#[from] ExtractError),
+    #[error("request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+impl Error {
+    /// 稳定的字符串错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::MissingCredentials => "missing_credentials",
+            Error::Invalid(e) => e.kind.code(),
+            Error::Request(e) if e.is_timeout() => "timeout",
+            Error::Request(_) => "request",
+            Error::Serialize(_) => "serialize",
+        }
+    }
+}
+
+/// 单次提交的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    /// 7fa4 已接收
+    Submitted(SubmitResponse),
+    /// 同一 `(oj, rid)` 已推送过或正在推送, 未发送请求
+    Duplicate { oj: String, rid: String },
+    /// 接口拒绝, 如会话过期或题目不存在
+    Rejected(SubmitResponse),
+}
+
+impl SubmitOutcome {
+    pub fn is_submitted(&self) -> bool {
+        matches!(self, SubmitOutcome::Submitted(_))
+    }
+
+    /// 新建记录的编号
+    pub fn record_id(&self) -> Option<&str> {
+        match self {
+            SubmitOutcome::Submitted(response) => response.record_id.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// 7fa4 提交客户端
+#[derive(Debug)]
+pub struct Submitter {
+    http: reqwest::Client,
+    cookies: CookieInfo,
+    policy: ValidationPolicy,
+    limiter: RateLimiter,
+    /// 已推送的 `(oj, rid)`
+    submitted: Mutex<HashSet<(String, String)>>,
+}
+
+impl Submitter {
+    /// 以 7fa4 登录信息创建, 不含会话 cookie 时报错
+    pub fn new(cookies: CookieInfo) -> Result<Self> {
+        Self::from_config(cookies, &SubmitterConfig::default())
+    }
+
+    /// 按配置创建
+    pub fn from_config(cookies: CookieInfo, config: &SubmitterConfig) -> Result<Self> {
+        if !cookies.has_session() {
+            return Err(Error::MissingCredentials);
+        }
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()?;
+        Ok(Self {
+            http,
+            cookies,
+            policy: ValidationPolicy::default(),
+            limiter: RateLimiter::new(Duration::from_millis(config.min_interval_ms)),
+            submitted: Mutex::default(),
+        })
+    }
+
+    /// 设置提交前的校验策略
+    pub fn with_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 预置已推送的 `(oj, rid)`, 如上次运行保存的记录
+    pub fn with_submitted(self, keys: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.submitted.lock().unwrap().extend(keys);
+        self
+    }
+
+    /// 提交接口主机
+    pub fn chost(&self) -> &str {
+        self.cookies.chost()
+    }
+
+    /// 已推送的 `(oj, rid)`
+    pub fn submitted(&self) -> Vec<(String, String)> {
+        self.submitted.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// 查询会话是否有效及所属用户
+    pub async fn check_login(&self) -> Result<LoginStatus> {
+        let response = self
+            .http
+            .get(whoami_url(self.chost()))
+            .header(COOKIE, self.cookies.cookie_header())
+            .send()
+            .await?;
+        let status = response.status().as_u16();
+        Ok(LoginStatus::from_body(status, &response.text().await?))
+    }
+
+    /// 规范化并校验, 生成请求地址与请求体
+    pub fn prepare(&self, sub: &Submission, in_contest: bool) -> Result<PreparedSubmission> {
+        Ok(PreparedSubmission::new(
+            sub,
+            self.chost(),
+            in_contest,
+            &self.policy,
+        )?)
+    }
+
+    /// 推送提交, 已推送过的 `(oj, rid)` 直接返回 [`SubmitOutcome::Duplicate`]
+    ///
+    /// 相邻两次提交按配置限速.
+    pub async fn submit(&self, sub: &Submission, in_contest: bool) -> Result<SubmitOutcome> {
+        let prepared = self.prepare(sub, in_contest)?;
+        let key = (prepared.payload.oj.clone(), prepared.payload.rid.clone());
+        // 发送前先占用, 避免并发提交同一记录时都通过检查
+        if !self.submitted.lock().unwrap().insert(key.clone()) {
+            let (oj, rid) = key;
+            return Ok(SubmitOutcome::Duplicate { oj, rid });
+        }
+
+        let outcome = self.send(&prepared).await;
+        if !matches!(outcome, Ok(SubmitOutcome::Submitted(_))) {
+            self.submitted.lock().unwrap().remove(&key);
+        }
+        outcome
+    }
+
+    async fn send(&self, prepared: &PreparedSubmission) -> Result<SubmitOutcome> {
+        self.limiter.wait(self.chost()).await;
+        let response = self
+            .http
+            .post(&prepared.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(COOKIE, self.cookies.cookie_header())
+            .body(serde_json::to_string(&prepared.payload)?)
+            .send()
+            .await?;
+        let status = response.status().as_u16();
+        let response = SubmitResponse::from_body(status, &response.text().await?);
+        let payload = &prepared.payload;
+        tracing::debug!(chost = self.chost(), status, oj = %payload.oj, rid = %payload.rid, "submitted to 7fa4");
+
+        if !response.success {
+            return Ok(SubmitOutcome::Rejected(response));
+        }
+        Ok(SubmitOutcome::Submitted(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use extractor::models::{SubmissionLanguage, SubmissionStatus};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 以给定响应体应答一次请求, 返回 `chost` 与收到的请求
+    async fn serve(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let chost = listener.local_addr().unwrap().to_string();
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            // 请求体在请求头之后, 以结尾的 `}` 判断接收完毕
+            while !request.ends_with(b"}") {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (chost, handle)
+    }
+
+    fn sample() -> Submission {
+        Submission {
+            code: "int main() {}".to_string(),
+            pid: "p4198".to_string(),
+            rid: "241494617".to_string(),
+            oj: "luogu".to_string(),
+            language: SubmissionLanguage::Cpp17,
+            status: SubmissionStatus::Accepted,
+            score: Some(100),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit() {
+        let (chost, server) = serve(r#"{"success":true,"data":{"id":1024}}"#).await;
+        let submitter = Submitter::new(CookieInfo {
+            login: Some("u".to_string()),
+            connect_sid: Some("s".to_string()),
+            chost: Some(chost),
+        })
+        .unwrap();
+
+        let outcome = submitter.submit(&sample(), false).await.unwrap();
+        assert_eq!(outcome.record_id(), Some("1024"));
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /foreign_oj "));
+        assert!(request.contains("login=u; connect.sid=s"));
+        assert!(request.contains(r#""pid":"P4198""#));
+
+        // 未启动新的服务, 重复提交不应发送请求
+        let outcome = submitter.submit(&sample(), false).await.unwrap();
+        assert!(matches!(outcome, SubmitOutcome::Duplicate { rid, .. } if rid == "241494617"));
+    }
+
+    #[tokio::test]
+    async fn test_submit_concurrent() {
+        let (chost, server) = serve(r#"{"success":false,"error":"no such problem"}"#).await;
+        let submitter = Submitter::new(CookieInfo {
+            login: Some("u".to_string()),
+            connect_sid: Some("s".to_string()),
+            chost: Some(chost),
+        })
+        .unwrap();
+
+        // 并发提交同一记录只发送一次请求
+        let sample = sample();
+        let (first, second) = tokio::join!(
+            submitter.submit(&sample, false),
+            submitter.submit(&sample, false)
+        );
+        assert!(matches!(first.unwrap(), SubmitOutcome::Rejected(_)));
+        assert!(matches!(second.unwrap(), SubmitOutcome::Duplicate { .. }));
+        server.await.unwrap();
+
+        // 被拒绝的记录不占用, 可以重试
+        assert!(submitter.submitted().is_empty());
+    }
+
+    #[test]
+    fn test_prepare() {
+        let err = Submitter::new(CookieInfo::default()).unwrap_err();
+        assert_eq!(err.code(), "missing_credentials");
+
+        let submitter = Submitter::new(CookieInfo {
+            login: Some("u".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+        .with_submitted([("luogu".to_string(), "1".to_string())]);
+        assert_eq!(submitter.chost(), "oj.7fa4.cn");
+        assert_eq!(submitter.submitted().len(), 1);
+
+        let empty = Submission {
+            code: String::new(),
+            ..sample()
+        };
+        let err = submitter.prepare(&empty, false).unwrap_err();
+        assert_eq!(err.code(), "missing_field");
+    }
+}